@@ -10,13 +10,13 @@
 // If not, see <https://opensource.org/licenses/MIT>.
 
 use core::cmp::Ordering;
-use core::convert::TryFrom;
 use core::ops::{Neg, Rem};
 
 use amplify::num::apfloat::{ieee, Float};
+use amplify::num::u1024;
 use half::bf16;
 
-use super::{FloatLayout, IntLayout, Layout, Number, NumberLayout};
+use super::{FloatLayout, IntLayout, Layout, Number, NumberLayout, Posit512};
 use crate::data::MaybeNumber;
 use crate::isa::{IntFlags, RoundingFlag};
 
@@ -65,11 +65,11 @@ impl Ord for Number {
             Layout::Float(FloatLayout::IeeeQuad) => ieee::Quad::from(self)
                 .partial_cmp(&ieee::Quad::from(other))
                 .expect("number value contains NaN"),
-            Layout::Float(FloatLayout::IeeeOct) => {
-                unimplemented!("IEEE-754 256-bit floats are not yet supported")
-            }
+            Layout::Float(FloatLayout::IeeeOct) => ieee::Oct::from(self)
+                .partial_cmp(&ieee::Oct::from(other))
+                .expect("number value contains NaN"),
             Layout::Float(FloatLayout::FloatTapered) => {
-                unimplemented!("512-bit tapered floats are not yet supported")
+                Posit512::from(self).cmp(&Posit512::from(other))
             }
         }
     }
@@ -83,17 +83,20 @@ impl Number {
         match self.layout() {
             Layout::Integer(_) => self.cmp(other),
             Layout::Float(FloatLayout::FloatTapered) => {
-                unimplemented!("512-bit tapered floats are not yet supported")
+                // Tapered floats have no fixed significand, but the least significant bit of the
+                // encoding always belongs to it
+                let last_bit = Number::masked_bit(0, self.layout());
+                (*self | last_bit).cmp(&(*other | last_bit))
             }
             Layout::Float(float_layout) => {
                 let last_bit = Number::masked_bit(
                     float_layout
                         .significand_pos()
                         .expect("non-tapered float layout does not provides significand position")
-                        .end,
+                        .start,
                     self.layout(),
                 );
-                (*self ^ last_bit).cmp(&(*other ^ last_bit))
+                (*self | last_bit).cmp(&(*other | last_bit))
             }
         }
     }
@@ -122,13 +125,13 @@ impl Number {
                 .checked_add(rhs.to_i1024_bytes())
                 .map(Number::from)
                 .and_then(|n| n.reshaped(Layout::signed(n.layout().bytes()), true))
-                .and_then(|mut n| (n.reshape(Layout::signed(bytes)) || flags.wrap).then(|| n)),
+                .and_then(|mut n| (n.reshape(Layout::signed(bytes)) || flags.wrap).then_some(n)),
             (Layout::Integer(IntLayout { bytes, .. }), false) => self
                 .to_u1024_bytes()
                 .checked_add(rhs.to_u1024_bytes())
                 .map(Number::from)
                 .and_then(|n| n.reshaped(Layout::unsigned(n.layout().bytes()), true))
-                .and_then(|mut n| (n.reshape(Layout::unsigned(bytes)) || flags.wrap).then(|| n)),
+                .and_then(|mut n| (n.reshape(Layout::unsigned(bytes)) || flags.wrap).then_some(n)),
             (Layout::Float(_), _) => panic!("integer addition of float numbers"),
         }
     }
@@ -150,13 +153,13 @@ impl Number {
                 .checked_sub(rhs.to_i1024_bytes())
                 .map(Number::from)
                 .and_then(|n| n.reshaped(Layout::signed(n.layout().bytes()), true))
-                .and_then(|mut n| (n.reshape(Layout::signed(bytes)) || flags.wrap).then(|| n)),
+                .and_then(|mut n| (n.reshape(Layout::signed(bytes)) || flags.wrap).then_some(n)),
             (Layout::Integer(IntLayout { bytes, .. }), false) => self
                 .to_u1024_bytes()
                 .checked_sub(rhs.to_u1024_bytes())
                 .map(Number::from)
                 .and_then(|n| n.reshaped(Layout::unsigned(n.layout().bytes()), true))
-                .and_then(|mut n| (n.reshape(Layout::unsigned(bytes)) || flags.wrap).then(|| n)),
+                .and_then(|mut n| (n.reshape(Layout::unsigned(bytes)) || flags.wrap).then_some(n)),
             (Layout::Float(_), _) => panic!("integer subtraction of float numbers"),
         }
     }
@@ -178,13 +181,13 @@ impl Number {
                 .checked_mul(rhs.to_i1024_bytes())
                 .map(Number::from)
                 .and_then(|n| n.reshaped(Layout::signed(n.layout().bytes()), true))
-                .and_then(|mut n| (n.reshape(Layout::signed(bytes)) || flags.wrap).then(|| n)),
+                .and_then(|mut n| (n.reshape(Layout::signed(bytes)) || flags.wrap).then_some(n)),
             (Layout::Integer(IntLayout { bytes, .. }), false) => self
                 .to_u1024_bytes()
                 .checked_mul(rhs.to_u1024_bytes())
                 .map(Number::from)
                 .and_then(|n| n.reshaped(Layout::unsigned(n.layout().bytes()), true))
-                .and_then(|mut n| (n.reshape(Layout::unsigned(bytes)) || flags.wrap).then(|| n)),
+                .and_then(|mut n| (n.reshape(Layout::unsigned(bytes)) || flags.wrap).then_some(n)),
             (Layout::Float(_), _) => panic!("integer multiplication of float numbers"),
         }
     }
@@ -223,7 +226,7 @@ impl Number {
                 .to_u1024_bytes()
                 .checked_div(rhs.to_u1024_bytes())
                 .map(Number::from)
-                .and_then(|n| n.reshaped(Layout::signed(bytes), false)),
+                .and_then(|n| n.reshaped(Layout::unsigned(bytes), false)),
             (Layout::Float(_), _) => panic!("integer division of float numbers"),
         }
     }
@@ -257,7 +260,9 @@ impl Number {
             Layout::Float(FloatLayout::IeeeOct) => {
                 ieee::Oct::from(self).add_r(rhs.into(), flag.into()).into()
             }
-            Layout::Float(FloatLayout::FloatTapered) => todo!("(#5) tapered float addition"),
+            Layout::Float(FloatLayout::FloatTapered) => {
+                Posit512::from(self).add_r(rhs.into(), flag.into()).into()
+            }
             Layout::Integer(_) => panic!("float addition of integer numbers"),
         }
     }
@@ -291,7 +296,9 @@ impl Number {
             Layout::Float(FloatLayout::IeeeOct) => {
                 ieee::Oct::from(self).sub_r(rhs.into(), flag.into()).into()
             }
-            Layout::Float(FloatLayout::FloatTapered) => todo!("(#5) tapered float subtraction"),
+            Layout::Float(FloatLayout::FloatTapered) => {
+                Posit512::from(self).sub_r(rhs.into(), flag.into()).into()
+            }
             Layout::Integer(_) => panic!("float subtraction of integer numbers"),
         }
     }
@@ -325,7 +332,9 @@ impl Number {
             Layout::Float(FloatLayout::IeeeOct) => {
                 ieee::Oct::from(self).mul_r(rhs.into(), flag.into()).into()
             }
-            Layout::Float(FloatLayout::FloatTapered) => todo!("(#5) tapered float multiplication"),
+            Layout::Float(FloatLayout::FloatTapered) => {
+                Posit512::from(self).mul_r(rhs.into(), flag.into()).into()
+            }
             Layout::Integer(_) => panic!("float multiplication of integer numbers"),
         }
     }
@@ -359,7 +368,9 @@ impl Number {
             Layout::Float(FloatLayout::IeeeOct) => {
                 ieee::Oct::from(self).div_r(rhs.into(), flag.into()).into()
             }
-            Layout::Float(FloatLayout::FloatTapered) => todo!("(#5) tapered float division"),
+            Layout::Float(FloatLayout::FloatTapered) => {
+                Posit512::from(self).div_r(rhs.into(), flag.into()).into()
+            }
             Layout::Integer(_) => panic!("float division of integer numbers"),
         }
     }
@@ -392,6 +403,12 @@ impl Number {
                 // applied to unsigned integer layout
                 None
             }
+            Layout::Float(FloatLayout::FloatTapered) => {
+                // Tapered floats use two's complement encoding for negative values
+                let val = Posit512::from(self);
+                let val = if val.is_negative() != sign.into() { -val } else { val };
+                MaybeNumber::from(val).into()
+            }
             Layout::Float(..) => {
                 let sign_byte = layout.sign_byte();
                 if sign.into() {
@@ -424,8 +441,8 @@ impl Rem for Number {
                 val1.rem(val2).into()
             }
             Layout::Integer(IntLayout { signed: false, .. }) if layout.bits() <= 128 => {
-                let val1 = i128::try_from(self).expect("integer layout is broken");
-                let val2 = i128::try_from(rhs).expect("integer layout is broken");
+                let val1 = i128::from(self);
+                let val2 = i128::from(rhs);
                 val1.rem(val2).into()
             }
             Layout::Integer(IntLayout { .. }) => {
@@ -453,6 +470,137 @@ impl Number {
     }
 }
 
+impl Number {
+    /// Modular addition `(self + rhs) mod modulo` of unsigned integers.
+    ///
+    /// Returns `None` if the modulo is zero. The operation never overflows since the intermediate
+    /// value is computed with an additional carry bit.
+    ///
+    /// # Panics
+    ///
+    /// - if applied to float number layouts
+    /// - if numbers in arguments has different layout.
+    pub fn add_mod(self, rhs: Self, modulo: Self) -> Option<Number> {
+        self.mod_op(rhs, modulo, "addition", |a, b, m| Some(u1024_add_mod(a % m, b % m, m)))
+    }
+
+    /// Modular multiplication `(self * rhs) mod modulo` of unsigned integers.
+    ///
+    /// Returns `None` if the modulo is zero. The operation never overflows since the intermediate
+    /// value is computed at the double width of the operands.
+    ///
+    /// # Panics
+    ///
+    /// - if applied to float number layouts
+    /// - if numbers in arguments has different layout.
+    pub fn mul_mod(self, rhs: Self, modulo: Self) -> Option<Number> {
+        self.mod_op(rhs, modulo, "multiplication", |a, b, m| Some(u1024_mul_mod(a, b, m)))
+    }
+
+    /// Modular exponentiation `(self ^ exp) mod modulo` of unsigned integers.
+    ///
+    /// Returns `None` if the modulo is zero.
+    ///
+    /// # Panics
+    ///
+    /// - if applied to float number layouts
+    /// - if numbers in arguments has different layout.
+    pub fn pow_mod(self, exp: Self, modulo: Self) -> Option<Number> {
+        self.mod_op(exp, modulo, "exponentiation", |a, e, m| Some(u1024_pow_mod(a, e, m)))
+    }
+
+    /// Modular multiplicative inverse `self^-1 mod modulo` of an unsigned integer.
+    ///
+    /// Returns `None` if the modulo is zero or if the inverse does not exist, i.e. when the
+    /// number and the modulo are not coprime.
+    ///
+    /// # Panics
+    ///
+    /// - if applied to float number layouts
+    /// - if numbers in arguments has different layout.
+    pub fn inv_mod(self, modulo: Self) -> Option<Number> {
+        self.mod_op(self, modulo, "inversion", |a, _, m| u1024_inv_mod(a, m))
+    }
+
+    fn mod_op(
+        self,
+        rhs: Self,
+        modulo: Self,
+        name: &str,
+        op: impl FnOnce(u1024, u1024, u1024) -> Option<u1024>,
+    ) -> Option<Number> {
+        let layout = self.layout();
+        assert_eq!(layout, rhs.layout(), "modular {} of numbers with different layout", name);
+        assert_eq!(layout, modulo.layout(), "modular {} of numbers with different layout", name);
+        let bytes = match layout {
+            Layout::Integer(IntLayout { bytes, .. }) => bytes,
+            Layout::Float(_) => panic!("modular {} of float numbers", name),
+        };
+        if modulo.is_zero() {
+            return None;
+        }
+        op(self.to_u1024_bytes(), rhs.to_u1024_bytes(), modulo.to_u1024_bytes())
+            .map(Number::from)
+            .and_then(|n| n.reshaped(Layout::unsigned(bytes), false))
+    }
+}
+
+/// Computes `(a + b) mod m` for `a, b < m` using the carry bit as an extra 1025th bit.
+fn u1024_add_mod(a: u1024, b: u1024, m: u1024) -> u1024 {
+    let (sum, carry) = a.overflowing_add(b);
+    if carry || sum >= m {
+        sum.wrapping_sub(m)
+    } else {
+        sum
+    }
+}
+
+fn u1024_mul_mod(a: u1024, b: u1024, m: u1024) -> u1024 {
+    let (a, b) = (a % m, b % m);
+    if a.bits_required() + b.bits_required() <= 1024 {
+        return a * b % m;
+    }
+    // Double-and-add, keeping the intermediate value below the modulo
+    let mut res = u1024::ZERO;
+    for bit in (0..b.bits_required()).rev() {
+        res = u1024_add_mod(res, res, m);
+        if b.bit(bit) {
+            res = u1024_add_mod(res, a, m);
+        }
+    }
+    res
+}
+
+fn u1024_pow_mod(base: u1024, exp: u1024, m: u1024) -> u1024 {
+    let base = base % m;
+    let mut res = u1024::ONE % m;
+    for bit in (0..exp.bits_required()).rev() {
+        res = u1024_mul_mod(res, res, m);
+        if exp.bit(bit) {
+            res = u1024_mul_mod(res, base, m);
+        }
+    }
+    res
+}
+
+/// Extended Euclidean algorithm. Since the signs of Bézout coefficients alternate, only their
+/// magnitudes are tracked, which never exceed the modulo.
+fn u1024_inv_mod(a: u1024, m: u1024) -> Option<u1024> {
+    let (mut r0, mut r1) = (m, a % m);
+    let (mut t0, mut t1) = (u1024::ZERO, u1024::ONE);
+    let mut negative = true;
+    while !r1.is_zero() {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 + q * t1);
+        negative = !negative;
+    }
+    if r0 != u1024::ONE {
+        return None;
+    }
+    Some(if negative { (m - t0) % m } else { t0 % m })
+}
+
 #[cfg(test)]
 mod tests {
     use core::str::FromStr;
@@ -469,10 +617,22 @@ mod tests {
         assert!(x < y);
         let x = Number::from(1);
         let y = Number::from(-1);
-        assert_eq!(true, x > y);
+        assert!(x > y);
         let x = Number::from(-128i8);
         let y = Number::from(-127i8);
-        assert_eq!(true, x < y);
+        assert!(x < y);
+    }
+
+    #[test]
+    fn compare_oct() {
+        let x = MaybeNumber::from(ieee::Oct::from_str("0x1p+0").unwrap()).unwrap();
+        let y = MaybeNumber::from(ieee::Oct::from_str("-0x1p+1").unwrap()).unwrap();
+        assert!(x > y);
+        assert_eq!(x.cmp(&x), Ordering::Equal);
+
+        let z = MaybeNumber::from(ieee::Oct::from_str("0x1p+0").unwrap().next_up().value).unwrap();
+        assert!(x < z);
+        assert_eq!(x.rounding_cmp(&z), Ordering::Equal);
     }
 
     #[test]
@@ -551,6 +711,13 @@ mod tests {
         assert_eq!(x.int_mul(y, IntFlags { signed: true, wrap: true }), Some(z));
     }
 
+    #[test]
+    fn int_div_unsigned_top_bit() {
+        let x = Number::from(200u8);
+        let y = Number::from(1u8);
+        assert_eq!(x.int_div(y, IntFlags { signed: false, wrap: false }), Some(x));
+    }
+
     #[test]
     fn int_div() {
         let x = Number::from(6);
@@ -751,4 +918,32 @@ mod tests {
         let z = MaybeNumber::from(bf16::INFINITY);
         assert_eq!(x.float_div(y, RoundingFlag::Ceil), z);
     }
+
+    #[test]
+    fn mod_arithm() {
+        let a = Number::from(7u8);
+        let b = Number::from(5u8);
+        let m = Number::from(9u8);
+        assert_eq!(a.add_mod(b, m), Some(Number::from(3u8)));
+        assert_eq!(a.mul_mod(b, m), Some(Number::from(8u8)));
+        assert_eq!(a.pow_mod(b, m), Some(Number::from(4u8)));
+        assert_eq!(a.inv_mod(m), Some(Number::from(4u8)));
+        assert_eq!(Number::from(3u8).inv_mod(m), None);
+        assert_eq!(Number::from(0u8).pow_mod(Number::from(0u8), m), Some(Number::from(1u8)));
+        assert_eq!(a.pow_mod(b, Number::from(1u8)), Some(Number::from(0u8)));
+        assert_eq!(a.inv_mod(Number::from(1u8)), Some(Number::from(0u8)));
+        assert_eq!(a.add_mod(b, Number::from(0u8)), None);
+        assert_eq!(a.inv_mod(Number::from(0u8)), None);
+    }
+
+    #[test]
+    fn mod_arithm_no_overflow() {
+        // Operands equal to `-1` modulo `2^1024 - 1`
+        let m = Number::from(u1024::MAX);
+        let a = Number::from(u1024::MAX - 1u8);
+        assert_eq!(a.add_mod(a, m), Some(Number::from(u1024::MAX - 2u8)));
+        assert_eq!(a.mul_mod(a, m), Some(Number::from(u1024::ONE)));
+        assert_eq!(a.pow_mod(Number::from(u1024::from(3u8)), m), Some(a));
+        assert_eq!(a.inv_mod(m), Some(a));
+    }
 }
@@ -9,7 +9,6 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use core::convert::TryFrom;
 use core::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
 
 use amplify::num::{i1024, u1024};
@@ -69,7 +68,7 @@ impl Shl for Number {
     fn shl(self, rhs: Self) -> Self::Output {
         let layout = self.layout();
         assert!(layout.is_integer(), "bit shifting float number");
-        let rhs = u16::try_from(rhs).expect("attempt to bitshift lhs for more than 2^16 bits");
+        let rhs = u16::from(rhs);
         let mut n = match layout.is_signed_int() {
             true => {
                 Number::from(self.to_i1024_bytes().checked_shl(rhs as u32).unwrap_or(i1024::ZERO))
@@ -90,7 +89,7 @@ impl Shr for Number {
     fn shr(self, rhs: Self) -> Self::Output {
         let layout = self.layout();
         assert!(layout.is_integer(), "bit shifting float number");
-        let rhs = u16::try_from(rhs).expect("attempt to bitshift right for more than 2^16 bits");
+        let rhs = u16::from(rhs);
         let mut n = match layout.is_signed_int() {
             true => {
                 Number::from(self.to_i1024_bytes().checked_shr(rhs as u32).unwrap_or(i1024::ZERO))
@@ -111,9 +110,7 @@ impl Number {
         let bits = self.len() * 8;
         let lhs = self.into_unsigned();
         assert!(layout.is_integer(), "bit shifting float number");
-        let excess = u16::try_from(shift).map(|v| v % bits).expect(
-            "shift value in `scl` operation must always be from either `a8` or `a16` registry",
-        );
+        let excess = u16::from(shift) % bits;
         let residue = lhs >> Number::from(bits - excess);
         ((lhs << Number::from(excess)) | residue).reshaped(layout, true).expect("restoring layout")
     }
@@ -124,9 +121,7 @@ impl Number {
         let bits = self.len() * 8;
         let lhs = self.into_unsigned();
         assert!(layout.is_integer(), "bit shifting float number");
-        let excess = u16::try_from(shift).map(|v| v % bits).expect(
-            "shift value in `scl` operation must always be from either `a8` or `a16` registry",
-        );
+        let excess = u16::from(shift) % bits;
         let residue = lhs << Number::from(bits - excess);
         ((lhs >> Number::from(excess)) | residue).reshaped(layout, true).expect("restoring layout")
     }
@@ -9,9 +9,9 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::borrow::{Borrow, BorrowMut};
+use core::cmp::Ordering;
 use core::convert::TryFrom;
 use core::fmt::{self, Display, Formatter};
 use core::ops::Range;
@@ -19,50 +19,53 @@ use core::ops::Range;
 use amplify::num::error::OverflowError;
 
 /// Large binary bytestring object.
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+///
+/// The string may contain up to `u16::MAX` bytes; memory is allocated proportionally to the
+/// actual string length.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
 pub struct ByteStr {
-    /// Adjusted slice length.
-    len: u16,
+    /// Slice bytes, never exceeding `u16::MAX` length
+    bytes: Vec<u8>,
+}
 
-    /// Slice bytes
-    #[doc(hidden)]
-    pub bytes: Box<[u8; u16::MAX as usize]>,
+impl PartialOrd for ByteStr {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 }
 
-impl Default for ByteStr {
-    fn default() -> ByteStr { ByteStr { len: 0, bytes: Box::new([0u8; u16::MAX as usize]) } }
+// Shorter strings always go first
+impl Ord for ByteStr {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.len().cmp(&other.bytes.len()).then_with(|| self.bytes.cmp(&other.bytes))
+    }
 }
 
 impl AsRef<[u8]> for ByteStr {
     #[inline]
-    fn as_ref(&self) -> &[u8] { &self.bytes[..self.len as usize] }
+    fn as_ref(&self) -> &[u8] { &self.bytes }
 }
 
 impl AsMut<[u8]> for ByteStr {
     #[inline]
-    fn as_mut(&mut self) -> &mut [u8] { &mut self.bytes[..self.len as usize] }
+    fn as_mut(&mut self) -> &mut [u8] { &mut self.bytes }
 }
 
 impl Borrow<[u8]> for ByteStr {
     #[inline]
-    fn borrow(&self) -> &[u8] { &self.bytes[..self.len as usize] }
+    fn borrow(&self) -> &[u8] { &self.bytes }
 }
 
 impl BorrowMut<[u8]> for ByteStr {
     #[inline]
-    fn borrow_mut(&mut self) -> &mut [u8] { &mut self.bytes[..self.len as usize] }
+    fn borrow_mut(&mut self) -> &mut [u8] { &mut self.bytes }
 }
 
 impl Extend<u8> for ByteStr {
     fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
-        let mut pos = self.len();
-        let iter = iter.into_iter();
         for byte in iter {
-            assert!(pos < u16::MAX);
-            self.bytes[pos as usize] = byte;
-            pos += 1;
+            assert!(self.len() < u16::MAX);
+            self.bytes.push(byte);
         }
-        self.len = pos as u16;
     }
 }
 
@@ -74,9 +77,7 @@ impl TryFrom<&[u8]> for ByteStr {
         if len > u16::MAX as usize {
             return Err(OverflowError { max: u16::MAX as usize + 1, value: len });
         }
-        let mut bytes = [0u8; u16::MAX as usize];
-        bytes[0..len].copy_from_slice(slice.as_ref());
-        Ok(ByteStr { len: len as u16, bytes: Box::new(bytes) })
+        Ok(ByteStr { bytes: slice.to_vec() })
     }
 }
 
@@ -92,15 +93,16 @@ impl ByteStr {
 
     /// Returns correct length of the string, in range `0 ..= u16::MAX`
     #[inline]
-    pub fn len(&self) -> u16 { self.len }
+    pub fn len(&self) -> u16 { self.bytes.len() as u16 }
 
     /// Returns when the string has a zero length
     #[inline]
-    pub fn is_empty(&self) -> bool { self.len == 0 }
+    pub fn is_empty(&self) -> bool { self.bytes.is_empty() }
 
-    /// Adjusts the length of the string if necessary
+    /// Adjusts the length of the string if necessary. If the string gets longer, it is extended
+    /// with zero bytes.
     #[inline]
-    pub fn adjust_len(&mut self, new_len: u16) { self.len = new_len }
+    pub fn adjust_len(&mut self, new_len: u16) { self.bytes.resize(new_len as usize, 0) }
 
     /// Fills range within a string with the provided byte value, increasing string length if
     /// necessary
@@ -113,7 +115,7 @@ impl ByteStr {
 
     /// Returns vector representation of the contained bytecode
     #[inline]
-    pub fn to_vec(&self) -> Vec<u8> { self.as_ref().to_vec() }
+    pub fn to_vec(&self) -> Vec<u8> { self.bytes.clone() }
 }
 
 #[cfg(feature = "std")]
@@ -122,7 +124,7 @@ impl Display for ByteStr {
         use std::fmt::Write;
 
         use amplify::hex::ToHex;
-        let vec = Vec::from(&self.bytes[..self.len as usize]);
+        let vec = self.bytes.clone();
         if f.alternate() {
             for (line, slice) in self.as_ref().chunks(16).enumerate() {
                 write!(f, "\x1B[0;35m{:>1$x}0  |  \x1B[0m", line, f.width().unwrap_or(1) - 1)?;
@@ -168,9 +170,7 @@ impl Display for ByteStr {
 
 #[cfg(not(feature = "std"))]
 impl Display for ByteStr {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{:#04X?}", &self.bytes[0usize..self.len as usize])
-    }
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { write!(f, "{:#04X?}", &self.bytes) }
 }
 
 #[cfg(feature = "strict_encoding")]
@@ -227,3 +227,36 @@ mod _serde {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjust_len() {
+        let mut s = ByteStr::with([1u8, 2, 3]);
+        s.adjust_len(1);
+        assert_eq!(s.as_ref(), &[1]);
+        s.adjust_len(3);
+        assert_eq!(s.as_ref(), &[1, 0, 0]);
+        s.fill(2..5, 7);
+        assert_eq!(s.as_ref(), &[1, 0, 7, 7, 7]);
+    }
+
+    #[test]
+    fn max_len() {
+        let mut s = ByteStr::with([0u8; u16::MAX as usize]);
+        assert_eq!(s.len(), u16::MAX);
+        assert!(ByteStr::try_from(&[0u8; u16::MAX as usize + 1][..]).is_err());
+        s.adjust_len(0);
+        s.extend([1u8; 4]);
+        assert_eq!(s.to_vec(), vec![1u8; 4]);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(ByteStr::with([0xFFu8]) < ByteStr::with([0u8, 0]));
+        assert!(ByteStr::with([1u8, 0]) < ByteStr::with([1u8, 1]));
+        assert_eq!(ByteStr::default(), ByteStr::with([]));
+    }
+}
@@ -16,6 +16,7 @@ use std::iter::FromIterator;
 use std::marker::PhantomData;
 use std::string::FromUtf8Error;
 
+use amplify::num::u5;
 use amplify::IoError;
 use bitcoin_hashes::Hash;
 
@@ -24,6 +25,7 @@ use crate::data::{ByteStr, FloatLayout, IntLayout, Layout, MaybeNumber, Number,
 use crate::program::{
     IsaSeg, IsaSegError, Lib, LibId, LibSeg, LibSegOverflow, LibSite, SegmentError,
 };
+use crate::reg::{CoreRegs, NumericRegister, Reg32, RegS, NUMERIC_REGS};
 
 /// Trait for encodable container data structures used by AluVM and runtime environments
 pub trait Encode {
@@ -121,6 +123,9 @@ pub enum DecodeError {
     #[display(inner)]
     #[from]
     IsaSeg(IsaSegError),
+
+    /// register index {0} is out of range
+    RegIndex(u8),
 }
 
 /// Wrapper around collections which may contain at most [`u8::MAX`] elements
@@ -228,11 +233,35 @@ impl Decode for u16 {
     }
 }
 
+impl Encode for u64 {
+    type Error = io::Error;
+
+    #[inline]
+    fn encode(&self, mut writer: impl Write) -> Result<usize, Self::Error> {
+        writer.write_all(&self.to_le_bytes())?;
+        Ok(8)
+    }
+}
+
+impl Decode for u64 {
+    type Error = io::Error;
+
+    #[inline]
+    fn decode(mut reader: impl Read) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut word = [0u8; 8];
+        reader.read_exact(&mut word)?;
+        Ok(u64::from_le_bytes(word))
+    }
+}
+
 impl Encode for String {
     type Error = EncodeError;
 
     fn encode(&self, mut writer: impl Write) -> Result<usize, Self::Error> {
-        let len = self.as_bytes().len();
+        let len = self.len();
         if len > u8::MAX as usize {
             return Err(EncodeError::StringTooLong(len));
         }
@@ -692,3 +721,88 @@ impl Decode for Lib {
         )?)
     }
 }
+
+impl Encode for CoreRegs {
+    type Error = io::Error;
+
+    fn encode(&self, mut writer: impl Write) -> Result<usize, Self::Error> {
+        let mut count = 0;
+        for reg in NUMERIC_REGS {
+            let values = (0..32u8)
+                .filter_map(|index| {
+                    self.get_raw(reg, Reg32::from(u5::with(index))).map(|value| (index, value))
+                })
+                .collect::<Vec<_>>();
+            count += (values.len() as u8).encode(&mut writer)?;
+            for (index, value) in values {
+                count += index.encode(&mut writer)?;
+                writer.write_all(value.as_ref())?;
+                count += value.len() as usize;
+            }
+        }
+
+        let strings =
+            (0..16u8).filter_map(|index| self.get_s(index).map(|s| (index, s))).collect::<Vec<_>>();
+        count += (strings.len() as u8).encode(&mut writer)?;
+        for (index, s) in strings {
+            count += index.encode(&mut writer)? + s.encode(&mut writer)?;
+        }
+
+        count += self.st0.encode(&mut writer)?;
+        count += self.cy0.encode(&mut writer)?;
+        count += self.ca0.encode(&mut writer)?;
+        count += self.cl0.is_some().encode(&mut writer)?;
+        if let Some(cl0) = self.cl0 {
+            count += cl0.encode(&mut writer)?;
+        }
+        count += self.cp0.encode(&mut writer)?;
+        for site in &self.cs0 {
+            count += site.encode(&mut writer)?;
+        }
+        Ok(count)
+    }
+}
+
+impl Decode for CoreRegs {
+    type Error = DecodeError;
+
+    fn decode(mut reader: impl Read) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut regs = CoreRegs::default();
+        for reg in NUMERIC_REGS {
+            for _ in 0..u8::decode(&mut reader)? {
+                let index = u8::decode(&mut reader)?;
+                if index >= 32 {
+                    return Err(DecodeError::RegIndex(index));
+                }
+                let mut vec = vec![0u8; reg.bytes() as usize];
+                reader.read_exact(&mut vec)?;
+                let value = Number::with(&vec, reg.layout())
+                    .ok_or(DecodeError::NumberLayout(reg.layout(), vec))?;
+                regs.set(reg, Reg32::from(u5::with(index)), value);
+            }
+        }
+
+        for _ in 0..u8::decode(&mut reader)? {
+            let index = u8::decode(&mut reader)?;
+            if index >= 16 {
+                return Err(DecodeError::RegIndex(index));
+            }
+            regs.s16[RegS::from(index).as_usize()] = Some(ByteStr::decode(&mut reader)?);
+        }
+
+        regs.st0 = bool::decode(&mut reader)?;
+        regs.cy0 = u16::decode(&mut reader)?;
+        regs.ca0 = u64::decode(&mut reader)?;
+        if bool::decode(&mut reader)? {
+            regs.cl0 = Some(u64::decode(&mut reader)?);
+        }
+        regs.cp0 = u16::decode(&mut reader)?;
+        for _ in 0..regs.cp0 {
+            regs.cs0.push(LibSite::decode(&mut reader)?);
+        }
+        Ok(regs)
+    }
+}
@@ -17,8 +17,10 @@ mod byte_str;
 #[cfg(feature = "std")]
 pub mod encoding;
 mod number;
+mod tapered;
 
 pub use byte_str::ByteStr;
 pub use number::{
     FloatLayout, IntLayout, Layout, LiteralParseError, MaybeNumber, Number, NumberLayout, Step,
 };
+pub use tapered::Posit512;
@@ -13,7 +13,7 @@
 //! number representation.
 
 use alloc::format;
-use alloc::string::{String, ToString};
+use alloc::string::String;
 use core::fmt::{
     self, Debug, Display, Formatter, LowerExp, LowerHex, Octal, UpperExp, UpperHex, Write,
 };
@@ -23,10 +23,12 @@ use core::ops::{
 };
 use core::str::FromStr;
 
-use amplify::num::apfloat::{ieee, Float, Status, StatusAnd};
+use amplify::num::apfloat::{ieee, ExpInt, Float, FloatConvert, Round, Status, StatusAnd};
 use amplify::num::{i1024, i256, i512, u1024, u256, u512};
 use half::bf16;
 
+use super::Posit512;
+
 /// Trait of different number layouts
 pub trait NumberLayout: Copy {
     /// Returns how many bits are used by the layout
@@ -275,7 +277,7 @@ pub enum FloatLayout {
     #[display("ieee:binary256")]
     IeeeOct = 8,
 
-    /// 512-bit tapered floating point
+    /// 512-bit tapered floating point in posit format (see [`super::Posit512`])
     #[display("tapered:binary512")]
     FloatTapered = 9,
 }
@@ -673,7 +675,7 @@ impl Number {
     /// matches the layout bit size.
     pub fn min_bit_len(&self) -> u16 {
         if self.layout.is_float() {
-            return self.layout.bits() as u16;
+            return self.layout.bits();
         }
         if self.len() == 0 {
             return 0;
@@ -727,7 +729,7 @@ impl Number {
             Layout::Float(FloatLayout::X87DoubleExt) => {
                 ieee::X87DoubleExtended::from(self).is_nan()
             }
-            Layout::Float(FloatLayout::FloatTapered) => todo!("(#5) tapered float NaN detection"),
+            Layout::Float(FloatLayout::FloatTapered) => Posit512::from(self).is_nar(),
         }
     }
 
@@ -780,89 +782,185 @@ impl Number {
     }
 
     /// Transforms internal value layout returning whether this was possible without discarding any
-    /// bit information
+    /// bit information.
+    ///
+    /// Integers are truncated or sign-extended, so negative values wrap into unsigned layouts.
+    /// Floats are rounded to the nearest value (with ties to even), overflowing into infinity, and
+    /// are converted into integers rounding toward zero.
     pub fn reshape(&mut self, to: Layout) -> bool {
         match (self.layout, to) {
             (from, to) if from == to => true,
             (
-                Layout::Integer(IntLayout { signed: true, bytes: b_from }),
-                Layout::Integer(IntLayout { signed: true, bytes: b_to }),
-            ) if !self.is_positive() && b_from < b_to => {
+                Layout::Integer(IntLayout { signed: s_from, bytes: b_from }),
+                Layout::Integer(IntLayout { signed: s_to, bytes: b_to }),
+            ) => {
+                let negative = self.is_negative();
+                // Signed layouts require an extra bit for the sign
+                let bit_len = self.min_bit_len() - s_from as u16 + s_to as u16;
                 self.layout = to;
-                for i in b_from..b_to {
-                    self[i] = 255u8;
+                // Negative values are sign-extended, so they wrap into unsigned layouts
+                if negative {
+                    for i in b_from..b_to {
+                        self[i] = 255u8;
+                    }
                 }
                 self.clean();
-                true
+                (s_to || !negative) && bit_len <= b_to * 8
             }
-            // We need to change only bit dimensions
-            (Layout::Integer(IntLayout { .. }), Layout::Integer(IntLayout { bytes: len2, .. })) => {
-                let bit_len = self.min_bit_len();
-                self.layout = to;
-                self.clean();
-                bit_len <= len2 * 8
+            (Layout::Float(FloatLayout::FloatTapered), Layout::Float(l2)) => {
+                let val = Posit512::from(*self);
+                let rnd = Round::NearestTiesToEven;
+                let res = match l2 {
+                    FloatLayout::BFloat16 => val.to_float::<BFloat16>(rnd).map(MaybeNumber::from),
+                    FloatLayout::IeeeHalf => val.to_float::<ieee::Half>(rnd).map(MaybeNumber::from),
+                    FloatLayout::IeeeSingle => {
+                        val.to_float::<ieee::Single>(rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeDouble => {
+                        val.to_float::<ieee::Double>(rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::X87DoubleExt => {
+                        val.to_float::<ieee::X87DoubleExtended>(rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeQuad => val.to_float::<ieee::Quad>(rnd).map(MaybeNumber::from),
+                    FloatLayout::IeeeOct => val.to_float::<ieee::Oct>(rnd).map(MaybeNumber::from),
+                    FloatLayout::FloatTapered => Status::OK.and(MaybeNumber::from(val)),
+                };
+                *self = res.value.0.expect("tapered float is never converted into NaN");
+                res.status == Status::OK
             }
-            (Layout::Float(l1), Layout::Float(l2)) => {
-                let value = match l1 {
-                    FloatLayout::BFloat16 => bf16::from(*self).to_string(),
-                    FloatLayout::IeeeHalf => ieee::Half::from(*self).to_string(),
-                    FloatLayout::IeeeSingle => ieee::Single::from(*self).to_string(),
-                    FloatLayout::IeeeDouble => ieee::Double::from(*self).to_string(),
-                    FloatLayout::X87DoubleExt => ieee::X87DoubleExtended::from(*self).to_string(),
-                    FloatLayout::IeeeQuad => ieee::Quad::from(*self).to_string(),
-                    FloatLayout::IeeeOct => {
-                        unimplemented!("IEEE octal precision layout conversion")
+            (Layout::Float(l1), Layout::Float(FloatLayout::FloatTapered)) => {
+                let rnd = Round::NearestTiesToEven;
+                let res = match l1 {
+                    FloatLayout::BFloat16 => Posit512::from_float(BFloat16::from(*self), rnd),
+                    FloatLayout::IeeeHalf => Posit512::from_float(ieee::Half::from(*self), rnd),
+                    FloatLayout::IeeeSingle => Posit512::from_float(ieee::Single::from(*self), rnd),
+                    FloatLayout::IeeeDouble => Posit512::from_float(ieee::Double::from(*self), rnd),
+                    FloatLayout::X87DoubleExt => {
+                        Posit512::from_float(ieee::X87DoubleExtended::from(*self), rnd)
                     }
-                    FloatLayout::FloatTapered => unimplemented!("tapered float layout conversion"),
+                    FloatLayout::IeeeQuad => Posit512::from_float(ieee::Quad::from(*self), rnd),
+                    FloatLayout::IeeeOct => Posit512::from_float(ieee::Oct::from(*self), rnd),
+                    FloatLayout::FloatTapered => Status::OK.and(Posit512::from(*self)),
                 };
-                *self = match l2 {
-                    FloatLayout::BFloat16 => bf16::from_str(&value)
-                        .ok()
-                        .and_then(|v| MaybeNumber::from(v).0)
-                        .expect("float layout conversion"),
-                    FloatLayout::IeeeHalf => ieee::Half::from_str(&value)
-                        .ok()
-                        .and_then(|v| MaybeNumber::from(v).0)
-                        .expect("float layout conversion"),
-                    FloatLayout::IeeeSingle => ieee::Single::from_str(&value)
-                        .ok()
-                        .and_then(|v| MaybeNumber::from(v).0)
-                        .expect("float layout conversion"),
-                    FloatLayout::IeeeDouble => ieee::Double::from_str(&value)
-                        .ok()
-                        .and_then(|v| MaybeNumber::from(v).0)
-                        .expect("float layout conversion"),
-                    FloatLayout::X87DoubleExt => ieee::X87DoubleExtended::from_str(&value)
-                        .ok()
-                        .and_then(|v| MaybeNumber::from(v).0)
-                        .expect("float layout conversion"),
-                    FloatLayout::IeeeQuad => ieee::Quad::from_str(&value)
-                        .ok()
-                        .and_then(|v| MaybeNumber::from(v).0)
-                        .expect("float layout conversion"),
-                    FloatLayout::IeeeOct => {
-                        unimplemented!("IEEE octal precision layout conversion")
+                // Tapered floats have no infinities, so they saturate to the largest value
+                let val = match res.value {
+                    val if !val.is_nar() => val,
+                    _ if self.is_negative() => -Posit512::MAX,
+                    _ => Posit512::MAX,
+                };
+                *self = MaybeNumber::from(val).0.expect("NaR is excluded above");
+                res.status == Status::OK
+            }
+            (Layout::Float(l1), Layout::Float(l2)) => {
+                // Octuple precision represents values of all other IEEE layouts exactly, so it is
+                // used as an intermediary to round the value only once
+                let val = match l1 {
+                    FloatLayout::BFloat16 => oct_from(BFloat16::from(*self)),
+                    FloatLayout::IeeeHalf => oct_from(ieee::Half::from(*self)),
+                    FloatLayout::IeeeSingle => oct_from(ieee::Single::from(*self)),
+                    FloatLayout::IeeeDouble => oct_from(ieee::Double::from(*self)),
+                    FloatLayout::X87DoubleExt => oct_from(ieee::X87DoubleExtended::from(*self)),
+                    FloatLayout::IeeeQuad => oct_from(ieee::Quad::from(*self)),
+                    FloatLayout::IeeeOct => ieee::Oct::from(*self),
+                    FloatLayout::FloatTapered => unreachable!("tapered float layout conversion"),
+                };
+                let rnd = Round::NearestTiesToEven;
+                let res = match l2 {
+                    FloatLayout::BFloat16 => {
+                        convert_float::<_, BFloat16>(val, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeHalf => {
+                        convert_float::<_, ieee::Half>(val, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeSingle => {
+                        convert_float::<_, ieee::Single>(val, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeDouble => {
+                        convert_float::<_, ieee::Double>(val, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::X87DoubleExt => {
+                        convert_float::<_, ieee::X87DoubleExtended>(val, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeQuad => {
+                        convert_float::<_, ieee::Quad>(val, rnd).map(MaybeNumber::from)
                     }
-                    FloatLayout::FloatTapered => unimplemented!("tapered float layout conversion"),
+                    FloatLayout::IeeeOct => Status::OK.and(MaybeNumber::from(val)),
+                    FloatLayout::FloatTapered => unreachable!("tapered float layout conversion"),
                 };
-                false
+                match res.value.0 {
+                    Some(val) => {
+                        *self = val;
+                        res.status == Status::OK
+                    }
+                    // NaN values can't be represented by a number and are left intact
+                    None => false,
+                }
             }
             (Layout::Float(fl), Layout::Integer(_)) => {
                 let val = match fl {
-                    FloatLayout::BFloat16 => todo!("BFloat16 to integer conversion"),
-                    FloatLayout::IeeeHalf => ieee::Half::from(*self).to_i256(256),
-                    FloatLayout::IeeeSingle => ieee::Single::from(*self).to_i256(256),
-                    FloatLayout::IeeeDouble => ieee::Double::from(*self).to_i256(256),
-                    FloatLayout::X87DoubleExt => ieee::X87DoubleExtended::from(*self).to_i256(256),
-                    FloatLayout::IeeeQuad => ieee::Quad::from(*self).to_i256(256),
-                    FloatLayout::IeeeOct => ieee::Oct::from(*self).to_i256(256),
-                    FloatLayout::FloatTapered => unimplemented!("tapered float layout conversion"),
+                    FloatLayout::BFloat16 => float_to_int(BFloat16::from(*self)),
+                    FloatLayout::IeeeHalf => float_to_int(ieee::Half::from(*self)),
+                    FloatLayout::IeeeSingle => float_to_int(ieee::Single::from(*self)),
+                    FloatLayout::IeeeDouble => float_to_int(ieee::Double::from(*self)),
+                    FloatLayout::X87DoubleExt => float_to_int(ieee::X87DoubleExtended::from(*self)),
+                    FloatLayout::IeeeQuad => float_to_int(ieee::Quad::from(*self)),
+                    FloatLayout::IeeeOct => float_to_int(ieee::Oct::from(*self)),
+                    FloatLayout::FloatTapered => {
+                        Posit512::from(*self).to_i1024_r(Round::TowardZero).map(Number::from)
+                    }
                 };
-                *self = Number::from(val.value);
-                self.reshape(to);
-                val.status == Status::OK
+                *self = val.value;
+                self.reshape(to) && val.status == Status::OK
+            }
+            (
+                Layout::Integer(IntLayout { signed, .. }),
+                Layout::Float(FloatLayout::FloatTapered),
+            ) => {
+                let rnd = Round::NearestTiesToEven;
+                let res = match signed {
+                    true => Posit512::from_i1024_r(i1024::from(*self), rnd),
+                    false => Posit512::from_u1024_r(u1024::from(*self), rnd),
+                };
+                *self =
+                    MaybeNumber::from(res.value).0.expect("integers are never converted into NaR");
+                res.status == Status::OK
+            }
+            (Layout::Integer(IntLayout { signed, .. }), Layout::Float(fl)) => {
+                let negative = signed && self.is_negative();
+                let abs = match negative {
+                    true => u1024::from_le_bytes(i1024::from(*self).wrapping_neg().to_le_bytes()),
+                    false => u1024::from(*self),
+                };
+                let rnd = Round::NearestTiesToEven;
+                let res = match fl {
+                    FloatLayout::IeeeHalf => {
+                        int_to_float::<ieee::Half>(abs, negative, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeSingle => {
+                        int_to_float::<ieee::Single>(abs, negative, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeDouble => {
+                        int_to_float::<ieee::Double>(abs, negative, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::X87DoubleExt => {
+                        int_to_float::<ieee::X87DoubleExtended>(abs, negative, rnd)
+                            .map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeQuad => {
+                        int_to_float::<ieee::Quad>(abs, negative, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::IeeeOct => {
+                        int_to_float::<ieee::Oct>(abs, negative, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::BFloat16 => {
+                        int_to_float::<BFloat16>(abs, negative, rnd).map(MaybeNumber::from)
+                    }
+                    FloatLayout::FloatTapered => unreachable!("tapered float layout conversion"),
+                };
+                *self = res.value.0.expect("integers are never converted into NaN");
+                res.status == Status::OK
             }
-            (from, to) => todo!("Number layout reshape from {} to {}", from, to),
         }
     }
 
@@ -872,7 +970,7 @@ impl Number {
     /// Transformed number as an optional - or `None` if the operation was impossible without
     /// discarding bit information and `wrap` is set to false.
     pub fn reshaped(mut self, to: Layout, wrap: bool) -> Option<Number> {
-        self.reshape(to).then(|| self).or(if wrap { Some(self) } else { None })
+        self.reshape(to).then_some(self).or(if wrap { Some(self) } else { None })
     }
 
     #[doc(hidden)]
@@ -973,13 +1071,11 @@ impl Display for Number {
             Layout::Float(FloatLayout::IeeeSingle) => Display::fmt(&ieee::Single::from(self), f),
             Layout::Float(FloatLayout::IeeeDouble) => Display::fmt(&ieee::Double::from(self), f),
             Layout::Float(FloatLayout::IeeeQuad) => Display::fmt(&ieee::Quad::from(self), f),
+            Layout::Float(FloatLayout::IeeeOct) => Display::fmt(&ieee::Oct::from(self), f),
             Layout::Float(FloatLayout::X87DoubleExt) => {
                 Display::fmt(&ieee::X87DoubleExtended::from(self), f)
             }
-            _ => {
-                // TODO(#16) Implement Display for the rest of float layouts
-                f.write_str("<not supported float layout for display>")
-            }
+            Layout::Float(FloatLayout::FloatTapered) => Display::fmt(&Posit512::from(self), f),
         }
     }
 }
@@ -1127,7 +1223,9 @@ macro_rules! impl_number_bytes_conv {
                     len <= $len,
                     "attempt to convert number into a byte array with incorrect length",
                 );
-                let mut bytes = [0u8; $len];
+                // Negative values are sign-extended
+                let negative = val.layout.is_signed_int() && val.is_negative();
+                let mut bytes = if negative { [0xFFu8; $len] } else { [0u8; $len] };
                 bytes[..len].copy_from_slice(&val.bytes[..len]);
                 bytes
             }
@@ -1223,13 +1321,16 @@ macro_rules! impl_number_int_conv {
 
 macro_rules! impl_number_float_conv {
     ($ty:ident, $tys:ident, $len:literal, $layout:ident) => {
+        impl_number_float_conv!($ty, $tys, $len, $layout, $ty::from_bits);
+    };
+    ($ty:ident, $tys:ident, $len:literal, $layout:ident, $from_bits:path) => {
         impl From<Number> for $ty {
             fn from(val: Number) -> Self {
                 assert!(
                     val.min_bit_len() <= $len * 8,
                     "attempt to convert Number into type with lower bit dimension"
                 );
-                $ty::from_bits(val.into())
+                $from_bits(val.into())
             }
         }
 
@@ -1296,9 +1397,103 @@ mod _float_impl {
     impl_number_float_conv!(Half, HalfS, 2, IeeeHalf);
     impl_number_float_conv!(Single, SingleS, 4, IeeeSingle);
     impl_number_float_conv!(Double, DoubleS, 8, IeeeDouble);
-    impl_number_float_conv!(X87DoubleExtended, X87DoubleExtendedS, 10, X87DoubleExt);
+    impl_number_float_conv!(X87DoubleExtended, X87DoubleExtendedS, 10, X87DoubleExt, x87_from_bits);
     impl_number_float_conv!(Quad, QuadS, 16, IeeeQuad);
     impl_number_float_conv!(Oct, OctS, 32, IeeeOct);
+    impl_number_float_conv!(Posit512, Posit512, 64, FloatTapered);
+}
+
+/// Semantics of bfloat16 format, which follows IEEE-754 binary layout with 8-bit exponent and 8-bit
+/// precision; allows to use [`half::bf16`] values in the conversions implemented for [`Float`]
+/// types.
+pub(crate) struct BFloat16S;
+
+impl ieee::Semantics for BFloat16S {
+    const BITS: usize = 16;
+    const PRECISION: usize = 8;
+    const MAX_EXP: ExpInt = 127;
+}
+
+/// IEEE-754 float type with bfloat16 semantics
+pub(crate) type BFloat16 = ieee::IeeeFloat<BFloat16S>;
+
+impl_number_float_conv!(BFloat16, BFloat16S, 2, BFloat16);
+
+/// Constructs x87 extended precision float from its binary encoding.
+///
+/// `from_bits` provided by `apfloat` drops the explicit integer bit of the x87 significand, so the
+/// value is decoded from the binary128 encoding instead, which has the same exponent range and
+/// represents all x87 values exactly.
+fn x87_from_bits(bits: u256) -> ieee::X87DoubleExtended {
+    let sign = (bits >> 79) & u256::ONE;
+    let exp = (bits >> 64) & u256::from(0x7FFFu16);
+    let fraction = bits & ((u256::ONE << 63) - u256::ONE);
+    let quad = ieee::Quad::from_bits((sign << 127) | (exp << 112) | (fraction << 49));
+    convert_float(quad, Round::NearestTiesToEven).value
+}
+
+/// Converts float value between IEEE layouts
+#[inline]
+fn convert_float<F, T>(val: F, round: Round) -> StatusAnd<T>
+where
+    F: FloatConvert<T>,
+    T: Float,
+{
+    val.convert_r(round, &mut false)
+}
+
+/// Converts float value into octuple precision, which is always exact
+#[inline]
+fn oct_from<F: FloatConvert<ieee::Oct>>(val: F) -> ieee::Oct {
+    convert_float(val, Round::NearestTiesToEven).value
+}
+
+/// Converts float value into an integer, rounding it toward zero. Values not fitting into 1024-bit
+/// signed integer are saturated with [`Status::INVALID_OP`].
+fn float_to_int<F: Float>(val: F) -> StatusAnd<Number> {
+    if val.is_zero() || !val.is_finite() || val.ilogb() < 255 {
+        return val.to_i256(256).map(Number::from);
+    }
+    let exp = val.ilogb();
+    let saturated = || {
+        let sat = if val.is_negative() { i1024::MIN } else { i1024::MAX };
+        Status::INVALID_OP.and(Number::from(sat))
+    };
+    if exp > 1023 {
+        return saturated();
+    }
+    // Significand is scaled into an integer which is exact since the value has no fractional part
+    let prec = F::PRECISION as ExpInt - 1;
+    let sig = val.abs().scalbn(prec - exp).to_u256(256).value;
+    let mut bytes = [0u8; 128];
+    bytes[..32].copy_from_slice(&sig.to_le_bytes());
+    let abs = i1024::from_le_bytes(bytes) << (exp - prec) as usize;
+    // The only value with 1024 significant bits which fits is the minimal negative one
+    if exp == 1023 && (!val.is_negative() || abs != i1024::MIN) {
+        return saturated();
+    }
+    Status::OK.and(Number::from(if val.is_negative() { abs.wrapping_neg() } else { abs }))
+}
+
+/// Converts integer with the magnitude `abs` into a float value
+fn int_to_float<F: Float>(abs: u1024, negative: bool, round: Round) -> StatusAnd<F> {
+    // Bits beyond the top 255 ones are collapsed into a sticky bit, which keeps the rounding
+    // correct since the precision of all float layouts is lower than that
+    let shift = (1024 - abs.leading_zeros()).saturating_sub(255);
+    let sticky = abs.trailing_zeros() < shift;
+    let top = (abs >> shift as usize) | u1024::from(sticky as u8);
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&top.to_le_bytes()[..32]);
+    let round = if negative { -round } else { round };
+    let mut res =
+        F::from_u256_r(u256::from_le_bytes(bytes), round).map(|val| val.scalbn(shift as ExpInt));
+    if res.value.is_infinite() {
+        res.status |= Status::OVERFLOW | Status::INEXACT;
+    }
+    if negative {
+        res = res.map(|val| -val);
+    }
+    res
 }
 
 impl_number_int_conv!(i8, 1, true);
@@ -1349,6 +1544,8 @@ impl Display for Step {
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use super::*;
 
     #[test]
@@ -1389,54 +1586,54 @@ mod tests {
     #[test]
     fn is_zero_test() {
         let num = Number::from(0);
-        assert_eq!(true, num.is_zero());
+        assert!(num.is_zero());
         let num = Number::from(1);
-        assert_eq!(false, num.is_zero());
+        assert!(!num.is_zero());
     }
 
     #[test]
     fn is_unsigned_int_test() {
         let num = Number::from(0u8);
-        assert_eq!(true, num.layout.is_unsigned_int());
+        assert!(num.layout.is_unsigned_int());
         let num = Number::from(0i8);
-        assert_eq!(false, num.layout.is_unsigned_int());
+        assert!(!num.layout.is_unsigned_int());
         let num = Number::from(1u16);
-        assert_eq!(true, num.layout.is_unsigned_int());
+        assert!(num.layout.is_unsigned_int());
         let num = Number::from(1i16);
-        assert_eq!(false, num.layout.is_unsigned_int());
+        assert!(!num.layout.is_unsigned_int());
         let num = Number::from(-1);
-        assert_eq!(false, num.layout.is_unsigned_int());
+        assert!(!num.layout.is_unsigned_int());
     }
 
     #[test]
     fn is_positive_test() {
         let num = Number::from(1);
-        assert_eq!(true, num.is_positive());
+        assert!(num.is_positive());
         let num = Number::from(0);
-        assert_eq!(false, num.is_positive());
+        assert!(!num.is_positive());
         let num = Number::from(-1);
-        assert_eq!(false, num.is_positive());
+        assert!(!num.is_positive());
         let num = Number::from(127);
-        assert_eq!(true, num.is_positive());
+        assert!(num.is_positive());
     }
 
     #[test]
     fn reshape_test() {
         let mut x =
-            Number::with(&[1u8], Layout::Integer(IntLayout { signed: false, bytes: 1 })).unwrap();
-        let y = Number::with(&[1u8, 0u8], Layout::Integer(IntLayout { signed: false, bytes: 2 }))
+            Number::with([1u8], Layout::Integer(IntLayout { signed: false, bytes: 1 })).unwrap();
+        let y = Number::with([1u8, 0u8], Layout::Integer(IntLayout { signed: false, bytes: 2 }))
             .unwrap();
-        assert_eq!(true, x.reshape(Layout::Integer(IntLayout { signed: false, bytes: 2 })));
+        assert!(x.reshape(Layout::Integer(IntLayout { signed: false, bytes: 2 })));
         assert_eq!(x, y);
     }
 
     #[test]
     fn reshape_with_same_layout_test() {
         let mut x =
-            Number::with(&[1u8], Layout::Integer(IntLayout { signed: false, bytes: 1 })).unwrap();
+            Number::with([1u8], Layout::Integer(IntLayout { signed: false, bytes: 1 })).unwrap();
         let y =
-            Number::with(&[1u8], Layout::Integer(IntLayout { signed: false, bytes: 1 })).unwrap();
-        assert_eq!(true, x.reshape(Layout::Integer(IntLayout { signed: false, bytes: 1 })));
+            Number::with([1u8], Layout::Integer(IntLayout { signed: false, bytes: 1 })).unwrap();
+        assert!(x.reshape(Layout::Integer(IntLayout { signed: false, bytes: 1 })));
         assert_eq!(x, y);
     }
 
@@ -1446,12 +1643,248 @@ mod tests {
         let y = Number::from(-24i16);
         let z = Number::from(-24i128);
         assert_eq!(x.layout, Layout::Integer(IntLayout { signed: true, bytes: 1 }));
-        assert_eq!(true, x.reshape(Layout::Integer(IntLayout { signed: true, bytes: 2 })));
+        assert!(x.reshape(Layout::Integer(IntLayout { signed: true, bytes: 2 })));
         assert_eq!(x, y);
-        assert_eq!(true, x.reshape(Layout::Integer(IntLayout { signed: true, bytes: 16 })));
+        assert!(x.reshape(Layout::Integer(IntLayout { signed: true, bytes: 16 })));
         assert_eq!(x, z);
     }
 
+    #[test]
+    fn reshape_oct_test() {
+        let oct = Layout::float(FloatLayout::IeeeOct);
+        let double = Layout::float(FloatLayout::IeeeDouble);
+
+        let mut x = MaybeNumber::from(ieee::Double::from_str("0x1.8p-3").unwrap()).unwrap();
+        let y = x;
+        assert!(x.reshape(oct));
+        assert_eq!(x, MaybeNumber::from(ieee::Oct::from_str("0x1.8p-3").unwrap()).unwrap());
+        assert!(x.reshape(double));
+        assert_eq!(x, y);
+
+        let third =
+            ieee::Oct::from_u256(u256::ONE).value / ieee::Oct::from_u256(u256::from(3u8)).value;
+        let mut x = MaybeNumber::from(third.value).unwrap();
+        assert!(!x.reshape(double));
+        assert_eq!(
+            x,
+            MaybeNumber::from(ieee::Double::from_str("0x1.5555555555555p-2").unwrap()).unwrap()
+        );
+
+        let mut x = MaybeNumber::from(ieee::Oct::from_str("0x1p+20000").unwrap()).unwrap();
+        assert!(!x.reshape(Layout::float(FloatLayout::IeeeQuad)));
+        assert_eq!(x, MaybeNumber::from(ieee::Quad::INFINITY).unwrap());
+    }
+
+    #[test]
+    fn reshape_int_oct_test() {
+        let oct = Layout::float(FloatLayout::IeeeOct);
+        let int = Layout::Integer(IntLayout::signed(128));
+
+        let mut x = Number::from(-24i8);
+        assert!(x.reshape(oct));
+        assert_eq!(x, MaybeNumber::from(ieee::Oct::from_i256(i256::from(-24i8)).value).unwrap());
+        assert!(x.reshape(Layout::Integer(IntLayout::signed(1))));
+        assert_eq!(x, Number::from(-24i8));
+
+        let big = (i1024::from(3u8) << 1000).wrapping_neg();
+        let mut x = Number::from(big);
+        assert!(x.reshape(oct));
+        assert_eq!(x, MaybeNumber::from(ieee::Oct::from_str("-0x1.8p+1001").unwrap()).unwrap());
+        assert!(x.reshape(int));
+        assert_eq!(x, Number::from(big));
+
+        let mut x = Number::from(u1024::MAX);
+        assert!(!x.reshape(oct));
+        assert!(!x.reshape(int));
+
+        let mut x = MaybeNumber::from(ieee::Oct::from_str("0x1.8p+1").unwrap()).unwrap();
+        assert!(x.reshape(Layout::Integer(IntLayout::unsigned(8))));
+        assert_eq!(x, Number::from(3u64));
+        let mut x = MaybeNumber::from(ieee::Oct::from_str("-0x1.8p+0").unwrap()).unwrap();
+        assert!(!x.reshape(Layout::Integer(IntLayout::signed(8))));
+        assert_eq!(x, Number::from(-1i64));
+        let mut x = MaybeNumber::from(ieee::Oct::from_str("0x1p+300").unwrap()).unwrap();
+        assert!(!x.reshape(Layout::Integer(IntLayout::unsigned(32))));
+
+        let mut x = Number::from(70000u32);
+        assert!(!x.reshape(Layout::float(FloatLayout::IeeeHalf)));
+        assert_eq!(x, MaybeNumber::from(ieee::Half::INFINITY).unwrap());
+    }
+
+    /// Test value `±m·2^e` with odd `m` (or zero)
+    type Case = (bool, u64, i32);
+
+    const RESHAPE_CASES: [Case; 18] = [
+        (false, 0, 0),
+        (false, 1, 0),
+        (true, 1, 0),
+        (false, 3, -1),
+        (true, 5, -1),
+        (false, 255, 0),
+        (true, 1, 7),
+        (false, 1, 8),
+        (true, 129, 0),
+        (false, 2047, 5),
+        (false, 4375, 4),
+        (true, 1, -20),
+        (false, 1, -30),
+        (false, u64::MAX, 0),
+        (true, 1, 100),
+        (false, 1, 300),
+        (true, 1, 1023),
+        (false, 1, 2000),
+    ];
+
+    fn int_case(layout: IntLayout, (negative, m, e): Case) -> (Option<Number>, bool) {
+        if e < 0 {
+            return (None, false);
+        }
+        let bits = layout.bits() as i32;
+        let len = 64 - m.leading_zeros() as i32 + e;
+        let fits = match (layout.signed, negative) {
+            (false, false) => len <= bits,
+            (false, true) => m == 0,
+            (true, false) => len < bits,
+            (true, true) => len < bits || (m == 1 && e == bits - 1),
+        };
+        if e >= 1024 {
+            return (None, fits);
+        }
+        let mut val = i1024::from(m) << e as usize;
+        if negative {
+            val = val.wrapping_neg();
+        }
+        let bytes = val.to_le_bytes();
+        (Number::with(&bytes[..layout.bytes as usize], layout).filter(|_| fits), fits)
+    }
+
+    fn ieee_case<F: Float>((negative, m, e): Case) -> (F, bool) {
+        let len = 64 - m.leading_zeros() as ExpInt;
+        let fits = m == 0
+            || (len <= F::PRECISION as ExpInt
+                && e > F::MIN_EXP - F::PRECISION as ExpInt
+                && e + len - 1 <= F::MAX_EXP);
+        let val = F::from_u256(u256::from(m)).value.scalbn(e);
+        (if negative { -val } else { val }, fits)
+    }
+
+    fn float_case(layout: FloatLayout, case: Case) -> (Option<Number>, bool) {
+        let (val, fits) = match layout {
+            FloatLayout::BFloat16 => {
+                let (val, fits) = ieee_case::<BFloat16>(case);
+                (MaybeNumber::from(val), fits)
+            }
+            FloatLayout::IeeeHalf => {
+                let (val, fits) = ieee_case::<ieee::Half>(case);
+                (MaybeNumber::from(val), fits)
+            }
+            FloatLayout::IeeeSingle => {
+                let (val, fits) = ieee_case::<ieee::Single>(case);
+                (MaybeNumber::from(val), fits)
+            }
+            FloatLayout::IeeeDouble => {
+                let (val, fits) = ieee_case::<ieee::Double>(case);
+                (MaybeNumber::from(val), fits)
+            }
+            FloatLayout::X87DoubleExt => {
+                let (val, fits) = ieee_case::<ieee::X87DoubleExtended>(case);
+                (MaybeNumber::from(val), fits)
+            }
+            FloatLayout::IeeeQuad => {
+                let (val, fits) = ieee_case::<ieee::Quad>(case);
+                (MaybeNumber::from(val), fits)
+            }
+            FloatLayout::IeeeOct => {
+                let (val, fits) = ieee_case::<ieee::Oct>(case);
+                (MaybeNumber::from(val), fits)
+            }
+            // All test values are within the range where posits have more than 64 bits of
+            // precision
+            FloatLayout::FloatTapered => {
+                let (val, _) = ieee_case::<ieee::Oct>(case);
+                let res = Posit512::from_float(val, Round::NearestTiesToEven);
+                assert_eq!(res.status, Status::OK);
+                (MaybeNumber::from(res.value), true)
+            }
+        };
+        (val.0.filter(|_| fits), fits)
+    }
+
+    #[test]
+    fn reshape_matrix_test() {
+        let mut layouts = vec![];
+        for bytes in [1u16, 2, 4, 8, 16, 32, 64, 128] {
+            layouts.push(Layout::Integer(IntLayout::unsigned(bytes)));
+            layouts.push(Layout::Integer(IntLayout::signed(bytes)));
+        }
+        for layout in 0..=FloatLayout::FloatTapered as u8 {
+            if let Some(layout) = FloatLayout::with(layout) {
+                layouts.push(Layout::Float(layout));
+            }
+        }
+        let case = |layout: Layout, case: Case| match layout {
+            Layout::Integer(layout) => int_case(layout, case),
+            Layout::Float(layout) => float_case(layout, case),
+        };
+
+        for c in RESHAPE_CASES {
+            for from in &layouts {
+                let (val, _) = case(*from, c);
+                let val = match val {
+                    Some(val) => val,
+                    None => continue,
+                };
+                for to in &layouts {
+                    let (expected, fits) = case(*to, c);
+                    let mut x = val;
+                    assert_eq!(
+                        x.reshape(*to),
+                        fits,
+                        "reshape of {:?} from {} to {} must return {}",
+                        c,
+                        from,
+                        to,
+                        fits
+                    );
+                    assert_eq!(x.layout(), *to);
+                    if let Some(expected) = expected {
+                        assert_eq!(x, expected, "reshape of {:?} from {} to {}", c, from, to);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reshape_rounding_test() {
+        let double = Layout::float(FloatLayout::IeeeDouble);
+
+        let mut x = Number::from(u64::MAX);
+        assert!(!x.reshape(double));
+        assert_eq!(x, MaybeNumber::from(ieee::Double::from_str("0x1p+64").unwrap()).unwrap());
+
+        let mut x = MaybeNumber::from(ieee::Double::from_str("0x1.8p+0").unwrap()).unwrap();
+        assert!(!x.reshape(Layout::Integer(IntLayout::signed(1))));
+        assert_eq!(x, Number::from(1i8));
+        let mut x = MaybeNumber::from(ieee::Double::from_str("-0x1.4p+1").unwrap()).unwrap();
+        assert!(!x.reshape(Layout::Integer(IntLayout::signed(1))));
+        assert_eq!(x, Number::from(-2i8));
+
+        let mut x =
+            MaybeNumber::from(ieee::Double::from_str("0x1.5555555555555p-2").unwrap()).unwrap();
+        assert!(!x.reshape(Layout::float(FloatLayout::IeeeHalf)));
+        assert_eq!(x, MaybeNumber::from(ieee::Half::from_str("0x1.554p-2").unwrap()).unwrap());
+        assert!(x.reshape(Layout::float(FloatLayout::X87DoubleExt)));
+        assert!(!x.reshape(Layout::float(FloatLayout::BFloat16)));
+        assert_eq!(x, MaybeNumber::from(bf16::from_bits(0x3EAB)).unwrap());
+
+        let mut x =
+            MaybeNumber::from(ieee::X87DoubleExtended::from_str("0x1.8p+0").unwrap()).unwrap();
+        assert_eq!(ieee::X87DoubleExtended::from(x).to_string(), "1.5");
+        assert!(x.reshape(double));
+        assert_eq!(x, MaybeNumber::from(ieee::Double::from_str("0x1.8p+0").unwrap()).unwrap());
+    }
+
     #[test]
     fn take_sign_test() {
         let x = Number::from(-1i8);
@@ -0,0 +1,502 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! 512-bit tapered floating point numbers
+
+use core::cmp::Ordering;
+use core::fmt::{self, Display, Formatter};
+use core::ops::Neg;
+
+use amplify::num::apfloat::{ieee, Float, Round, Status, StatusAnd};
+use amplify::num::{i1024, u1024, u256, u512};
+
+/// Number of exponent bits following the regime
+const ES: u32 = 2;
+
+/// Position of the leading (hidden) significand bit in the unpacked representation. Gives two
+/// extra bits over the longest possible fraction (`511 - 2 - ES` bits).
+const SIG_TOP: u32 = 509;
+
+/// Largest regime which does not saturate to [`Posit512::MAX`]
+const MAX_REGIME: i32 = 509;
+
+/// Smallest regime which does not saturate to [`Posit512::MIN_POSITIVE`]
+const MIN_REGIME: i32 = -510;
+
+/// 512-bit tapered floating point number in posit format with 2 exponent bits, as defined by the
+/// Posit Standard (2022).
+///
+/// The value is stored as a two's complement integer, consisting of a sign bit, a variable-length
+/// regime, up to two exponent bits and a fraction, which takes all the remaining bits. Thus, the
+/// numbers close to one have up to 507 bits of fraction, while the precision tapers off toward the
+/// ends of the range `2^-2040..=2^2040`.
+///
+/// Unlike IEEE-754 floats, the format has a single zero and no infinities; the only exceptional
+/// value is "not a real" (NaR), which is produced by the operations without a real result (like
+/// division by zero) and is treated by the VM as an undefined register value. Results of arithmetic
+/// operations are rounded according to the provided rounding mode, but never round to zero or to
+/// NaR: values beyond the range of the format saturate to [`Posit512::MAX`] or
+/// [`Posit512::MIN_POSITIVE`] (with the corresponding sign).
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Posit512(u512);
+
+/// Unpacked finite non-zero posit value, equal to `sig * 2^exp`
+struct Unpacked {
+    negative: bool,
+    exp: i32,
+    sig: u1024,
+}
+
+impl Posit512 {
+    /// Zero value
+    pub const ZERO: Posit512 = Posit512(u512::ZERO);
+
+    /// "Not a real" value
+    pub const NAR: Posit512 = Posit512(u512::from_inner([0, 0, 0, 0, 0, 0, 0, 1 << 63]));
+
+    /// Largest representable value, equal to `2^2040`
+    pub const MAX: Posit512 = Posit512(u512::from_inner([
+        u64::MAX,
+        u64::MAX,
+        u64::MAX,
+        u64::MAX,
+        u64::MAX,
+        u64::MAX,
+        u64::MAX,
+        u64::MAX >> 1,
+    ]));
+
+    /// Smallest positive value, equal to `2^-2040`
+    pub const MIN_POSITIVE: Posit512 = Posit512(u512::ONE);
+
+    /// Constructs number from its bit representation
+    #[inline]
+    pub fn from_bits(bits: u512) -> Posit512 { Posit512(bits) }
+
+    /// Returns bit representation of the number
+    #[inline]
+    pub fn to_bits(self) -> u512 { self.0 }
+
+    /// Detects whether the value is "not a real" (NaR)
+    #[inline]
+    pub fn is_nar(self) -> bool { self == Posit512::NAR }
+
+    /// Detects whether the value is "not a real" (NaR); provided for the API compatibility with the
+    /// IEEE-754 float types, which report NaN values with the same method
+    #[inline]
+    pub fn is_nan(self) -> bool { self.is_nar() }
+
+    /// Detects whether the value is equal to zero
+    #[inline]
+    pub fn is_zero(self) -> bool { self == Posit512::ZERO }
+
+    /// Detects whether the value is negative (i.e. `<0`). NaR is not considered negative.
+    #[inline]
+    pub fn is_negative(self) -> bool { self.0.bit(511) && !self.is_nar() }
+
+    /// Returns the absolute value of the number
+    #[inline]
+    pub fn abs(self) -> Posit512 {
+        if self.is_negative() {
+            -self
+        } else {
+            self
+        }
+    }
+
+    /// Addition with the given rounding mode
+    pub fn add_r(self, rhs: Posit512, round: Round) -> StatusAnd<Posit512> {
+        let (a, b) = match (self.unpack(), rhs.unpack()) {
+            _ if self.is_nar() || rhs.is_nar() => return Status::OK.and(Posit512::NAR),
+            (None, _) => return Status::OK.and(rhs),
+            (_, None) => return Status::OK.and(self),
+            (Some(a), Some(b)) => (a, b),
+        };
+        // Both significands have the same width, so the exponent defines the larger magnitude
+        let (big, small) = match a.exp.cmp(&b.exp).then_with(|| a.sig.cmp(&b.sig)) {
+            Ordering::Less => (b, a),
+            _ => (a, b),
+        };
+        // The extra precision makes it enough to keep the bits shifted out from the smaller operand
+        // as a single sticky bit at the very end
+        let (small_sig, sticky) =
+            shr_sticky(small.sig << 512, (big.exp - small.exp).unsigned_abs());
+        let small_sig = small_sig | u1024::from(sticky);
+        let big_sig = big.sig << 512;
+        let sig =
+            if big.negative == small.negative { big_sig + small_sig } else { big_sig - small_sig };
+        if sig == u1024::ZERO {
+            return Status::OK.and(Posit512::ZERO);
+        }
+        Posit512::encode(big.negative, big.exp - 512, sig, false, round)
+    }
+
+    /// Subtraction with the given rounding mode
+    #[inline]
+    pub fn sub_r(self, rhs: Posit512, round: Round) -> StatusAnd<Posit512> {
+        self.add_r(-rhs, round)
+    }
+
+    /// Multiplication with the given rounding mode
+    pub fn mul_r(self, rhs: Posit512, round: Round) -> StatusAnd<Posit512> {
+        match (self.unpack(), rhs.unpack()) {
+            _ if self.is_nar() || rhs.is_nar() => Status::OK.and(Posit512::NAR),
+            (None, _) | (_, None) => Status::OK.and(Posit512::ZERO),
+            (Some(a), Some(b)) => Posit512::encode(
+                a.negative ^ b.negative,
+                a.exp + b.exp,
+                a.sig * b.sig,
+                false,
+                round,
+            ),
+        }
+    }
+
+    /// Division with the given rounding mode. Division by zero results in NaR.
+    pub fn div_r(self, rhs: Posit512, round: Round) -> StatusAnd<Posit512> {
+        match (self.unpack(), rhs.unpack()) {
+            _ if self.is_nar() || rhs.is_nar() => Status::OK.and(Posit512::NAR),
+            (_, None) => Status::DIV_BY_ZERO.and(Posit512::NAR),
+            (None, _) => Status::OK.and(Posit512::ZERO),
+            (Some(a), Some(b)) => {
+                let num = a.sig << 512;
+                let sig = num / b.sig;
+                let sticky = num % b.sig != u1024::ZERO;
+                Posit512::encode(a.negative ^ b.negative, a.exp - b.exp - 512, sig, sticky, round)
+            }
+        }
+    }
+
+    /// Converts IEEE-754 float into posit with the given rounding mode. NaN and infinite values
+    /// are converted into NaR.
+    pub fn from_float<F: Float>(val: F, round: Round) -> StatusAnd<Posit512> {
+        if val.is_nan() || val.is_infinite() {
+            return Status::INVALID_OP.and(Posit512::NAR);
+        }
+        if val.is_zero() {
+            return Status::OK.and(Posit512::ZERO);
+        }
+        let prec = F::PRECISION as i32;
+        let exp = val.ilogb() - (prec - 1);
+        let sig = val.abs().scalbn(-exp).to_u256(256).value;
+        Posit512::encode(val.is_negative(), exp, widen(sig.to_le_bytes()), false, round)
+    }
+
+    /// Converts posit into IEEE-754 float with the given rounding mode. NaR is converted into NaN.
+    pub fn to_float<F: Float>(self, round: Round) -> StatusAnd<F> {
+        let val = match self.unpack() {
+            _ if self.is_nar() => return Status::INVALID_OP.and(F::NAN),
+            None => return Status::OK.and(F::ZERO),
+            Some(val) => val,
+        };
+        let prec = F::PRECISION as i32;
+        // Exponent of the least significant bit kept by the float, accounting for denormals
+        let lsb = (val.exp + SIG_TOP as i32 - (prec - 1)).max(F::MIN_EXP - (prec - 1));
+        let (sig, inexact) = round_off(val.sig, (lsb - val.exp) as u32, false, val.negative, round);
+        // The significand fits the float precision, so the conversion is exact unless overflows
+        let sig = u256::from_le_bytes(narrow(sig.to_le_bytes()));
+        let mut res = F::from_u256(sig).value.scalbn(lsb);
+        let mut status = if inexact { Status::INEXACT } else { Status::OK };
+        if res.is_infinite() {
+            status = Status::OVERFLOW | Status::INEXACT;
+            let to_largest = match round {
+                Round::TowardZero => true,
+                Round::TowardPositive => val.negative,
+                Round::TowardNegative => !val.negative,
+                Round::NearestTiesToEven | Round::NearestTiesToAway => false,
+            };
+            if to_largest {
+                res = F::largest();
+            }
+        }
+        status.and(if val.negative { -res } else { res })
+    }
+
+    /// Converts unsigned integer into posit with the given rounding mode
+    pub fn from_u1024_r(val: u1024, round: Round) -> StatusAnd<Posit512> {
+        if val == u1024::ZERO {
+            return Status::OK.and(Posit512::ZERO);
+        }
+        Posit512::encode(false, 0, val, false, round)
+    }
+
+    /// Converts signed integer into posit with the given rounding mode
+    pub fn from_i1024_r(val: i1024, round: Round) -> StatusAnd<Posit512> {
+        let negative = val.is_negative();
+        let abs = if negative { val.wrapping_neg() } else { val };
+        let res = Posit512::from_u1024_r(u1024::from_le_bytes(abs.to_le_bytes()), round);
+        if negative {
+            res.map(Posit512::neg)
+        } else {
+            res
+        }
+    }
+
+    /// Converts posit into signed integer with the given rounding mode.
+    ///
+    /// Values not fitting the integer are reported with [`Status::INVALID_OP`] and converted into
+    /// the closest integer value; NaR is converted into zero with the same status.
+    pub fn to_i1024_r(self, round: Round) -> StatusAnd<i1024> {
+        let val = match self.unpack() {
+            _ if self.is_nar() => return Status::INVALID_OP.and(i1024::ZERO),
+            None => return Status::OK.and(i1024::ZERO),
+            Some(val) => val,
+        };
+        let saturated = Status::INVALID_OP.and(if val.negative { i1024::MIN } else { i1024::MAX });
+        let (abs, inexact) = match val.exp {
+            exp if exp + SIG_TOP as i32 >= 1024 => return saturated,
+            exp if exp >= 0 => (val.sig << exp as usize, false),
+            exp => round_off(val.sig, exp.unsigned_abs(), false, val.negative, round),
+        };
+        let limit = if val.negative { u1024::ONE << 1023 } else { (u1024::ONE << 1023) - 1u8 };
+        if abs > limit {
+            return saturated;
+        }
+        let int = i1024::from_le_bytes(abs.to_le_bytes());
+        let status = if inexact { Status::INEXACT } else { Status::OK };
+        status.and(if val.negative { int.wrapping_neg() } else { int })
+    }
+
+    /// Splits finite non-zero value into sign, exponent and significand with the leading bit at
+    /// [`SIG_TOP`] position. Returns `None` for zero and NaR values.
+    fn unpack(self) -> Option<Unpacked> {
+        if self.is_zero() || self.is_nar() {
+            return None;
+        }
+        let negative = self.is_negative();
+        // Drop the sign bit, so the regime starts from the most significant bit
+        let body = self.abs().0 << 1;
+        let (regime, len) = if body.bit(511) {
+            let ones = body.leading_ones();
+            (ones as i32 - 1, ones)
+        } else {
+            let zeros = body.leading_zeros();
+            (-(zeros as i32), zeros)
+        };
+        // Exponent and fraction bits truncated by a long regime are zeros
+        let rest = body.checked_shl(len + 1).unwrap_or(u512::ZERO);
+        let exp = (rest >> (512 - ES as usize)).low_u32() as i32;
+        let fraction = widen((rest << ES as usize).to_le_bytes()) >> (512 - SIG_TOP as usize);
+        Some(Unpacked {
+            negative,
+            exp: (regime << ES) + exp - SIG_TOP as i32,
+            sig: (u1024::ONE << SIG_TOP as usize) | fraction,
+        })
+    }
+
+    /// Encodes value `sig * 2^exp`, where `sig` is non-zero, rounding the bits which do not fit
+    /// the format. `sticky` indicates whether the value has non-zero bits below `sig`.
+    fn encode(
+        negative: bool,
+        exp: i32,
+        sig: u1024,
+        sticky: bool,
+        round: Round,
+    ) -> StatusAnd<Posit512> {
+        let top = 1023 - sig.leading_zeros();
+        let scale = exp + top as i32;
+        let regime = scale >> ES;
+        let (abs, inexact) = if regime > MAX_REGIME {
+            let exact = regime == MAX_REGIME + 1
+                && scale & ((1 << ES) - 1) == 0
+                && sig.trailing_zeros() == top
+                && !sticky;
+            (widen(Posit512::MAX.0.to_le_bytes()), !exact)
+        } else if regime < MIN_REGIME {
+            (u1024::ONE, true)
+        } else {
+            let (sig, sticky) = match top.cmp(&SIG_TOP) {
+                Ordering::Greater => {
+                    let (sig, lost) = shr_sticky(sig, top - SIG_TOP);
+                    (sig, sticky || lost)
+                }
+                _ => (sig << (SIG_TOP - top) as usize, sticky),
+            };
+            let fraction = sig ^ (u1024::ONE << SIG_TOP as usize);
+            let (regime_bits, regime_len) = if regime >= 0 {
+                (((u1024::ONE << (regime as usize + 1)) - 1u8) << 1, regime as u32 + 2)
+            } else {
+                (u1024::ONE, regime.unsigned_abs() + 1)
+            };
+            let exp_bits = u1024::from((scale & ((1 << ES) - 1)) as u8);
+            let body = (regime_bits << (ES + SIG_TOP) as usize)
+                | (exp_bits << SIG_TOP as usize)
+                | fraction;
+            // The body is `regime_len + 511` bits long, so the shift leaves exactly 511 bits
+            round_off(body, regime_len, sticky, negative, round)
+        };
+        let status = if inexact { Status::INEXACT } else { Status::OK };
+        let abs = Posit512(u512::from_le_bytes(narrow(abs.to_le_bytes())));
+        status.and(if negative { -abs } else { abs })
+    }
+}
+
+/// Shifts value right, returning whether any of the non-zero bits were shifted out
+fn shr_sticky(val: u1024, shift: u32) -> (u1024, bool) {
+    if shift >= 1024 {
+        return (u1024::ZERO, val != u1024::ZERO);
+    }
+    let mask = (u1024::ONE << shift as usize) - 1u8;
+    (val >> shift as usize, val & mask != u1024::ZERO)
+}
+
+/// Drops `shift` least significant bits of the absolute value, rounding the result according to
+/// the rounding mode. Returns the rounded value and whether the rounding was inexact.
+fn round_off(val: u1024, shift: u32, sticky: bool, negative: bool, round: Round) -> (u1024, bool) {
+    let (kept, guard, sticky) = if shift == 0 {
+        (val, false, sticky)
+    } else {
+        let (half, lost) = shr_sticky(val, shift - 1);
+        (half >> 1, half.bit(0), sticky || lost)
+    };
+    let inexact = guard || sticky;
+    let up = match round {
+        Round::NearestTiesToEven => guard && (sticky || kept.bit(0)),
+        Round::NearestTiesToAway => guard,
+        Round::TowardPositive => inexact && !negative,
+        Round::TowardNegative => inexact && negative,
+        Round::TowardZero => false,
+    };
+    (if up { kept + 1u8 } else { kept }, inexact)
+}
+
+fn widen<const LEN: usize>(bytes: [u8; LEN]) -> u1024 {
+    let mut wide = [0u8; 128];
+    wide[..LEN].copy_from_slice(&bytes);
+    u1024::from_le_bytes(wide)
+}
+
+fn narrow<const LEN: usize>(bytes: [u8; 128]) -> [u8; LEN] {
+    let mut short = [0u8; LEN];
+    short.copy_from_slice(&bytes[..LEN]);
+    short
+}
+
+impl Neg for Posit512 {
+    type Output = Posit512;
+
+    #[inline]
+    fn neg(self) -> Self::Output { Posit512(self.0.wrapping_neg()) }
+}
+
+impl PartialOrd for Posit512 {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+/// Posits are ordered as two's complement integers, with NaR being less than any other value
+impl Ord for Posit512 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0 ^ Posit512::NAR.0).cmp(&(other.0 ^ Posit512::NAR.0))
+    }
+}
+
+impl Display for Posit512 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_nar() {
+            return f.write_str("NaR");
+        }
+        Display::fmt(&self.to_float::<ieee::Quad>(Round::NearestTiesToEven).value, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use super::*;
+
+    fn posit(s: &str) -> Posit512 {
+        Posit512::from_float(ieee::Quad::from_str(s).unwrap(), Round::NearestTiesToEven).value
+    }
+
+    #[test]
+    fn encoding() {
+        assert_eq!(posit("1").to_bits(), u512::ONE << 510);
+        assert_eq!(posit("-1").to_bits(), (u512::ONE << 510).wrapping_neg());
+        assert_eq!(posit("2").to_bits(), (u512::ONE << 510) | (u512::ONE << 507));
+        assert_eq!(posit("16").to_bits(), (u512::ONE << 510) | (u512::ONE << 509));
+        assert_eq!(posit("0.5").to_bits(), (u512::ONE << 509) | (u512::from(3u8) << 507));
+        assert_eq!(posit("0"), Posit512::ZERO);
+        assert!(Posit512::from_float(ieee::Quad::NAN, Round::NearestTiesToEven).value.is_nar());
+        assert!(posit("1").abs() == posit("-1").abs());
+    }
+
+    #[test]
+    fn arithmetics() {
+        let rnd = Round::NearestTiesToEven;
+        let one = posit("1");
+        let three = posit("3");
+        assert_eq!(one.add_r(one, rnd), Status::OK.and(posit("2")));
+        assert_eq!(one.sub_r(three, rnd), Status::OK.and(posit("-2")));
+        assert_eq!(three.mul_r(posit("-0.5"), rnd), Status::OK.and(posit("-1.5")));
+        assert_eq!(three.div_r(posit("2"), rnd), Status::OK.and(posit("1.5")));
+        assert_eq!(one.sub_r(one, rnd), Status::OK.and(Posit512::ZERO));
+
+        let third = one.div_r(three, rnd);
+        assert_eq!(third.status, Status::INEXACT);
+        let back = third.value.mul_r(three, rnd);
+        assert_eq!(back, Status::INEXACT.and(one));
+        let down = one.div_r(three, Round::TowardZero).value;
+        let up = one.div_r(three, Round::TowardPositive).value;
+        assert!(down < up);
+        assert_eq!(down.to_bits() + u512::ONE, up.to_bits());
+
+        assert!(one.div_r(Posit512::ZERO, rnd).value.is_nar());
+        assert!(Posit512::NAR.add_r(one, rnd).value.is_nar());
+    }
+
+    #[test]
+    fn saturation() {
+        let rnd = Round::NearestTiesToEven;
+        let max = Posit512::MAX;
+        let min = Posit512::MIN_POSITIVE;
+        assert_eq!(max.mul_r(max, rnd), Status::INEXACT.and(max));
+        assert_eq!(min.mul_r(min, Round::TowardZero), Status::INEXACT.and(min));
+        assert_eq!((-max).mul_r(max, rnd), Status::INEXACT.and(-max));
+        assert_eq!(max.div_r(min, rnd).value, max);
+        assert_eq!(max.mul_r(min, rnd), Status::OK.and(posit("1")));
+    }
+
+    #[test]
+    fn ordering() {
+        let values = [Posit512::NAR, -Posit512::MAX, posit("-1"), Posit512::ZERO, posit("0.5")];
+        for (no, val) in values.iter().enumerate().skip(1) {
+            assert!(values[no - 1] < *val);
+        }
+        assert!(Posit512::MAX > posit("1e100"));
+    }
+
+    #[test]
+    fn conversions() {
+        let rnd = Round::NearestTiesToEven;
+        let val = ieee::Double::from_str("-1234.5625").unwrap();
+        let p = Posit512::from_float(val, rnd);
+        assert_eq!(p.status, Status::OK);
+        assert_eq!(p.value.to_float::<ieee::Double>(rnd), Status::OK.and(val));
+        let third = posit("1").div_r(posit("3"), rnd).value;
+        assert_eq!(
+            third.to_float::<ieee::Double>(rnd),
+            Status::INEXACT.and(ieee::Double::from_str("0x1.5555555555555p-2").unwrap())
+        );
+        let huge = posit("0x1p+2000").to_float::<ieee::Double>(rnd);
+        assert_eq!(huge.status, Status::OVERFLOW | Status::INEXACT);
+        assert!(huge.value.is_infinite());
+
+        assert_eq!(Posit512::from_i1024_r(i1024::from(-7), rnd), Status::OK.and(posit("-7")));
+        assert_eq!(
+            posit("-7.5").to_i1024_r(Round::TowardZero),
+            Status::INEXACT.and(i1024::from(-7))
+        );
+        assert_eq!(posit("-7.5").to_i1024_r(rnd), Status::INEXACT.and(i1024::from(-8)));
+        assert_eq!(posit("0x1p+1100").to_i1024_r(rnd).status, Status::INVALID_OP);
+    }
+}
@@ -49,8 +49,9 @@ macro_rules! aluasm {
         use alloc::boxed::Box;
 
         use aluvm::isa::{
-            ArithmeticOp, BitwiseOp, CmpOp, ControlFlowOp, DigestOp, FloatEqFlag, Instr, IntFlags,
-            MergeFlag, MoveOp, ReservedOp, PutOp, RoundingFlag, Secp256k1Op, SignFlag, NoneEqFlag,
+            AluReOp, ArithmeticOp, BitwiseOp, CmpOp, ControlFlowOp, Curve25519Op, DigestOp,
+            FloatEqFlag, Instr, IntFlags, MergeFlag, ModularOp, MoveOp, ReservedOp, PutOp,
+            RoundingFlag, Secp256k1Op, Secp256k1VerifyOp, SignFlag, NoneEqFlag, _asm_panic,
         };
         use aluvm::reg::{
             Reg16, Reg32, Reg8, RegA, RegA2, RegBlockAFR, RegBlockAR, RegF, RegR, RegS,
@@ -125,7 +126,7 @@ macro_rules! instr {
         Instr::ControlFlow(ControlFlowOp::Jif($offset))
     };
     (routine $offset:literal) => {
-        Instr::ControlFlow(ControlFlowOp::Reutine($offset))
+        Instr::ControlFlow(ControlFlowOp::Routine($offset))
     };
     (call $offset:literal @ $lib:ident) => {
         Instr::ControlFlow(ControlFlowOp::Call(LibSite::with(
@@ -220,13 +221,13 @@ macro_rules! instr {
             (RegBlockAFR::A, RegBlockAFR::A) => Instr::Move(MoveOp::CnvA(
                 _reg_tya!(Reg, $reg1),
                 _reg_idx!($idx1),
-                _reg_tya!(Reg, $reg1),
+                _reg_tya!(Reg, $reg2),
                 _reg_idx!($idx2),
             )),
             (RegBlockAFR::F, RegBlockAFR::F) => Instr::Move(MoveOp::CnvF(
                 _reg_tyf!(Reg, $reg1),
                 _reg_idx!($idx1),
-                _reg_tyf!(Reg, $reg1),
+                _reg_tyf!(Reg, $reg2),
                 _reg_idx!($idx2),
             )),
             (_, _) => panic!("Conversion operation between unsupported register types"),
@@ -563,6 +564,86 @@ macro_rules! instr {
     (abs $reg:ident[$idx:literal]) => {
         Instr::Arithmetic(ArithmeticOp::Abs(_reg_ty!(Reg, $reg).into(), _reg_idx16!($idx)))
     };
+    (
+        addmod
+        $reg1:ident[$idx1:literal],
+        $reg2:ident[$idx2:literal],
+        $reg3:ident[$idx3:literal],
+        $reg4:ident[$idx4:literal]
+    ) => {
+        if _reg_ty!(Reg, $reg1) != _reg_ty!(Reg, $reg2)
+            || _reg_ty!(Reg, $reg2) != _reg_ty!(Reg, $reg3)
+            || _reg_ty!(Reg, $reg3) != _reg_ty!(Reg, $reg4)
+        {
+            panic!("`addmod` operation must use the same type of registers for all operands");
+        } else {
+            Instr::Modular(ModularOp::AddMod(
+                _reg_tya!(Reg, $reg1),
+                _reg_idx!($idx1),
+                _reg_idx!($idx2),
+                _reg_idx!($idx3),
+                _reg_idx!($idx4),
+            ))
+        }
+    };
+    (
+        mulmod
+        $reg1:ident[$idx1:literal],
+        $reg2:ident[$idx2:literal],
+        $reg3:ident[$idx3:literal],
+        $reg4:ident[$idx4:literal]
+    ) => {
+        if _reg_ty!(Reg, $reg1) != _reg_ty!(Reg, $reg2)
+            || _reg_ty!(Reg, $reg2) != _reg_ty!(Reg, $reg3)
+            || _reg_ty!(Reg, $reg3) != _reg_ty!(Reg, $reg4)
+        {
+            panic!("`mulmod` operation must use the same type of registers for all operands");
+        } else {
+            Instr::Modular(ModularOp::MulMod(
+                _reg_tya!(Reg, $reg1),
+                _reg_idx!($idx1),
+                _reg_idx!($idx2),
+                _reg_idx!($idx3),
+                _reg_idx!($idx4),
+            ))
+        }
+    };
+    (
+        powmod
+        $reg1:ident[$idx1:literal],
+        $reg2:ident[$idx2:literal],
+        $reg3:ident[$idx3:literal],
+        $reg4:ident[$idx4:literal]
+    ) => {
+        if _reg_ty!(Reg, $reg1) != _reg_ty!(Reg, $reg2)
+            || _reg_ty!(Reg, $reg2) != _reg_ty!(Reg, $reg3)
+            || _reg_ty!(Reg, $reg3) != _reg_ty!(Reg, $reg4)
+        {
+            panic!("`powmod` operation must use the same type of registers for all operands");
+        } else {
+            Instr::Modular(ModularOp::PowMod(
+                _reg_tya!(Reg, $reg1),
+                _reg_idx!($idx1),
+                _reg_idx!($idx2),
+                _reg_idx!($idx3),
+                _reg_idx!($idx4),
+            ))
+        }
+    };
+    (invmod $reg1:ident[$idx1:literal], $reg2:ident[$idx2:literal], $reg3:ident[$idx3:literal]) => {
+        if _reg_ty!(Reg, $reg1) != _reg_ty!(Reg, $reg2)
+            || _reg_ty!(Reg, $reg2) != _reg_ty!(Reg, $reg3)
+        {
+            panic!("`invmod` operation must use the same type of registers for all operands");
+        } else {
+            Instr::Modular(ModularOp::InvMod(
+                _reg_tya!(Reg, $reg1),
+                _reg_idx!($idx1),
+                _reg_idx!($idx2),
+                _reg_idx!($idx3),
+            ))
+        }
+    };
 
     (and $reg1:ident[$idx1:literal], $reg2:ident[$idx2:literal], $reg3:ident[$idx3:literal]) => {
         if _reg_ty!(Reg, $reg1) != _reg_ty!(Reg, $reg2)
@@ -683,6 +764,65 @@ macro_rules! instr {
     (sha2 s16[$idx1:literal],r512[$idx2:literal]) => {
         Instr::Digest(DigestOp::Sha512(RegS::from($idx1), _reg_idx16!($idx2)))
     };
+    (sha3 s16[$idx1:literal],r256[$idx2:literal]) => {
+        Instr::Digest(DigestOp::Sha3(RegS::from($idx1), _reg_idx8!($idx2)))
+    };
+    (keccak s16[$idx1:literal],r256[$idx2:literal]) => {
+        Instr::Digest(DigestOp::Keccak(RegS::from($idx1), _reg_idx8!($idx2)))
+    };
+    (blake2 s16[$idx1:literal],r512[$idx2:literal]) => {
+        Instr::Digest(DigestOp::Blake2b(RegS::from($idx1), _reg_idx8!($idx2)))
+    };
+    (blake3 s16[$idx1:literal],r256[$idx2:literal]) => {
+        Instr::Digest(DigestOp::Blake3(RegS::from($idx1), _reg_idx8!($idx2)))
+    };
+    (hash160 s16[$idx1:literal],r160[$idx2:literal]) => {
+        Instr::Digest(DigestOp::Hash160(RegS::from($idx1), _reg_idx8!($idx2)))
+    };
+    (hash256 s16[$idx1:literal],r256[$idx2:literal]) => {
+        Instr::Digest(DigestOp::Hash256(RegS::from($idx1), _reg_idx8!($idx2)))
+    };
+    (tagged s16[$idx1:literal],s16[$idx2:literal],r256[$idx3:literal]) => {
+        Instr::Digest(DigestOp::TaggedSha256(
+            RegS::from($idx1),
+            RegS::from($idx2),
+            _reg_idx16!($idx3),
+        ))
+    };
+    (hmac s16[$idx1:literal],s16[$idx2:literal],r256[$idx3:literal]) => {
+        Instr::Digest(DigestOp::HmacSha256(
+            RegS::from($idx1),
+            RegS::from($idx2),
+            _reg_idx16!($idx3),
+        ))
+    };
+    (hmac s16[$idx1:literal],s16[$idx2:literal],r512[$idx3:literal]) => {
+        Instr::Digest(DigestOp::HmacSha512(
+            RegS::from($idx1),
+            RegS::from($idx2),
+            _reg_idx16!($idx3),
+        ))
+    };
+    // HKDF-SHA256 extract step is an alias for HMAC-SHA256 keyed with the salt
+    (hkdfext s16[$idx1:literal],s16[$idx2:literal],r256[$idx3:literal]) => {
+        Instr::Digest(DigestOp::HmacSha256(
+            RegS::from($idx1),
+            RegS::from($idx2),
+            _reg_idx16!($idx3),
+        ))
+    };
+    (hkdfexp r256[$idx1:literal],s16[$idx2:literal],a16[$idx3:literal],s16[$idx4:literal]) => {
+        Instr::Digest(DigestOp::HkdfExpand(
+            _reg_idx!($idx1),
+            RegS::from($idx2),
+            _reg_idx!($idx3),
+            RegS::from($idx4),
+        ))
+    };
+
+    (hcall $id:literal) => {
+        Instr::AluRe(AluReOp::HCall($id))
+    };
 
     (secpgen $reg1:ident[$idx1:literal], $reg2:ident[$idx2:literal]) => {
         if _reg_block!($reg1) != RegBlockAFR::R || _reg_block!($reg2) != RegBlockAFR::R {
@@ -719,6 +859,52 @@ macro_rules! instr {
             Instr::Secp256k1(Secp256k1Op::Neg(_reg_idx!($idx1), _reg_idx8!($idx2)))
         }
     };
+    (ecdsa r256[$idx1:literal],r512[$idx2:literal],r512[$idx3:literal]) => {
+        Instr::Secp256k1Verify(Secp256k1VerifyOp::Ecdsa(
+            _reg_idx!($idx1),
+            _reg_idx!($idx2),
+            _reg_idx!($idx3),
+        ))
+    };
+    (ecdsa r256[$idx1:literal],r512[$idx2:literal],s16[$idx3:literal]) => {
+        Instr::Secp256k1Verify(Secp256k1VerifyOp::EcdsaDer(
+            _reg_idx!($idx1),
+            _reg_idx!($idx2),
+            RegS::from($idx3),
+        ))
+    };
+    (schnorr r256[$idx1:literal],r256[$idx2:literal],r512[$idx3:literal]) => {
+        Instr::Secp256k1Verify(Secp256k1VerifyOp::Schnorr(
+            _reg_idx!($idx1),
+            _reg_idx!($idx2),
+            _reg_idx!($idx3),
+        ))
+    };
+
+    (edgen r256[$idx1:literal],r256[$idx2:literal]) => {
+        Instr::Curve25519(Curve25519Op::Gen(_reg_idx!($idx1), _reg_idx8!($idx2)))
+    };
+    (edmul $reg1:ident[$idx1:literal],r256[$idx2:literal],r256[$idx3:literal]) => {
+        Instr::Curve25519(Curve25519Op::Mul(
+            _reg_block_ar!($reg1),
+            _reg_idx!($idx1),
+            _reg_idx!($idx2),
+            _reg_idx!($idx3),
+        ))
+    };
+    (edadd r256[$idx1:literal],r256[$idx2:literal],r256[$idx3:literal]) => {
+        Instr::Curve25519(Curve25519Op::Add(_reg_idx!($idx1), _reg_idx!($idx2), _reg_idx!($idx3)))
+    };
+    (edneg r256[$idx1:literal],r256[$idx2:literal]) => {
+        Instr::Curve25519(Curve25519Op::Neg(_reg_idx!($idx1), _reg_idx8!($idx2)))
+    };
+    (edver s16[$idx1:literal],r256[$idx2:literal],r512[$idx3:literal]) => {
+        Instr::Curve25519(Curve25519Op::Verify(
+            RegS::from($idx1),
+            _reg_idx!($idx2),
+            _reg_idx!($idx3),
+        ))
+    };
 }
 
 #[doc(hidden)]
@@ -1012,6 +1198,15 @@ macro_rules! _reg_ty {
     };
 }
 
+/// Panics with the provided message, rejecting operand which is not supported by the instruction.
+///
+/// Used by [`aluasm!`] instead of `panic!` in the macro arms which are expanded as operands of the
+/// instructions: unlike `panic!` it returns the operand type, so the expansion does not contain
+/// diverging sub-expressions.
+#[doc(hidden)]
+#[track_caller]
+pub fn _asm_panic<T>(msg: &str) -> T { panic!("{}", msg) }
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! _reg_tya2 {
@@ -1051,7 +1246,7 @@ macro_rules! _reg_tya {
         paste! { [<$ident A>] :: A1024 }
     };
     ($ident:ident, $other:ident) => {
-        panic!("operation requires `A` register")
+        _asm_panic("operation requires `A` register")
     };
 }
 
@@ -1083,7 +1278,7 @@ macro_rules! _reg_tyf {
         paste! { [<$ident F>] :: F512 }
     };
     ($ident:ident, $other:ident) => {
-        panic!("operation requires `F` register")
+        _asm_panic("operation requires `F` register")
     };
 }
 
@@ -1115,7 +1310,7 @@ macro_rules! _reg_tyr {
         paste! { [<$ident R>] :: R8192 }
     };
     ($ident:ident, $other:ident) => {
-        panic!("operation requires `R` register")
+        _asm_panic("operation requires `R` register")
     };
 }
 
@@ -1176,7 +1371,7 @@ macro_rules! _rounding_flag {
         RoundingFlag::Ceil
     };
     ($other:ident) => {
-        panic!("wrong float rounding flag")
+        _asm_panic("wrong float rounding flag")
     };
 }
 
@@ -1208,6 +1403,6 @@ macro_rules! _int_flags {
         IntFlags::signed_wrapped()
     };
     ($other:ident) => {
-        panic!("wrong integer operation flags")
+        _asm_panic("wrong integer operation flags")
     };
 }
@@ -14,12 +14,12 @@
 use alloc::boxed::Box;
 use core::ops::RangeInclusive;
 
-use amplify::num::{u1, u2, u3, u5};
+use amplify::num::{u1, u2, u3, u4, u5, u6};
 
 use super::opcodes::*;
 use super::{
-    ArithmeticOp, BitwiseOp, BytesOp, CmpOp, ControlFlowOp, Curve25519Op, DigestOp, Instr,
-    InstructionSet, MoveOp, PutOp, ReservedOp, Secp256k1Op,
+    AluReOp, ArithmeticOp, BitwiseOp, BytesOp, CmpOp, ControlFlowOp, Curve25519Op, DigestOp, Instr,
+    InstructionSet, ModularOp, MoveOp, PutOp, ReservedOp, Secp256k1Op, Secp256k1VerifyOp,
 };
 use crate::data::{ByteStr, MaybeNumber};
 use crate::program::{CodeEofError, LibSite, Read, Write, WriteError};
@@ -68,6 +68,10 @@ pub trait Bytecode {
     #[inline]
     fn call_site(&self) -> Option<LibSite> { None }
 
+    /// If the instruction jumps within the same library, returns the offset of the jump target.
+    #[inline]
+    fn jump_target(&self) -> Option<u16> { None }
+
     /// Writes the instruction as bytecode
     fn write<W>(&self, writer: &mut W) -> Result<(), BytecodeError>
     where
@@ -102,11 +106,15 @@ where
             Instr::Arithmetic(instr) => instr.byte_count(),
             Instr::Bitwise(instr) => instr.byte_count(),
             Instr::Bytes(instr) => instr.byte_count(),
+            Instr::Modular(instr) => instr.byte_count(),
             Instr::Digest(instr) => instr.byte_count(),
             #[cfg(feature = "secp256k1")]
             Instr::Secp256k1(instr) => instr.byte_count(),
             #[cfg(feature = "curve25519")]
             Instr::Curve25519(instr) => instr.byte_count(),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1Verify(instr) => instr.byte_count(),
+            Instr::AluRe(instr) => instr.byte_count(),
             Instr::ExtensionCodes(instr) => instr.byte_count(),
             Instr::ReservedInstruction(instr) => instr.byte_count(),
             Instr::Nop => 1,
@@ -125,11 +133,15 @@ where
             Instr::Arithmetic(instr) => instr.instr_byte(),
             Instr::Bitwise(instr) => instr.instr_byte(),
             Instr::Bytes(instr) => instr.instr_byte(),
+            Instr::Modular(instr) => instr.instr_byte(),
             Instr::Digest(instr) => instr.instr_byte(),
             #[cfg(feature = "secp256k1")]
             Instr::Secp256k1(instr) => instr.instr_byte(),
             #[cfg(feature = "curve25519")]
             Instr::Curve25519(instr) => instr.instr_byte(),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1Verify(instr) => instr.instr_byte(),
+            Instr::AluRe(instr) => instr.instr_byte(),
             Instr::ExtensionCodes(instr) => instr.instr_byte(),
             Instr::ReservedInstruction(instr) => instr.instr_byte(),
             Instr::Nop => 1,
@@ -145,17 +157,45 @@ where
             Instr::Arithmetic(instr) => instr.call_site(),
             Instr::Bitwise(instr) => instr.call_site(),
             Instr::Bytes(instr) => instr.call_site(),
+            Instr::Modular(instr) => instr.call_site(),
             Instr::Digest(instr) => instr.call_site(),
             #[cfg(feature = "secp256k1")]
             Instr::Secp256k1(instr) => instr.call_site(),
             #[cfg(feature = "curve25519")]
             Instr::Curve25519(instr) => instr.call_site(),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1Verify(instr) => instr.call_site(),
+            Instr::AluRe(instr) => instr.call_site(),
             Instr::ExtensionCodes(instr) => instr.call_site(),
             Instr::ReservedInstruction(instr) => instr.call_site(),
             Instr::Nop => None,
         }
     }
 
+    fn jump_target(&self) -> Option<u16> {
+        match self {
+            Instr::ControlFlow(instr) => instr.jump_target(),
+            Instr::Put(instr) => instr.jump_target(),
+            Instr::Move(instr) => instr.jump_target(),
+            Instr::Cmp(instr) => instr.jump_target(),
+            Instr::Arithmetic(instr) => instr.jump_target(),
+            Instr::Bitwise(instr) => instr.jump_target(),
+            Instr::Bytes(instr) => instr.jump_target(),
+            Instr::Modular(instr) => instr.jump_target(),
+            Instr::Digest(instr) => instr.jump_target(),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1(instr) => instr.jump_target(),
+            #[cfg(feature = "curve25519")]
+            Instr::Curve25519(instr) => instr.jump_target(),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1Verify(instr) => instr.jump_target(),
+            Instr::AluRe(instr) => instr.jump_target(),
+            Instr::ExtensionCodes(instr) => instr.jump_target(),
+            Instr::ReservedInstruction(instr) => instr.jump_target(),
+            Instr::Nop => None,
+        }
+    }
+
     fn write_args<W>(&self, writer: &mut W) -> Result<(), BytecodeError>
     where
         W: Write,
@@ -168,11 +208,15 @@ where
             Instr::Arithmetic(instr) => instr.write_args(writer),
             Instr::Bitwise(instr) => instr.write_args(writer),
             Instr::Bytes(instr) => instr.write_args(writer),
+            Instr::Modular(instr) => instr.write_args(writer),
             Instr::Digest(instr) => instr.write_args(writer),
             #[cfg(feature = "secp256k1")]
             Instr::Secp256k1(instr) => instr.write_args(writer),
             #[cfg(feature = "curve25519")]
             Instr::Curve25519(instr) => instr.write_args(writer),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1Verify(instr) => instr.write_args(writer),
+            Instr::AluRe(instr) => instr.write_args(writer),
             Instr::ExtensionCodes(instr) => instr.write_args(writer),
             Instr::ReservedInstruction(instr) => instr.write_args(writer),
             Instr::Nop => Ok(()),
@@ -200,6 +244,9 @@ where
             instr if BytesOp::instr_range().contains(&instr) => {
                 Instr::Bytes(BytesOp::read(reader)?)
             }
+            instr if ModularOp::instr_range().contains(&instr) => {
+                Instr::Modular(ModularOp::read(reader)?)
+            }
             instr if DigestOp::instr_range().contains(&instr) => {
                 Instr::Digest(DigestOp::read(reader)?)
             }
@@ -211,6 +258,17 @@ where
             instr if Curve25519Op::instr_range().contains(&instr) => {
                 Instr::Curve25519(Curve25519Op::read(reader)?)
             }
+            #[cfg(feature = "secp256k1")]
+            instr if Secp256k1VerifyOp::instr_range().contains(&instr) => {
+                Instr::Secp256k1Verify(Secp256k1VerifyOp::read(reader)?)
+            }
+            instr if AluReOp::instr_range().contains(&instr) => {
+                Instr::AluRe(AluReOp::read(reader)?)
+            }
+            // Unallocated opcodes of the ALURE sub-range are not available to other extensions
+            INSTR_ALURE_FROM..=INSTR_ALURE_TO => {
+                Instr::ReservedInstruction(ReservedOp::read(reader)?)
+            }
             INSTR_RESV_FROM..=INSTR_RESV_TO => {
                 Instr::ReservedInstruction(ReservedOp::read(reader)?)
             }
@@ -230,6 +288,16 @@ impl Bytecode for ControlFlowOp {
         }
     }
 
+    #[inline]
+    fn jump_target(&self) -> Option<u16> {
+        match self {
+            ControlFlowOp::Jmp(pos) | ControlFlowOp::Jif(pos) | ControlFlowOp::Routine(pos) => {
+                Some(*pos)
+            }
+            _ => None,
+        }
+    }
+
     fn byte_count(&self) -> u16 {
         match self {
             ControlFlowOp::Fail | ControlFlowOp::Succ => 1,
@@ -878,6 +946,77 @@ impl Bytecode for ArithmeticOp {
     }
 }
 
+impl Bytecode for ModularOp {
+    #[inline]
+    fn byte_count(&self) -> u16 { 4 }
+
+    #[inline]
+    fn instr_range() -> RangeInclusive<u8> { INSTR_ADDMOD..=INSTR_INVMOD }
+
+    fn instr_byte(&self) -> u8 {
+        match self {
+            ModularOp::AddMod(_, _, _, _, _) => INSTR_ADDMOD,
+            ModularOp::MulMod(_, _, _, _, _) => INSTR_MULMOD,
+            ModularOp::PowMod(_, _, _, _, _) => INSTR_POWMOD,
+            ModularOp::InvMod(_, _, _, _) => INSTR_INVMOD,
+        }
+    }
+
+    fn write_args<W>(&self, writer: &mut W) -> Result<(), BytecodeError>
+    where
+        W: Write,
+    {
+        match self {
+            ModularOp::AddMod(reg, src1, src2, modulo, dst)
+            | ModularOp::MulMod(reg, src1, src2, modulo, dst)
+            | ModularOp::PowMod(reg, src1, src2, modulo, dst) => {
+                writer.write_u3(reg)?;
+                writer.write_u5(src1)?;
+                writer.write_u5(src2)?;
+                writer.write_u5(modulo)?;
+                writer.write_u5(dst)?;
+                writer.write_u1(u1::with(0))?;
+            }
+            ModularOp::InvMod(reg, src, modulo, dst) => {
+                writer.write_u3(reg)?;
+                writer.write_u5(src)?;
+                writer.write_u5(modulo)?;
+                writer.write_u5(dst)?;
+                writer.write_u6(u6::with(0))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read<R>(reader: &mut R) -> Result<Self, CodeEofError>
+    where
+        R: Read,
+    {
+        let instr = reader.read_u8()?;
+        let reg = reader.read_u3()?.into();
+        let src1 = reader.read_u5()?.into();
+        let src2 = reader.read_u5()?.into();
+        let src3 = reader.read_u5()?.into();
+
+        Ok(match instr {
+            INSTR_INVMOD => {
+                reader.read_u6()?; // Discard garbage bits
+                Self::InvMod(reg, src1, src2, src3)
+            }
+            instr => {
+                let dst = reader.read_u5()?.into();
+                reader.read_u1()?; // Discard garbage bit
+                match instr {
+                    INSTR_ADDMOD => Self::AddMod(reg, src1, src2, src3, dst),
+                    INSTR_MULMOD => Self::MulMod(reg, src1, src2, src3, dst),
+                    INSTR_POWMOD => Self::PowMod(reg, src1, src2, src3, dst),
+                    x => unreachable!("instruction {:#010b} classified as modular operation", x),
+                }
+            }
+        })
+    }
+}
+
 impl Bytecode for BitwiseOp {
     fn byte_count(&self) -> u16 {
         match self {
@@ -1079,8 +1218,8 @@ impl Bytecode for BytesOp {
             BytesOp::Inj(_, _, _, _) => INSTR_INJ,
             BytesOp::Join(_, _, _) => INSTR_JOIN,
             BytesOp::Splt(_, _, _, _, _) => INSTR_SPLT,
-            BytesOp::Ins(_, _, _, _) => INSTR_DEL,
-            BytesOp::Del(_, _, _, _, _, _, _, _, _) => INSTR_INS,
+            BytesOp::Ins(_, _, _, _) => INSTR_INS,
+            BytesOp::Del(_, _, _, _, _, _, _, _, _) => INSTR_DEL,
             BytesOp::Rev(_, _) => INSTR_REV,
         }
     }
@@ -1260,17 +1399,39 @@ impl Bytecode for BytesOp {
 }
 
 impl Bytecode for DigestOp {
-    #[inline]
-    fn byte_count(&self) -> u16 { 3 }
+    fn byte_count(&self) -> u16 {
+        match self {
+            DigestOp::Ripemd(_, _)
+            | DigestOp::Sha256(_, _)
+            | DigestOp::Sha512(_, _)
+            | DigestOp::Sha3(_, _)
+            | DigestOp::Keccak(_, _)
+            | DigestOp::Blake2b(_, _)
+            | DigestOp::Blake3(_, _)
+            | DigestOp::Hash160(_, _)
+            | DigestOp::Hash256(_, _) => 2,
+            DigestOp::TaggedSha256(_, _, _)
+            | DigestOp::HmacSha256(_, _, _)
+            | DigestOp::HmacSha512(_, _, _) => 3,
+            DigestOp::HkdfExpand(_, _, _, _) => 4,
+        }
+    }
 
     #[inline]
-    fn instr_range() -> RangeInclusive<u8> { INSTR_RIPEMD..=INSTR_SHA512 }
+    fn instr_range() -> RangeInclusive<u8> { INSTR_RIPEMD..=INSTR_SHA256_TAGGED }
 
     fn instr_byte(&self) -> u8 {
         match self {
             DigestOp::Ripemd(_, _) => INSTR_RIPEMD,
             DigestOp::Sha256(_, _) => INSTR_SHA256,
             DigestOp::Sha512(_, _) => INSTR_SHA512,
+            DigestOp::Sha3(_, _) | DigestOp::Keccak(_, _) => INSTR_KECCAK,
+            DigestOp::Blake2b(_, _) | DigestOp::Blake3(_, _) => INSTR_BLAKE,
+            DigestOp::HmacSha256(_, _, _)
+            | DigestOp::HmacSha512(_, _, _)
+            | DigestOp::HkdfExpand(_, _, _, _) => INSTR_HMAC,
+            DigestOp::Hash160(_, _) | DigestOp::Hash256(_, _) => INSTR_HASH_BTC,
+            DigestOp::TaggedSha256(_, _, _) => INSTR_SHA256_TAGGED,
         }
     }
 
@@ -1285,6 +1446,42 @@ impl Bytecode for DigestOp {
                 writer.write_u4(src)?;
                 writer.write_u4(dst)?;
             }
+            DigestOp::Sha3(src, dst)
+            | DigestOp::Blake2b(src, dst)
+            | DigestOp::Hash160(src, dst) => {
+                writer.write_u4(src)?;
+                writer.write_u3(dst)?;
+                writer.write_bool(false)?;
+            }
+            DigestOp::Keccak(src, dst)
+            | DigestOp::Blake3(src, dst)
+            | DigestOp::Hash256(src, dst) => {
+                writer.write_u4(src)?;
+                writer.write_u3(dst)?;
+                writer.write_bool(true)?;
+            }
+            DigestOp::TaggedSha256(tag, src, dst) => {
+                writer.write_u4(tag)?;
+                writer.write_u4(src)?;
+                writer.write_u4(dst)?;
+                writer.write_u4(u4::with(0))?;
+            }
+            DigestOp::HmacSha256(key, src, dst) | DigestOp::HmacSha512(key, src, dst) => {
+                writer.write_bool(false)?;
+                writer.write_bool(matches!(self, DigestOp::HmacSha512(_, _, _)))?;
+                writer.write_u4(key)?;
+                writer.write_u4(src)?;
+                writer.write_u4(dst)?;
+                writer.write_u2(u2::with(0))?;
+            }
+            DigestOp::HkdfExpand(prk, info, len, dst) => {
+                writer.write_bool(true)?;
+                writer.write_u5(prk)?;
+                writer.write_u4(info)?;
+                writer.write_u5(len)?;
+                writer.write_u4(dst)?;
+                writer.write_u5(u5::with(0))?;
+            }
         }
         Ok(())
     }
@@ -1294,18 +1491,102 @@ impl Bytecode for DigestOp {
         R: Read,
     {
         let instr = reader.read_u8()?;
-        let src = reader.read_u4()?.into();
-        let dst = reader.read_u4()?.into();
+        if instr == INSTR_HMAC {
+            return Ok(match reader.read_bool()? {
+                true => {
+                    let i = Self::HkdfExpand(
+                        reader.read_u5()?.into(),
+                        reader.read_u4()?.into(),
+                        reader.read_u5()?.into(),
+                        reader.read_u4()?.into(),
+                    );
+                    reader.read_u5()?; // Discard garbage bits
+                    i
+                }
+                false => {
+                    let sha512 = reader.read_bool()?;
+                    let key = reader.read_u4()?.into();
+                    let src = reader.read_u4()?.into();
+                    let dst = reader.read_u4()?.into();
+                    reader.read_u2()?; // Discard garbage bits
+                    match sha512 {
+                        false => Self::HmacSha256(key, src, dst),
+                        true => Self::HmacSha512(key, src, dst),
+                    }
+                }
+            });
+        }
 
+        let src = reader.read_u4()?.into();
         Ok(match instr {
-            INSTR_RIPEMD => Self::Ripemd(src, dst),
-            INSTR_SHA256 => Self::Sha256(src, dst),
-            INSTR_SHA512 => Self::Sha512(src, dst),
+            INSTR_RIPEMD => Self::Ripemd(src, reader.read_u4()?.into()),
+            INSTR_SHA256 => Self::Sha256(src, reader.read_u4()?.into()),
+            INSTR_SHA512 => Self::Sha512(src, reader.read_u4()?.into()),
+            INSTR_KECCAK => {
+                let dst = reader.read_u3()?.into();
+                match reader.read_bool()? {
+                    false => Self::Sha3(src, dst),
+                    true => Self::Keccak(src, dst),
+                }
+            }
+            INSTR_BLAKE => {
+                let dst = reader.read_u3()?.into();
+                match reader.read_bool()? {
+                    false => Self::Blake2b(src, dst),
+                    true => Self::Blake3(src, dst),
+                }
+            }
+            INSTR_HASH_BTC => {
+                let dst = reader.read_u3()?.into();
+                match reader.read_bool()? {
+                    false => Self::Hash160(src, dst),
+                    true => Self::Hash256(src, dst),
+                }
+            }
+            INSTR_SHA256_TAGGED => {
+                let i = Self::TaggedSha256(src, reader.read_u4()?.into(), reader.read_u4()?.into());
+                reader.read_u4()?; // Discard garbage bits
+                i
+            }
             x => unreachable!("instruction {:#010b} classified as digest operation", x),
         })
     }
 }
 
+impl Bytecode for AluReOp {
+    #[inline]
+    fn byte_count(&self) -> u16 { 3 }
+
+    #[inline]
+    fn instr_range() -> RangeInclusive<u8> { INSTR_HCALL..=INSTR_HCALL }
+
+    fn instr_byte(&self) -> u8 {
+        match self {
+            AluReOp::HCall(_) => INSTR_HCALL,
+        }
+    }
+
+    fn write_args<W>(&self, writer: &mut W) -> Result<(), BytecodeError>
+    where
+        W: Write,
+    {
+        match self {
+            AluReOp::HCall(id) => writer.write_u16(*id)?,
+        }
+        Ok(())
+    }
+
+    fn read<R>(reader: &mut R) -> Result<Self, CodeEofError>
+    where
+        R: Read,
+    {
+        Ok(match reader.read_u8()? {
+            INSTR_HCALL => Self::HCall(reader.read_u16()?),
+            x => unreachable!("instruction {:#010b} classified as runtime extension operation", x),
+        })
+    }
+}
+
 impl Bytecode for Secp256k1Op {
     fn byte_count(&self) -> u16 {
         match self {
@@ -1379,20 +1660,22 @@ impl Bytecode for Curve25519Op {
         match self {
             Curve25519Op::Gen(_, _) => 2,
             Curve25519Op::Mul(_, _, _, _) => 3,
-            Curve25519Op::Add(_, _, _, _) => 3,
+            Curve25519Op::Add(_, _, _) => 3,
             Curve25519Op::Neg(_, _) => 2,
+            Curve25519Op::Verify(_, _, _) => 3,
         }
     }
 
     #[inline]
-    fn instr_range() -> RangeInclusive<u8> { INSTR_ED_GEN..=INSTR_ED_NEG }
+    fn instr_range() -> RangeInclusive<u8> { INSTR_ED_GEN..=INSTR_ED_VERIFY }
 
     fn instr_byte(&self) -> u8 {
         match self {
             Curve25519Op::Gen(_, _) => INSTR_ED_GEN,
             Curve25519Op::Mul(_, _, _, _) => INSTR_ED_MUL,
-            Curve25519Op::Add(_, _, _, _) => INSTR_ED_ADD,
+            Curve25519Op::Add(_, _, _) => INSTR_ED_ADD,
             Curve25519Op::Neg(_, _) => INSTR_ED_NEG,
+            Curve25519Op::Verify(_, _, _) => INSTR_ED_VERIFY,
         }
     }
 
@@ -1411,16 +1694,22 @@ impl Bytecode for Curve25519Op {
                 writer.write_u5(src)?;
                 writer.write_u5(dst)?;
             }
-            Curve25519Op::Add(src1, src2, dst, overflow) => {
+            Curve25519Op::Add(src1, src2, dst) => {
                 writer.write_u5(src1)?;
                 writer.write_u5(src2)?;
                 writer.write_u5(dst)?;
-                writer.write_bool(*overflow)?;
+                writer.write_u1(u1::with(0))?;
             }
             Curve25519Op::Neg(src, dst) => {
                 writer.write_u5(src)?;
                 writer.write_u3(dst)?;
             }
+            Curve25519Op::Verify(msg, key, sig) => {
+                writer.write_u4(msg)?;
+                writer.write_u5(key)?;
+                writer.write_u5(sig)?;
+                writer.write_u2(u2::with(0))?;
+            }
         }
         Ok(())
     }
@@ -1437,18 +1726,111 @@ impl Bytecode for Curve25519Op {
                 reader.read_u5()?.into(),
                 reader.read_u5()?.into(),
             ),
-            INSTR_ED_ADD => Self::Add(
-                reader.read_u5()?.into(),
-                reader.read_u5()?.into(),
-                reader.read_u5()?.into(),
-                reader.read_bool()?,
-            ),
+            INSTR_ED_ADD => {
+                let i = Self::Add(
+                    reader.read_u5()?.into(),
+                    reader.read_u5()?.into(),
+                    reader.read_u5()?.into(),
+                );
+                reader.read_u1()?; // Discard garbage bit
+                i
+            }
             INSTR_ED_NEG => Self::Neg(reader.read_u5()?.into(), reader.read_u3()?.into()),
+            INSTR_ED_VERIFY => {
+                let i = Self::Verify(
+                    reader.read_u4()?.into(),
+                    reader.read_u5()?.into(),
+                    reader.read_u5()?.into(),
+                );
+                reader.read_u2()?; // Discard garbage bits
+                i
+            }
             x => unreachable!("instruction {:#010b} classified as Curve25519 operation", x),
         })
     }
 }
 
+impl Bytecode for Secp256k1VerifyOp {
+    fn byte_count(&self) -> u16 {
+        match self {
+            Secp256k1VerifyOp::Ecdsa(_, _, _) => 3,
+            Secp256k1VerifyOp::EcdsaDer(_, _, _) => 3,
+            Secp256k1VerifyOp::Schnorr(_, _, _) => 3,
+        }
+    }
+
+    #[inline]
+    fn instr_range() -> RangeInclusive<u8> { INSTR_SECP_ECDSA..=INSTR_SECP_SCHNORR }
+
+    fn instr_byte(&self) -> u8 {
+        match self {
+            Secp256k1VerifyOp::Ecdsa(_, _, _) => INSTR_SECP_ECDSA,
+            Secp256k1VerifyOp::EcdsaDer(_, _, _) => INSTR_SECP_ECDSA_DER,
+            Secp256k1VerifyOp::Schnorr(_, _, _) => INSTR_SECP_SCHNORR,
+        }
+    }
+
+    fn write_args<W>(&self, writer: &mut W) -> Result<(), BytecodeError>
+    where
+        W: Write,
+    {
+        match self {
+            Secp256k1VerifyOp::Ecdsa(msg, key, sig) | Secp256k1VerifyOp::Schnorr(msg, key, sig) => {
+                writer.write_u5(msg)?;
+                writer.write_u5(key)?;
+                writer.write_u5(sig)?;
+                writer.write_u1(u1::with(0))?;
+            }
+            Secp256k1VerifyOp::EcdsaDer(msg, key, sig) => {
+                writer.write_u5(msg)?;
+                writer.write_u5(key)?;
+                writer.write_u4(sig)?;
+                writer.write_u2(u2::with(0))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read<R>(reader: &mut R) -> Result<Self, CodeEofError>
+    where
+        R: Read,
+    {
+        Ok(match reader.read_u8()? {
+            INSTR_SECP_ECDSA => {
+                let i = Self::Ecdsa(
+                    reader.read_u5()?.into(),
+                    reader.read_u5()?.into(),
+                    reader.read_u5()?.into(),
+                );
+                reader.read_u1()?; // Discard garbage bit
+                i
+            }
+            INSTR_SECP_ECDSA_DER => {
+                let i = Self::EcdsaDer(
+                    reader.read_u5()?.into(),
+                    reader.read_u5()?.into(),
+                    reader.read_u4()?.into(),
+                );
+                reader.read_u2()?; // Discard garbage bits
+                i
+            }
+            INSTR_SECP_SCHNORR => {
+                let i = Self::Schnorr(
+                    reader.read_u5()?.into(),
+                    reader.read_u5()?.into(),
+                    reader.read_u5()?.into(),
+                );
+                reader.read_u1()?; // Discard garbage bit
+                i
+            }
+            x => unreachable!(
+                "instruction {:#010b} classified as Secp256k1 signature verification",
+                x
+            ),
+        })
+    }
+}
+
 impl Bytecode for ReservedOp {
     #[inline]
     fn byte_count(&self) -> u16 { 1 }
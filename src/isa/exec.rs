@@ -16,16 +16,23 @@ use alloc::vec::Vec;
 use core::cmp::Ordering;
 use core::ops::{BitAnd, BitOr, BitXor, Neg, Rem, Shl, Shr};
 
-use bitcoin_hashes::{ripemd160, sha256, sha512, Hash};
+use bitcoin_hashes::hmac::{Hmac, HmacEngine};
+use bitcoin_hashes::{hash160, ripemd160, sha256, sha256d, sha512, Hash, HashEngine};
+use blake2::Blake2b;
+use sha3::{Digest, Keccak256, Sha3_256};
 
 use super::{
-    ArithmeticOp, BitwiseOp, Bytecode, BytesOp, CmpOp, ControlFlowOp, Curve25519Op, DigestOp,
-    Instr, MoveOp, PutOp, ReservedOp, Secp256k1Op,
+    AluReOp, ArithmeticOp, BitwiseOp, Bytecode, BytesOp, CmpOp, ControlFlowOp, Curve25519Op,
+    DigestOp, Instr, ModularOp, MoveOp, PutOp, ReservedOp, Secp256k1Op, Secp256k1VerifyOp,
 };
 use crate::data::{ByteStr, MaybeNumber, Number, NumberLayout};
-use crate::isa::{ExtendFlag, FloatEqFlag, IntFlags, MergeFlag, NoneEqFlag, SignFlag};
+use crate::isa::{
+    DeleteFlag, ExtendFlag, FloatEqFlag, InsertFlag, IntFlags, MergeFlag, NoneEqFlag, SignFlag,
+    SplitFlag,
+};
 use crate::program::{constants, LibSite};
-use crate::reg::{CoreRegs, NumericRegister, Reg32, RegA, RegR};
+use crate::reg::{CoreRegs, NumericRegister, Reg32, RegA, RegA2, RegR, RegS};
+use crate::vm::Halt;
 
 /// Turing machine movement after instruction execution
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -33,6 +40,9 @@ pub enum ExecStep {
     /// Stop program execution
     Stop,
 
+    /// Stop program execution for a specific reason
+    Halt(Halt),
+
     /// Move to the next instruction
     Next,
 
@@ -41,6 +51,9 @@ pub enum ExecStep {
 
     /// Jump to another code fragment
     Call(LibSite),
+
+    /// Call host function with the provided id and move to the next instruction
+    Host(u16),
 }
 
 /// Trait for instructions
@@ -51,6 +64,19 @@ pub trait InstructionSet: Bytecode + core::fmt::Display + core::fmt::Debug {
     /// starting with non-number.
     fn isa_ids() -> BTreeSet<&'static str>;
 
+    /// ISA Extensions declared by every library assembled from the instructions of the set.
+    ///
+    /// Defaults to [`InstructionSet::isa_ids`]. Instruction sets may leave out optional extensions,
+    /// which are declared by a library only if its code uses them (see
+    /// [`InstructionSet::instr_isa_ids`]).
+    #[inline]
+    fn base_isa_ids() -> BTreeSet<&'static str> { Self::isa_ids() }
+
+    /// ISA Extensions used by the instruction which are not part of
+    /// [`InstructionSet::base_isa_ids`]
+    #[inline]
+    fn instr_isa_ids(&self) -> BTreeSet<&'static str> { BTreeSet::new() }
+
     /// ISA Extension IDs represented as a standard string (space-separated)
     ///
     /// Concatenated length of the ISA IDs joined via ' ' character must not exceed 128 bytes.
@@ -71,6 +97,17 @@ pub trait InstructionSet: Bytecode + core::fmt::Display + core::fmt::Debug {
     #[inline]
     fn complexity(&self) -> u64 { 1 }
 
+    /// Returns size in bytes of the widest numeric (`A`, `F` or `R`) register used by the
+    /// instruction, or zero if the instruction does not operate numeric registers.
+    #[inline]
+    fn reg_bytes(&self) -> u16 { 0 }
+
+    /// Returns total length of the byte strings the instruction operates on, taking them from the
+    /// provided registers before the instruction execution. Instructions which do not use `S`
+    /// registers return zero.
+    #[inline]
+    fn str_bytes(&self, _regs: &CoreRegs) -> u32 { 0 }
+
     /// Executes given instruction taking all registers as input and output.
     ///
     /// # Arguments
@@ -91,14 +128,105 @@ where
 {
     #[inline]
     fn isa_ids() -> BTreeSet<&'static str> {
+        let mut set = Self::base_isa_ids();
+        set.extend(ModularOp::isa_ids());
+        set.extend(AluReOp::isa_ids());
+        set
+    }
+
+    #[inline]
+    fn base_isa_ids() -> BTreeSet<&'static str> {
         let mut set = BTreeSet::new();
         set.insert(constants::ISA_ID_ALU);
         set.extend(DigestOp::isa_ids());
         set.extend(Secp256k1Op::isa_ids());
         set.extend(Curve25519Op::isa_ids());
+        set.extend(Secp256k1VerifyOp::isa_ids());
         set
     }
 
+    #[inline]
+    fn instr_isa_ids(&self) -> BTreeSet<&'static str> {
+        match self {
+            Instr::Modular(_) => ModularOp::isa_ids(),
+            Instr::AluRe(_) => AluReOp::isa_ids(),
+            _ => BTreeSet::new(),
+        }
+    }
+
+    #[inline]
+    fn complexity(&self) -> u64 {
+        match self {
+            Instr::ControlFlow(instr) => instr.complexity(),
+            Instr::Put(instr) => instr.complexity(),
+            Instr::Move(instr) => instr.complexity(),
+            Instr::Cmp(instr) => instr.complexity(),
+            Instr::Arithmetic(instr) => instr.complexity(),
+            Instr::Bitwise(instr) => instr.complexity(),
+            Instr::Bytes(instr) => instr.complexity(),
+            Instr::Modular(instr) => instr.complexity(),
+            Instr::Digest(instr) => instr.complexity(),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1(instr) => instr.complexity(),
+            #[cfg(feature = "curve25519")]
+            Instr::Curve25519(instr) => instr.complexity(),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1Verify(instr) => instr.complexity(),
+            Instr::AluRe(instr) => instr.complexity(),
+            Instr::ExtensionCodes(instr) => instr.complexity(),
+            Instr::ReservedInstruction(_) => ControlFlowOp::Fail.complexity(),
+            Instr::Nop => 1,
+        }
+    }
+
+    #[inline]
+    fn reg_bytes(&self) -> u16 {
+        match self {
+            Instr::ControlFlow(instr) => instr.reg_bytes(),
+            Instr::Put(instr) => instr.reg_bytes(),
+            Instr::Move(instr) => instr.reg_bytes(),
+            Instr::Cmp(instr) => instr.reg_bytes(),
+            Instr::Arithmetic(instr) => instr.reg_bytes(),
+            Instr::Bitwise(instr) => instr.reg_bytes(),
+            Instr::Bytes(instr) => instr.reg_bytes(),
+            Instr::Modular(instr) => instr.reg_bytes(),
+            Instr::Digest(instr) => instr.reg_bytes(),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1(instr) => instr.reg_bytes(),
+            #[cfg(feature = "curve25519")]
+            Instr::Curve25519(instr) => instr.reg_bytes(),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1Verify(instr) => instr.reg_bytes(),
+            Instr::AluRe(instr) => instr.reg_bytes(),
+            Instr::ExtensionCodes(instr) => instr.reg_bytes(),
+            Instr::ReservedInstruction(_) | Instr::Nop => 0,
+        }
+    }
+
+    #[inline]
+    fn str_bytes(&self, regs: &CoreRegs) -> u32 {
+        match self {
+            Instr::ControlFlow(instr) => instr.str_bytes(regs),
+            Instr::Put(instr) => instr.str_bytes(regs),
+            Instr::Move(instr) => instr.str_bytes(regs),
+            Instr::Cmp(instr) => instr.str_bytes(regs),
+            Instr::Arithmetic(instr) => instr.str_bytes(regs),
+            Instr::Bitwise(instr) => instr.str_bytes(regs),
+            Instr::Bytes(instr) => instr.str_bytes(regs),
+            Instr::Modular(instr) => instr.str_bytes(regs),
+            Instr::Digest(instr) => instr.str_bytes(regs),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1(instr) => instr.str_bytes(regs),
+            #[cfg(feature = "curve25519")]
+            Instr::Curve25519(instr) => instr.str_bytes(regs),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1Verify(instr) => instr.str_bytes(regs),
+            Instr::AluRe(instr) => instr.str_bytes(regs),
+            Instr::ExtensionCodes(instr) => instr.str_bytes(regs),
+            Instr::ReservedInstruction(_) | Instr::Nop => 0,
+        }
+    }
+
     #[inline]
     fn exec(&self, regs: &mut CoreRegs, site: LibSite) -> ExecStep {
         match self {
@@ -109,11 +237,15 @@ where
             Instr::Arithmetic(instr) => instr.exec(regs, site),
             Instr::Bitwise(instr) => instr.exec(regs, site),
             Instr::Bytes(instr) => instr.exec(regs, site),
+            Instr::Modular(instr) => instr.exec(regs, site),
             Instr::Digest(instr) => instr.exec(regs, site),
             #[cfg(feature = "secp256k1")]
             Instr::Secp256k1(instr) => instr.exec(regs, site),
             #[cfg(feature = "curve25519")]
             Instr::Curve25519(instr) => instr.exec(regs, site),
+            #[cfg(feature = "secp256k1")]
+            Instr::Secp256k1Verify(instr) => instr.exec(regs, site),
+            Instr::AluRe(instr) => instr.exec(regs, site),
             Instr::ExtensionCodes(instr) => instr.exec(regs, site),
             Instr::ReservedInstruction(_) => ControlFlowOp::Fail.exec(regs, site),
             Instr::Nop => ExecStep::Next,
@@ -129,35 +261,39 @@ impl InstructionSet for ControlFlowOp {
     fn complexity(&self) -> u64 { 2 }
 
     fn exec(&self, regs: &mut CoreRegs, site: LibSite) -> ExecStep {
+        // Call stack stores the location of the instruction following the call
+        let ret_site = LibSite::with(site.pos.saturating_add(self.byte_count()), site.lib);
         match self {
             ControlFlowOp::Fail => {
                 regs.st0 = false;
-                ExecStep::Stop
+                ExecStep::Halt(Halt::Fail)
             }
             ControlFlowOp::Succ => {
                 regs.st0 = true;
-                ExecStep::Stop
+                ExecStep::Halt(Halt::Succ)
             }
             ControlFlowOp::Jmp(offset) => {
-                regs.jmp().map(|_| ExecStep::Jump(*offset)).unwrap_or(ExecStep::Stop)
+                regs.jmp().map(|_| ExecStep::Jump(*offset)).unwrap_or_else(ExecStep::Halt)
             }
             ControlFlowOp::Jif(offset) => {
                 if regs.st0 {
-                    regs.jmp().map(|_| ExecStep::Jump(*offset)).unwrap_or(ExecStep::Stop)
+                    regs.jmp().map(|_| ExecStep::Jump(*offset)).unwrap_or_else(ExecStep::Halt)
                 } else {
                     ExecStep::Next
                 }
             }
             ControlFlowOp::Routine(offset) => {
-                regs.call(site).map(|_| ExecStep::Jump(*offset)).unwrap_or(ExecStep::Stop)
+                regs.call(ret_site).map(|_| ExecStep::Jump(*offset)).unwrap_or_else(ExecStep::Halt)
             }
             ControlFlowOp::Call(site) => {
-                regs.call(*site).map(|_| ExecStep::Call(*site)).unwrap_or(ExecStep::Stop)
+                regs.call(ret_site).map(|_| ExecStep::Call(*site)).unwrap_or_else(ExecStep::Halt)
             }
             ControlFlowOp::Exec(site) => {
-                regs.jmp().map(|_| ExecStep::Call(*site)).unwrap_or(ExecStep::Stop)
+                regs.jmp().map(|_| ExecStep::Call(*site)).unwrap_or_else(ExecStep::Halt)
+            }
+            ControlFlowOp::Ret => {
+                regs.ret().map(ExecStep::Call).unwrap_or(ExecStep::Halt(Halt::Ret))
             }
-            ControlFlowOp::Ret => regs.ret().map(ExecStep::Call).unwrap_or(ExecStep::Stop),
         }
     }
 }
@@ -169,6 +305,15 @@ impl InstructionSet for PutOp {
     #[inline]
     fn complexity(&self) -> u64 { 2 }
 
+    #[inline]
+    fn reg_bytes(&self) -> u16 {
+        match self {
+            PutOp::ClrA(reg, _) | PutOp::PutA(reg, _, _) | PutOp::PutIfA(reg, _, _) => reg.bytes(),
+            PutOp::ClrF(reg, _) | PutOp::PutF(reg, _, _) => reg.bytes(),
+            PutOp::ClrR(reg, _) | PutOp::PutR(reg, _, _) | PutOp::PutIfR(reg, _, _) => reg.bytes(),
+        }
+    }
+
     fn exec(&self, regs: &mut CoreRegs, _: LibSite) -> ExecStep {
         match self {
             PutOp::ClrA(reg, index) => {
@@ -214,6 +359,27 @@ impl InstructionSet for MoveOp {
     #[inline]
     fn isa_ids() -> BTreeSet<&'static str> { BTreeSet::default() }
 
+    #[inline]
+    fn reg_bytes(&self) -> u16 {
+        match self {
+            MoveOp::MovA(reg, _, _) | MoveOp::DupA(reg, _, _) | MoveOp::SwpA(reg, _, _) => {
+                reg.bytes()
+            }
+            MoveOp::MovF(reg, _, _) | MoveOp::DupF(reg, _, _) | MoveOp::SwpF(reg, _, _) => {
+                reg.bytes()
+            }
+            MoveOp::MovR(reg, _, _) | MoveOp::DupR(reg, _, _) => reg.bytes(),
+            MoveOp::CpyA(reg1, _, reg2, _) | MoveOp::CnvA(reg1, _, reg2, _) => {
+                reg1.bytes().max(reg2.bytes())
+            }
+            MoveOp::CnvF(reg1, _, reg2, _) => reg1.bytes().max(reg2.bytes()),
+            MoveOp::CpyR(reg1, _, reg2, _) => reg1.bytes().max(reg2.bytes()),
+            MoveOp::SpyAR(reg1, _, reg2, _) => reg1.bytes().max(reg2.bytes()),
+            MoveOp::CnvAF(reg1, _, reg2, _) => reg1.bytes().max(reg2.bytes()),
+            MoveOp::CnvFA(reg1, _, reg2, _) => reg1.bytes().max(reg2.bytes()),
+        }
+    }
+
     fn exec(&self, regs: &mut CoreRegs, _: LibSite) -> ExecStep {
         match self {
             MoveOp::MovA(reg, idx1, idx2) => {
@@ -254,7 +420,7 @@ impl InstructionSet for MoveOp {
                 regs.set(dreg, didx, val);
             }
             MoveOp::CnvA(sreg, sidx, dreg, didx) => {
-                let mut val = regs.get(sreg, sidx);
+                let mut val = MaybeNumber::from(regs.get(sreg, sidx).map(Number::into_signed));
                 regs.st0 = val.reshape(dreg.layout().into_signed());
                 regs.set(dreg, didx, val);
             }
@@ -276,13 +442,13 @@ impl InstructionSet for MoveOp {
                 regs.set(sreg, sidx, val2);
             }
             MoveOp::CnvAF(sreg, sidx, dreg, didx) => {
-                let mut val = regs.get(sreg, sidx);
+                let mut val = MaybeNumber::from(regs.get(sreg, sidx).map(Number::into_signed));
                 regs.st0 = val.reshape(dreg.layout());
                 regs.set(dreg, didx, val);
             }
             MoveOp::CnvFA(sreg, sidx, dreg, didx) => {
                 let mut val = regs.get(sreg, sidx);
-                regs.st0 = val.reshape(dreg.layout());
+                regs.st0 = val.reshape(dreg.layout().into_signed());
                 regs.set(dreg, didx, val);
             }
         }
@@ -294,6 +460,27 @@ impl InstructionSet for CmpOp {
     #[inline]
     fn isa_ids() -> BTreeSet<&'static str> { BTreeSet::default() }
 
+    #[inline]
+    fn reg_bytes(&self) -> u16 {
+        match self {
+            CmpOp::GtA(_, reg, _, _)
+            | CmpOp::LtA(_, reg, _, _)
+            | CmpOp::EqA(_, reg, _, _)
+            | CmpOp::IfZA(reg, _)
+            | CmpOp::IfNA(reg, _)
+            | CmpOp::St(_, reg, _) => reg.bytes(),
+            CmpOp::GtF(_, reg, _, _) | CmpOp::LtF(_, reg, _, _) | CmpOp::EqF(_, reg, _, _) => {
+                reg.bytes()
+            }
+            CmpOp::GtR(reg, _, _)
+            | CmpOp::LtR(reg, _, _)
+            | CmpOp::EqR(_, reg, _, _)
+            | CmpOp::IfZR(reg, _)
+            | CmpOp::IfNR(reg, _) => reg.bytes(),
+            CmpOp::StInv => 0,
+        }
+    }
+
     fn exec(&self, regs: &mut CoreRegs, _: LibSite) -> ExecStep {
         match self {
             CmpOp::GtA(sign_flag, reg, idx1, idx2) => {
@@ -415,6 +602,23 @@ impl InstructionSet for ArithmeticOp {
         }
     }
 
+    #[inline]
+    fn reg_bytes(&self) -> u16 {
+        match self {
+            ArithmeticOp::AddA(_, reg, _, _)
+            | ArithmeticOp::SubA(_, reg, _, _)
+            | ArithmeticOp::MulA(_, reg, _, _)
+            | ArithmeticOp::DivA(_, reg, _, _)
+            | ArithmeticOp::Stp(reg, _, _) => reg.bytes(),
+            ArithmeticOp::AddF(_, reg, _, _)
+            | ArithmeticOp::SubF(_, reg, _, _)
+            | ArithmeticOp::MulF(_, reg, _, _)
+            | ArithmeticOp::DivF(_, reg, _, _) => reg.bytes(),
+            ArithmeticOp::Rem(reg1, _, reg2, _) => reg1.bytes().max(reg2.bytes()),
+            ArithmeticOp::Neg(reg, _) | ArithmeticOp::Abs(reg, _) => reg.bytes(),
+        }
+    }
+
     fn exec(&self, regs: &mut CoreRegs, _: LibSite) -> ExecStep {
         let is_some = match self {
             ArithmeticOp::Abs(reg, idx) => {
@@ -477,11 +681,9 @@ impl InstructionSet for ArithmeticOp {
                 reg,
                 idx,
                 regs.get(reg, idx).and_then(|val| {
+                    // Negative steps wrap into two's complement of the register layout
                     let mut n = Number::from(*step);
-                    debug_assert!(
-                        n.reshape(val.layout()),
-                        "reshape target byte length is always greater"
-                    );
+                    n.reshape(val.layout());
                     val.int_add(n, IntFlags { signed: false, wrap: false })
                 }),
             ),
@@ -494,10 +696,90 @@ impl InstructionSet for ArithmeticOp {
     }
 }
 
+impl InstructionSet for ModularOp {
+    #[inline]
+    fn isa_ids() -> BTreeSet<&'static str> {
+        let mut set = BTreeSet::new();
+        set.insert(constants::ISA_ID_MODARITH);
+        set
+    }
+
+    fn complexity(&self) -> u64 {
+        // Cost of multiplication grows quadratically with the number of 64-bit words, while
+        // exponentiation and inversion perform a multiplication per each operand bit
+        let bits = self.reg_bytes() as u64 * 8;
+        let words = bits.div_ceil(64);
+        match self {
+            ModularOp::AddMod(_, _, _, _, _) => words,
+            ModularOp::MulMod(_, _, _, _, _) => words * words,
+            ModularOp::PowMod(_, _, _, _, _) | ModularOp::InvMod(_, _, _, _) => {
+                bits * words * words
+            }
+        }
+    }
+
+    #[inline]
+    fn reg_bytes(&self) -> u16 {
+        match self {
+            ModularOp::AddMod(reg, _, _, _, _)
+            | ModularOp::MulMod(reg, _, _, _, _)
+            | ModularOp::PowMod(reg, _, _, _, _)
+            | ModularOp::InvMod(reg, _, _, _) => reg.bytes(),
+        }
+    }
+
+    fn exec(&self, regs: &mut CoreRegs, _site: LibSite) -> ExecStep {
+        let is_some = match self {
+            ModularOp::AddMod(reg, src1, src2, modulo, dst) => {
+                let res = regs
+                    .get_both(reg, src1, reg, src2)
+                    .zip(*regs.get(reg, modulo))
+                    .and_then(|((val1, val2), m)| val1.add_mod(val2, m));
+                regs.set(reg, dst, res)
+            }
+            ModularOp::MulMod(reg, src1, src2, modulo, dst) => {
+                let res = regs
+                    .get_both(reg, src1, reg, src2)
+                    .zip(*regs.get(reg, modulo))
+                    .and_then(|((val1, val2), m)| val1.mul_mod(val2, m));
+                regs.set(reg, dst, res)
+            }
+            ModularOp::PowMod(reg, base, exp, modulo, dst) => {
+                let res = regs
+                    .get_both(reg, base, reg, exp)
+                    .zip(*regs.get(reg, modulo))
+                    .and_then(|((val, exp), m)| val.pow_mod(exp, m));
+                regs.set(reg, dst, res)
+            }
+            ModularOp::InvMod(reg, src, modulo, dst) => {
+                let res = regs.get_both(reg, src, reg, modulo).and_then(|(val, m)| val.inv_mod(m));
+                regs.set(reg, dst, res)
+            }
+        };
+        regs.st0 = is_some;
+        ExecStep::Next
+    }
+}
+
 impl InstructionSet for BitwiseOp {
     #[inline]
     fn isa_ids() -> BTreeSet<&'static str> { BTreeSet::default() }
 
+    #[inline]
+    fn reg_bytes(&self) -> u16 {
+        match self {
+            BitwiseOp::And(reg, _, _, _)
+            | BitwiseOp::Or(reg, _, _, _)
+            | BitwiseOp::Xor(reg, _, _, _)
+            | BitwiseOp::Not(reg, _)
+            | BitwiseOp::Shl(_, _, reg, _)
+            | BitwiseOp::Scl(_, _, reg, _)
+            | BitwiseOp::Scr(_, _, reg, _) => reg.bytes(),
+            BitwiseOp::ShrA(_, _, _, reg, _) | BitwiseOp::RevA(reg, _) => reg.bytes(),
+            BitwiseOp::ShrR(_, _, reg, _) | BitwiseOp::RevR(reg, _) => reg.bytes(),
+        }
+    }
+
     fn exec(&self, regs: &mut CoreRegs, _site: LibSite) -> ExecStep {
         match self {
             BitwiseOp::And(reg, src1, src2, dst) => {
@@ -552,6 +834,28 @@ impl InstructionSet for BytesOp {
     #[inline]
     fn complexity(&self) -> u64 { 5 }
 
+    fn str_bytes(&self, regs: &CoreRegs) -> u32 {
+        let len = |reg: &RegS| regs.get_s(*reg).map(|s| s.len() as u32).unwrap_or_default();
+        match self {
+            BytesOp::Put(_, bytes, _) => bytes.len() as u32,
+            BytesOp::Mov(src, _)
+            | BytesOp::Fill(src, _, _, _, _)
+            | BytesOp::Len(src, _, _)
+            | BytesOp::Cnt(src, _, _)
+            | BytesOp::Extr(src, _, _, _)
+            | BytesOp::Inj(src, _, _, _)
+            | BytesOp::Splt(_, _, src, _, _)
+            | BytesOp::Del(_, _, _, _, _, _, _, src, _)
+            | BytesOp::Rev(src, _) => len(src),
+            BytesOp::Swp(src1, src2)
+            | BytesOp::Eq(src1, src2)
+            | BytesOp::Con(src1, src2, _, _, _)
+            | BytesOp::Find(src1, src2)
+            | BytesOp::Join(src1, src2, _)
+            | BytesOp::Ins(_, _, src1, src2) => len(src1) + len(src2),
+        }
+    }
+
     #[allow(warnings)]
     fn exec(&self, regs: &mut CoreRegs, _site: LibSite) -> ExecStep {
         match self {
@@ -743,7 +1047,6 @@ impl InstructionSet for BytesOp {
                     let len = s1.len() + s2.len();
                     let mut d = s1.clone();
                     d.adjust_len(len);
-                    let mut d = ByteStr::with(s1);
                     d.as_mut()[s1.len() as usize..].copy_from_slice(s2.as_ref());
                     regs.s16[dst.as_usize()] = Some(d);
                     Some(())
@@ -754,13 +1057,137 @@ impl InstructionSet for BytesOp {
                 })
             }
             BytesOp::Splt(flag, offset, src, dst1, dst2) => {
-                todo!("#(6) complete bytestring opcode implementation")
+                let (first, second, exceeds) = match (regs.a16[offset.to_usize()], regs.get_s(*src))
+                {
+                    (Some(offset), Some(s)) => {
+                        let bytes = s.as_ref();
+                        let (offset, len) = (offset as usize, bytes.len());
+                        let empty = || Some(ByteStr::default());
+                        let whole = || Some(s.clone());
+                        let (first, second) = match (offset, flag) {
+                            (0, SplitFlag::NoneNone) => (None, None),
+                            (0, SplitFlag::NoneNoneOnEmpty) if len == 0 => (None, None),
+                            (0, SplitFlag::NoneNoneOnEmpty | SplitFlag::NoneZeroOnEmpty) => {
+                                (None, whole())
+                            }
+                            (0, _) => (empty(), whole()),
+                            (offset, _) if offset < len => (
+                                Some(ByteStr::with(&bytes[..offset])),
+                                Some(ByteStr::with(&bytes[offset..])),
+                            ),
+                            (_, SplitFlag::NoneNone) => (None, None),
+                            (offset, SplitFlag::CutNone | SplitFlag::ZeroNone) if offset == len => {
+                                (whole(), None)
+                            }
+                            (offset, _) if offset == len => (whole(), empty()),
+                            (_, SplitFlag::CutNone) => (whole(), None),
+                            (_, SplitFlag::CutZero) => (whole(), empty()),
+                            (_, SplitFlag::ZeroNone) => (empty(), None),
+                            (_, SplitFlag::ZeroZero) => (empty(), empty()),
+                            (_, _) => (None, None),
+                        };
+                        (first, second, offset > len)
+                    }
+                    _ => (None, None, false),
+                };
+                if first.is_none() || second.is_none() || exceeds {
+                    regs.st0 = false;
+                }
+                regs.s16[dst1.as_usize()] = first;
+                regs.s16[dst2.as_usize()] = second;
             }
             BytesOp::Ins(flag, offset, src, dst) => {
-                todo!("#(6) complete bytestring opcode implementation")
+                let mut fail = false;
+                let mut f = || -> Option<ByteStr> {
+                    let offset = regs.a16[offset.to_usize()]? as usize;
+                    let (s, d) = regs.get_both_s(*src, *dst)?;
+                    let max = u16::MAX as usize;
+                    let mut ins = s.as_ref();
+                    let mut bytes = d.as_ref().to_vec();
+                    let mut pos = offset;
+                    if offset > bytes.len() {
+                        fail = true;
+                        match flag {
+                            InsertFlag::FailOnOffset => return None,
+                            InsertFlag::FailOnOffsetLen | InsertFlag::Extend
+                                if offset + ins.len() > max =>
+                            {
+                                return None
+                            }
+                            InsertFlag::FailOnOffsetLen
+                            | InsertFlag::Extend
+                            | InsertFlag::ExtendCut => bytes.resize(offset, 0),
+                            InsertFlag::FailOnLen
+                            | InsertFlag::Append
+                            | InsertFlag::Cut
+                            | InsertFlag::Shorten => pos = bytes.len(),
+                        }
+                    }
+                    if bytes.len() + ins.len() > max {
+                        fail = true;
+                        match flag {
+                            InsertFlag::Cut | InsertFlag::ExtendCut => {}
+                            InsertFlag::Shorten => ins = &ins[..max - bytes.len()],
+                            _ => return None,
+                        }
+                    }
+                    bytes.splice(pos..pos, ins.iter().copied());
+                    bytes.truncate(max);
+                    Some(ByteStr::with(bytes))
+                };
+                let res = f();
+                if fail || res.is_none() {
+                    regs.st0 = false;
+                }
+                regs.s16[dst.as_usize()] = res;
             }
             BytesOp::Del(flag, reg1, offset1, reg2, offset2, flag1, flag2, src, dst) => {
-                todo!("#(6) complete bytestring opcode implementation")
+                // Undefined operands fail the operation regardless of `flag1` and `flag2`
+                let mut fail = true;
+                let mut f = || -> Option<ByteStr> {
+                    let offset = |reg: &RegA2, idx: &Reg32| match reg {
+                        RegA2::A8 => regs.a8[idx.to_usize()].map(u16::from),
+                        RegA2::A16 => regs.a16[idx.to_usize()],
+                    };
+                    let (offset1, offset2) = (offset(reg1, offset1)?, offset(reg2, offset2)?);
+                    let start = offset1.min(offset2) as usize;
+                    let end = offset1.max(offset2) as usize;
+                    let bytes = regs.get_s(*src)?.as_ref();
+                    let len = bytes.len();
+                    let fragment = |from: usize| {
+                        let mut fragment = bytes[from.min(len)..].to_vec();
+                        if *flag == DeleteFlag::Extend {
+                            fragment.resize(end - start, 0);
+                        }
+                        ByteStr::with(fragment)
+                    };
+                    let (res, exceeds) = if start > len {
+                        let res = match flag {
+                            DeleteFlag::None => None,
+                            DeleteFlag::Zero | DeleteFlag::Cut => Some(ByteStr::default()),
+                            DeleteFlag::Extend => Some(fragment(start)),
+                        };
+                        (res, *flag1)
+                    } else if end > len {
+                        let res = match flag {
+                            DeleteFlag::None => None,
+                            DeleteFlag::Zero => Some(ByteStr::default()),
+                            DeleteFlag::Cut | DeleteFlag::Extend => Some(fragment(start)),
+                        };
+                        (res, *flag2)
+                    } else {
+                        let mut res = bytes[..start].to_vec();
+                        res.extend_from_slice(&bytes[end..]);
+                        (Some(ByteStr::with(res)), false)
+                    };
+                    fail = exceeds;
+                    res
+                };
+                let res = f();
+                if fail {
+                    regs.st0 = false;
+                }
+                regs.s16[dst.as_usize()] = res;
             }
         }
         ExecStep::Next
@@ -778,6 +1205,29 @@ impl InstructionSet for DigestOp {
     #[inline]
     fn complexity(&self) -> u64 { 100 }
 
+    #[inline]
+    fn str_bytes(&self, regs: &CoreRegs) -> u32 {
+        let len = |reg: &RegS| regs.get_s(*reg).map(|s| s.len() as u32).unwrap_or_default();
+        match self {
+            DigestOp::Ripemd(src, _)
+            | DigestOp::Sha256(src, _)
+            | DigestOp::Sha512(src, _)
+            | DigestOp::Sha3(src, _)
+            | DigestOp::Keccak(src, _)
+            | DigestOp::Blake2b(src, _)
+            | DigestOp::Blake3(src, _)
+            | DigestOp::Hash160(src, _)
+            | DigestOp::Hash256(src, _) => len(src),
+            DigestOp::TaggedSha256(tag, src, _)
+            | DigestOp::HmacSha256(tag, src, _)
+            | DigestOp::HmacSha512(tag, src, _) => len(tag) + len(src),
+            DigestOp::HkdfExpand(_, info, okm_len, _) => {
+                let okm_len = regs.get(RegA::A16, okm_len).map(u16::from).unwrap_or_default();
+                len(info) + okm_len as u32
+            }
+        }
+    }
+
     fn exec(&self, regs: &mut CoreRegs, _site: LibSite) -> ExecStep {
         let none;
         match self {
@@ -799,6 +1249,107 @@ impl InstructionSet for DigestOp {
                 let hash = s.map(|s| sha512::Hash::hash(s.as_ref()).into_inner());
                 regs.set(RegR::R512, dst, hash);
             }
+            DigestOp::Sha3(src, dst) => {
+                let s = regs.get_s(*src);
+                none = s.is_none();
+                let hash = s.map(|s| <[u8; 32]>::from(Sha3_256::digest(s.as_ref())));
+                regs.set(RegR::R256, dst, hash);
+            }
+            DigestOp::Keccak(src, dst) => {
+                let s = regs.get_s(*src);
+                none = s.is_none();
+                let hash = s.map(|s| <[u8; 32]>::from(Keccak256::digest(s.as_ref())));
+                regs.set(RegR::R256, dst, hash);
+            }
+            DigestOp::Blake2b(src, dst) => {
+                let s = regs.get_s(*src);
+                none = s.is_none();
+                let hash = s.map(|s| {
+                    let mut hash = [0u8; 64];
+                    hash.copy_from_slice(&Blake2b::digest(s.as_ref()));
+                    hash
+                });
+                regs.set(RegR::R512, dst, hash);
+            }
+            DigestOp::Blake3(src, dst) => {
+                let s = regs.get_s(*src);
+                none = s.is_none();
+                let hash = s.map(|s| <[u8; 32]>::from(blake3::hash(s.as_ref())));
+                regs.set(RegR::R256, dst, hash);
+            }
+            DigestOp::Hash160(src, dst) => {
+                let s = regs.get_s(*src);
+                none = s.is_none();
+                let hash = s.map(|s| hash160::Hash::hash(s.as_ref()).into_inner());
+                regs.set(RegR::R160, dst, hash);
+            }
+            DigestOp::Hash256(src, dst) => {
+                let s = regs.get_s(*src);
+                none = s.is_none();
+                let hash = s.map(|s| sha256d::Hash::hash(s.as_ref()).into_inner());
+                regs.set(RegR::R256, dst, hash);
+            }
+            DigestOp::TaggedSha256(tag, src, dst) => {
+                let s = regs.get_both_s(*tag, *src);
+                none = s.is_none();
+                let hash = s.map(|(tag, s)| {
+                    let tag = sha256::Hash::hash(tag.as_ref());
+                    let mut engine = sha256::Hash::engine();
+                    engine.input(&tag[..]);
+                    engine.input(&tag[..]);
+                    engine.input(s.as_ref());
+                    sha256::Hash::from_engine(engine).into_inner()
+                });
+                regs.set(RegR::R256, dst, hash);
+            }
+            DigestOp::HmacSha256(key, src, dst) => {
+                let s = regs.get_both_s(*key, *src);
+                none = s.is_none();
+                let hash = s.map(|(key, s)| {
+                    let mut engine = HmacEngine::<sha256::Hash>::new(key.as_ref());
+                    engine.input(s.as_ref());
+                    Hmac::from_engine(engine).into_inner()
+                });
+                regs.set(RegR::R256, dst, hash);
+            }
+            DigestOp::HmacSha512(key, src, dst) => {
+                let s = regs.get_both_s(*key, *src);
+                none = s.is_none();
+                let hash = s.map(|(key, s)| {
+                    let mut engine = HmacEngine::<sha512::Hash>::new(key.as_ref());
+                    engine.input(s.as_ref());
+                    Hmac::from_engine(engine).into_inner()
+                });
+                regs.set(RegR::R512, dst, hash);
+            }
+            DigestOp::HkdfExpand(prk, info, okm_len, dst) => {
+                let prk = regs.get(RegR::R256, prk);
+                let info = regs.get_s(*info);
+                let okm_len = regs
+                    .get(RegA::A16, okm_len)
+                    .map(u16::from)
+                    .filter(|len| *len as usize <= 255 * sha256::Hash::LEN);
+                let okm = prk.zip(info).zip(okm_len).map(|((prk, info), okm_len)| {
+                    let mut okm = Vec::with_capacity(okm_len as usize);
+                    let mut block = [0u8; 32];
+                    let mut counter = 1u8;
+                    while okm.len() < okm_len as usize {
+                        let mut engine = HmacEngine::<sha256::Hash>::new(prk.as_ref());
+                        if counter > 1 {
+                            engine.input(&block[..]);
+                        }
+                        engine.input(info.as_ref());
+                        engine.input(&[counter]);
+                        block = Hmac::from_engine(engine).into_inner();
+                        let rest = okm_len as usize - okm.len();
+                        okm.extend_from_slice(&block[..rest.min(sha256::Hash::LEN)]);
+                        counter = counter.wrapping_add(1);
+                    }
+                    ByteStr::with(okm)
+                });
+                none = okm.is_none();
+                regs.set_s(*dst, okm);
+            }
         }
         if none {
             regs.st0 = false;
@@ -939,70 +1490,198 @@ impl InstructionSet for Curve25519Op {
         unimplemented!("AluVM runtime compiled without support for Curve25519 instructions")
     }
 
+    fn str_bytes(&self, regs: &CoreRegs) -> u32 {
+        match self {
+            Curve25519Op::Verify(msg, _, _) => {
+                regs.get_s(*msg).map(|s| s.len() as u32).unwrap_or_default()
+            }
+            _ => 0,
+        }
+    }
+
     #[cfg(feature = "curve25519")]
     fn exec(&self, regs: &mut CoreRegs, _site: LibSite) -> ExecStep {
-        use amplify::num::u256;
         use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+        use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
         use curve25519_dalek::scalar::Scalar;
 
         let get_scalar = |src: Number| {
             let mut scal = [0u8; 32];
             scal.copy_from_slice(&src.as_ref()[..32]);
-            Scalar::from_bits(scal)
+            Scalar::from_bytes_mod_order(scal)
         };
 
-        let from_scalar = |scal: Scalar| {
-            let mut n = [0u8; 64];
-            n[..32].copy_from_slice(scal.as_bytes());
-            n[32..].copy_from_slice((ED25519_BASEPOINT_POINT * scal).compress().as_bytes());
-            Number::from_slice(n)
+        let get_point = |src: Number| {
+            let mut point = [0u8; 32];
+            point.copy_from_slice(&src.as_ref()[..32]);
+            CompressedEdwardsY(point).decompress()
         };
 
+        let from_point = |point: EdwardsPoint| Number::from_slice(point.compress().as_bytes());
+
         match self {
             Curve25519Op::Gen(src, dst) => {
-                let res = regs.get(RegR::R256, src).map(get_scalar).map(from_scalar);
-                regs.set(RegR::R512, dst, res);
+                let res = regs
+                    .get(RegR::R256, src)
+                    .map(get_scalar)
+                    .map(|scal| ED25519_BASEPOINT_POINT * scal)
+                    .map(from_point);
+                regs.st0 = regs.set(RegR::R256, dst, res);
             }
             Curve25519Op::Mul(block, scal, src, dst) => {
                 let reg = block.into_reg(256).expect("register set does not match standard");
-                let lhs = regs.get(reg, scal).map(get_scalar);
-                let rhs = regs.get(reg, src).map(get_scalar);
-                let res = lhs.zip(rhs).map(|(lhs, rhs)| lhs * rhs).map(from_scalar);
-                regs.set(RegR::R512, dst, res);
+                let scal = regs.get(reg, scal).map(get_scalar);
+                let point = regs.get(RegR::R256, src).and_then(get_point);
+                let res = scal.zip(point).map(|(scal, point)| point * scal).map(from_point);
+                regs.st0 = regs.set(RegR::R256, dst, res);
             }
-            Curve25519Op::Add(lhs, rhs, dst, overflow) => {
-                let lhs = regs
-                    .get(RegR::R512, lhs)
-                    .map(get_scalar)
-                    .map(|s| u256::from_le_bytes(s.to_bytes()));
-                let rhs = regs
-                    .get(RegR::R512, rhs)
-                    .map(get_scalar)
-                    .map(|s| u256::from_le_bytes(s.to_bytes()));
-                let res = lhs
-                    .zip(rhs)
-                    .and_then(|(lhs, rhs)| {
-                        let scal = Scalar::from_bits((lhs + rhs).to_le_bytes());
-                        match !*overflow && !scal.is_canonical() {
-                            true => {
-                                regs.st0 = false;
-                                None
-                            }
-                            false => Some(scal.reduce()),
-                        }
-                    })
-                    .map(from_scalar);
-                regs.set(RegR::R512, dst, res);
+            Curve25519Op::Add(lhs, rhs, dst) => {
+                let lhs = regs.get(RegR::R256, lhs).and_then(get_point);
+                let rhs = regs.get(RegR::R256, rhs).and_then(get_point);
+                let res = lhs.zip(rhs).map(|(lhs, rhs)| lhs + rhs).map(from_point);
+                regs.st0 = regs.set(RegR::R256, dst, res);
             }
             Curve25519Op::Neg(src, dst) => {
-                let res = regs.get(RegR::R512, src).map(get_scalar).map(|s| -s).map(from_scalar);
-                regs.set(RegR::R512, dst, res);
+                let res = regs.get(RegR::R256, src).and_then(get_point).map(|p| -p).map(from_point);
+                regs.st0 = regs.set(RegR::R256, dst, res);
+            }
+            Curve25519Op::Verify(msg, key, sig) => {
+                let msg = regs.get_s(*msg);
+                let key = *regs.get(RegR::R256, key);
+                let sig = *regs.get(RegR::R512, sig);
+                regs.st0 = msg
+                    .zip(key)
+                    .zip(sig)
+                    .and_then(|((msg, key), sig)| {
+                        let mut r = [0u8; 32];
+                        let mut s = [0u8; 32];
+                        r.copy_from_slice(&sig.as_ref()[..32]);
+                        s.copy_from_slice(&sig.as_ref()[32..64]);
+                        let s = Scalar::from_canonical_bytes(s)?;
+                        let a = get_point(key)?;
+                        let mut engine = sha512::Hash::engine();
+                        engine.input(&r);
+                        engine.input(&key.as_ref()[..32]);
+                        engine.input(msg.as_ref());
+                        let k = Scalar::from_bytes_mod_order_wide(
+                            &sha512::Hash::from_engine(engine).into_inner(),
+                        );
+                        let check = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-a, &s);
+                        Some(check.compress() == CompressedEdwardsY(r))
+                    })
+                    .unwrap_or(false);
+            }
+        }
+        ExecStep::Next
+    }
+}
+
+impl InstructionSet for Secp256k1VerifyOp {
+    #[cfg(not(feature = "secp256k1"))]
+    #[inline]
+    fn isa_ids() -> BTreeSet<&'static str> { BTreeSet::default() }
+
+    #[cfg(feature = "secp256k1")]
+    #[inline]
+    fn isa_ids() -> BTreeSet<&'static str> {
+        let mut set = BTreeSet::new();
+        set.insert(constants::ISA_ID_SECP256K);
+        set
+    }
+
+    #[inline]
+    fn complexity(&self) -> u64 { 2000 }
+
+    fn str_bytes(&self, regs: &CoreRegs) -> u32 {
+        match self {
+            Secp256k1VerifyOp::EcdsaDer(_, _, sig) => {
+                regs.get_s(*sig).map(|s| s.len() as u32).unwrap_or_default()
+            }
+            _ => 0,
+        }
+    }
+
+    #[cfg(not(feature = "secp256k1"))]
+    fn exec(&self, _: &mut CoreRegs, _: LibSite) -> ExecStep {
+        unimplemented!("AluVM runtime compiled without support for Secp256k1 instructions")
+    }
+
+    #[cfg(feature = "secp256k1")]
+    fn exec(&self, regs: &mut CoreRegs, _site: LibSite) -> ExecStep {
+        use secp256k1::{ecdsa, schnorr, Message, PublicKey, XOnlyPublicKey, SECP256K1};
+
+        let get_pubkey = |val: Number| {
+            let mut pk = [4u8; 65];
+            pk[1..].copy_from_slice(val.as_ref());
+            PublicKey::from_slice(&pk).ok()
+        };
+
+        match self {
+            Secp256k1VerifyOp::Ecdsa(msg, key, sig) => {
+                let msg =
+                    regs.get(RegR::R256, msg).and_then(|msg| Message::from_slice(&msg[..]).ok());
+                let key = regs.get(RegR::R512, key).and_then(get_pubkey);
+                let sig = regs
+                    .get(RegR::R512, sig)
+                    .and_then(|sig| ecdsa::Signature::from_compact(&sig[..]).ok());
+                regs.st0 = msg
+                    .zip(key)
+                    .zip(sig)
+                    .map(|((msg, key), sig)| SECP256K1.verify_ecdsa(&msg, &sig, &key).is_ok())
+                    .unwrap_or(false);
+            }
+
+            Secp256k1VerifyOp::EcdsaDer(msg, key, sig) => {
+                let msg =
+                    regs.get(RegR::R256, msg).and_then(|msg| Message::from_slice(&msg[..]).ok());
+                let key = regs.get(RegR::R512, key).and_then(get_pubkey);
+                let sig =
+                    regs.get_s(*sig).and_then(|sig| ecdsa::Signature::from_der(sig.as_ref()).ok());
+                regs.st0 = msg
+                    .zip(key)
+                    .zip(sig)
+                    .map(|((msg, key), sig)| SECP256K1.verify_ecdsa(&msg, &sig, &key).is_ok())
+                    .unwrap_or(false);
+            }
+
+            Secp256k1VerifyOp::Schnorr(msg, key, sig) => {
+                let msg =
+                    regs.get(RegR::R256, msg).and_then(|msg| Message::from_slice(&msg[..]).ok());
+                let key = regs
+                    .get(RegR::R256, key)
+                    .and_then(|key| XOnlyPublicKey::from_slice(&key[..]).ok());
+                let sig = regs
+                    .get(RegR::R512, sig)
+                    .and_then(|sig| schnorr::Signature::from_slice(&sig[..]).ok());
+                regs.st0 = msg
+                    .zip(key)
+                    .zip(sig)
+                    .map(|((msg, key), sig)| SECP256K1.verify_schnorr(&sig, &msg, &key).is_ok())
+                    .unwrap_or(false);
             }
         }
         ExecStep::Next
     }
 }
 
+impl InstructionSet for AluReOp {
+    #[inline]
+    fn isa_ids() -> BTreeSet<&'static str> {
+        let mut set = BTreeSet::new();
+        set.insert(constants::ISA_ID_ALURE);
+        set
+    }
+
+    #[inline]
+    fn complexity(&self) -> u64 { 10 }
+
+    fn exec(&self, _regs: &mut CoreRegs, _site: LibSite) -> ExecStep {
+        match self {
+            AluReOp::HCall(id) => ExecStep::Host(*id),
+        }
+    }
+}
+
 impl InstructionSet for ReservedOp {
     #[inline]
     fn isa_ids() -> BTreeSet<&'static str> { BTreeSet::default() }
@@ -1016,7 +1695,9 @@ impl InstructionSet for ReservedOp {
 mod tests {
     use super::*;
     use crate::data::{Layout, Step};
-    use crate::reg::{Reg16, Reg8, RegBlockAR};
+    #[cfg(any(feature = "secp256k1", feature = "curve25519"))]
+    use crate::reg::RegBlockAR;
+    use crate::reg::{Reg16, Reg8};
 
     #[test]
     fn cmp_ne_test() {
@@ -1026,10 +1707,10 @@ mod tests {
             .exec(&mut register, lib_site);
         PutOp::PutA(RegA::A8, Reg32::Reg2, MaybeNumber::from(9).into())
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         CmpOp::EqA(NoneEqFlag::NonEqual, RegA::A8, Reg32::Reg1, Reg32::Reg2)
             .exec(&mut register, lib_site);
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
     }
 
     #[test]
@@ -1040,20 +1721,20 @@ mod tests {
             .exec(&mut register, lib_site);
         PutOp::PutA(RegA::A8, Reg32::Reg2, MaybeNumber::from(9).into())
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         CmpOp::EqA(NoneEqFlag::NonEqual, RegA::A8, Reg32::Reg1, Reg32::Reg2)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         assert_eq!(MaybeNumber::none(), register.get(RegA::A8, Reg32::Reg5));
         assert_eq!(MaybeNumber::none(), register.get(RegA::A8, Reg32::Reg6));
         CmpOp::EqA(NoneEqFlag::NonEqual, RegA::A8, Reg32::Reg5, Reg32::Reg6)
             .exec(&mut register, lib_site);
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
         ControlFlowOp::Succ.exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         CmpOp::EqA(NoneEqFlag::Equal, RegA::A8, Reg32::Reg5, Reg32::Reg6)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
     }
 
     #[test]
@@ -1065,10 +1746,10 @@ mod tests {
         ArithmeticOp::Stp(RegA::A8, Reg32::Reg1, Step::with(4)).exec(&mut register, lib_site);
         PutOp::PutA(RegA::A8, Reg32::Reg2, MaybeNumber::from(7).into())
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         CmpOp::EqA(NoneEqFlag::NonEqual, RegA::A8, Reg32::Reg1, Reg32::Reg2)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
     }
 
     #[test]
@@ -1080,10 +1761,102 @@ mod tests {
         ArithmeticOp::Stp(RegA::A8, Reg32::Reg1, Step::with(-4)).exec(&mut register, lib_site);
         PutOp::PutA(RegA::A8, Reg32::Reg2, MaybeNumber::from(-1i8).into())
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         CmpOp::EqA(NoneEqFlag::NonEqual, RegA::A8, Reg32::Reg1, Reg32::Reg2)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
+    }
+
+    #[test]
+    fn modular_test() {
+        let mut register = CoreRegs::default();
+        let lib_site = LibSite::default();
+        register.set(RegA::A8, Reg32::Reg1, MaybeNumber::from(7u8));
+        register.set(RegA::A8, Reg32::Reg2, MaybeNumber::from(5u8));
+        register.set(RegA::A8, Reg32::Reg3, MaybeNumber::from(9u8));
+
+        let ops = [
+            (ModularOp::AddMod(RegA::A8, Reg32::Reg1, Reg32::Reg2, Reg32::Reg3, Reg32::Reg4), 3u8),
+            (ModularOp::MulMod(RegA::A8, Reg32::Reg1, Reg32::Reg2, Reg32::Reg3, Reg32::Reg4), 8),
+            (ModularOp::PowMod(RegA::A8, Reg32::Reg1, Reg32::Reg2, Reg32::Reg3, Reg32::Reg4), 4),
+            (ModularOp::InvMod(RegA::A8, Reg32::Reg1, Reg32::Reg3, Reg32::Reg4), 4),
+        ];
+        for (op, res) in ops {
+            op.exec(&mut register, lib_site);
+            assert!(register.st0);
+            assert_eq!(register.get(RegA::A8, Reg32::Reg4), MaybeNumber::from(res));
+        }
+
+        // 3 and 9 are not coprime
+        register.set(RegA::A8, Reg32::Reg1, MaybeNumber::from(3u8));
+        ModularOp::InvMod(RegA::A8, Reg32::Reg1, Reg32::Reg3, Reg32::Reg4)
+            .exec(&mut register, lib_site);
+        assert!(!register.st0);
+        assert_eq!(register.get(RegA::A8, Reg32::Reg4), MaybeNumber::none());
+
+        // Zero modulo
+        register.set(RegA::A8, Reg32::Reg3, MaybeNumber::from(0u8));
+        ModularOp::AddMod(RegA::A8, Reg32::Reg1, Reg32::Reg2, Reg32::Reg3, Reg32::Reg4)
+            .exec(&mut register, lib_site);
+        assert!(!register.st0);
+        assert_eq!(register.get(RegA::A8, Reg32::Reg4), MaybeNumber::none());
+
+        // Uninitialized source
+        register.set(RegA::A8, Reg32::Reg3, MaybeNumber::from(9u8));
+        ModularOp::MulMod(RegA::A8, Reg32::Reg1, Reg32::Reg5, Reg32::Reg3, Reg32::Reg4)
+            .exec(&mut register, lib_site);
+        assert!(!register.st0);
+        assert_eq!(register.get(RegA::A8, Reg32::Reg4), MaybeNumber::none());
+    }
+
+    #[test]
+    fn modular_large_test() {
+        use amplify::hex::FromHex;
+        use amplify::num::u1024;
+
+        // 1024-bit MODP group prime from RFC 2409
+        let mut prime = [0u8; 128];
+        prime.copy_from_slice(
+            &Vec::<u8>::from_hex(
+                "FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22\
+                 514A08798E3404DDEF9519B3CD3A431B302B0A6DF25F14374FE1356D6D51C245E485B576625E7EC6\
+                 F44C42E9A637ED6B0BFF5CB6F406B7EDEE386BFB5A899FA5AE9F24117C4B1FE649286651ECE65381\
+                 FFFFFFFFFFFFFFFF",
+            )
+            .unwrap(),
+        );
+        let prime = u1024::from_be_bytes(prime);
+
+        let mut register = CoreRegs::default();
+        let lib_site = LibSite::default();
+        register.set(RegA::A1024, Reg32::Reg1, MaybeNumber::from(u1024::from(2u8)));
+        register.set(RegA::A1024, Reg32::Reg2, MaybeNumber::from(prime - 1u8));
+        register.set(RegA::A1024, Reg32::Reg3, MaybeNumber::from(prime));
+
+        // Fermat's little theorem: 2^(p-1) = 1 mod p
+        let op = ModularOp::PowMod(RegA::A1024, Reg32::Reg1, Reg32::Reg2, Reg32::Reg3, Reg32::Reg4);
+        op.exec(&mut register, lib_site);
+        assert!(register.st0);
+        assert_eq!(register.get(RegA::A1024, Reg32::Reg4), MaybeNumber::from(u1024::ONE));
+        assert_eq!(op.complexity(), 1024 * 16 * 16);
+
+        // (p - 1) * (p - 1) = 1 mod p
+        ModularOp::MulMod(RegA::A1024, Reg32::Reg2, Reg32::Reg2, Reg32::Reg3, Reg32::Reg4)
+            .exec(&mut register, lib_site);
+        assert!(register.st0);
+        assert_eq!(register.get(RegA::A1024, Reg32::Reg4), MaybeNumber::from(u1024::ONE));
+
+        // (p - 1) + (p - 1) = p - 2 mod p
+        ModularOp::AddMod(RegA::A1024, Reg32::Reg2, Reg32::Reg2, Reg32::Reg3, Reg32::Reg4)
+            .exec(&mut register, lib_site);
+        assert!(register.st0);
+        assert_eq!(register.get(RegA::A1024, Reg32::Reg4), MaybeNumber::from(prime - 2u8));
+
+        // 2^-1 = (p + 1) / 2 mod p
+        ModularOp::InvMod(RegA::A1024, Reg32::Reg1, Reg32::Reg3, Reg32::Reg4)
+            .exec(&mut register, lib_site);
+        assert!(register.st0);
+        assert_eq!(register.get(RegA::A1024, Reg32::Reg4), MaybeNumber::from(prime / 2u8 + 1u8));
     }
 
     #[test]
@@ -1096,16 +1869,16 @@ mod tests {
             .exec(&mut register, lib_site);
         BytesOp::Put(3.into(), Box::new(ByteStr::with([2; u16::MAX as usize])), false)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         BytesOp::Eq(1.into(), 2.into()).exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         BytesOp::Eq(1.into(), 3.into()).exec(&mut register, lib_site);
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
         ControlFlowOp::Succ.exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         BytesOp::Put(3.into(), Box::new(ByteStr::with([2; u16::MAX as usize])), true)
             .exec(&mut register, lib_site);
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
     }
 
     #[test]
@@ -1131,7 +1904,7 @@ mod tests {
         let mut num = register.get(RegR::R128, Reg16::Reg2).unwrap();
         num.reshape(Layout::unsigned(s.len() as u16 - 1));
         assert_eq!(num, Number::from_slice("ello".as_bytes()));
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
     }
 
     #[test]
@@ -1146,7 +1919,7 @@ mod tests {
             .exec(&mut register, lib_site);
         BytesOp::Extr(1.into(), RegR::R128, Reg16::Reg1, Reg16::Reg1).exec(&mut register, lib_site);
         assert_eq!(register.get(RegR::R128, Reg16::Reg1).unwrap(), Number::from(0x07u128));
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
     }
 
     #[test]
@@ -1157,7 +1930,7 @@ mod tests {
         BytesOp::Put(1.into(), Box::new(ByteStr::with(bytes)), false).exec(&mut register, lib_site);
         BytesOp::Extr(1.into(), RegR::R128, Reg16::Reg1, Reg16::Reg1).exec(&mut register, lib_site);
         assert_eq!(register.get(RegR::R128, Reg16::Reg1), MaybeNumber::none());
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
     }
 
     #[test]
@@ -1168,7 +1941,7 @@ mod tests {
             .exec(&mut register, lib_site);
         BytesOp::Extr(1.into(), RegR::R128, Reg16::Reg1, Reg16::Reg1).exec(&mut register, lib_site);
         assert_eq!(register.get(RegR::R128, Reg16::Reg1), MaybeNumber::none());
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
     }
 
     #[test]
@@ -1186,7 +1959,7 @@ mod tests {
             .exec(&mut register, lib_site);
         assert_eq!(register.get(RegA::A16, Reg16::Reg2).unwrap(), Number::from(0u16));
         assert_eq!(register.get(RegA::A16, Reg16::Reg3).unwrap(), Number::from(5u16));
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         // banana (1st fragment)
         PutOp::PutA(RegA::A16, Reg32::Reg1, MaybeNumber::from(1).into())
             .exec(&mut register, lib_site);
@@ -1194,7 +1967,7 @@ mod tests {
             .exec(&mut register, lib_site);
         assert_eq!(register.get(RegA::A16, Reg16::Reg2).unwrap(), Number::from(6u16));
         assert_eq!(register.get(RegA::A16, Reg16::Reg3).unwrap(), Number::from(6u16));
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         // kiwi (2nd fragment)
         PutOp::PutA(RegA::A16, Reg32::Reg1, MaybeNumber::from(2).into())
             .exec(&mut register, lib_site);
@@ -1202,7 +1975,7 @@ mod tests {
             .exec(&mut register, lib_site);
         assert_eq!(register.get(RegA::A16, Reg16::Reg2).unwrap(), Number::from(13u16));
         assert_eq!(register.get(RegA::A16, Reg16::Reg3).unwrap(), Number::from(4u16));
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         // no 3rd fragment
         PutOp::PutA(RegA::A16, Reg32::Reg1, MaybeNumber::from(3).into())
             .exec(&mut register, lib_site);
@@ -1210,7 +1983,7 @@ mod tests {
             .exec(&mut register, lib_site);
         assert_eq!(register.get(RegA::A16, Reg16::Reg2), MaybeNumber::none());
         assert_eq!(register.get(RegA::A16, Reg16::Reg3), MaybeNumber::none());
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
 
         let s1 = "aaa".as_bytes();
         let s2 = "bbb".as_bytes();
@@ -1222,7 +1995,7 @@ mod tests {
             .exec(&mut register, lib_site);
         assert_eq!(register.get(RegA::A16, Reg16::Reg2), MaybeNumber::none());
         assert_eq!(register.get(RegA::A16, Reg16::Reg3), MaybeNumber::none());
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
         ControlFlowOp::Succ.exec(&mut register, lib_site);
 
         let s1 = [0u8; u16::MAX as usize];
@@ -1235,14 +2008,239 @@ mod tests {
             .exec(&mut register, lib_site);
         assert_eq!(register.get(RegA::A16, Reg16::Reg2).unwrap(), Number::from(0u16));
         assert_eq!(register.get(RegA::A16, Reg16::Reg3).unwrap(), Number::from(u16::MAX));
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         PutOp::PutA(RegA::A16, Reg32::Reg1, MaybeNumber::from(1).into())
             .exec(&mut register, lib_site);
         BytesOp::Con(1.into(), 2.into(), Reg32::Reg1, Reg32::Reg2, Reg32::Reg3)
             .exec(&mut register, lib_site);
         assert_eq!(register.get(RegA::A16, Reg16::Reg2), MaybeNumber::none());
         assert_eq!(register.get(RegA::A16, Reg16::Reg3), MaybeNumber::none());
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
+    }
+
+    #[test]
+    fn digest_test() {
+        use amplify::hex::FromHex;
+
+        type Vector = (&'static [u8], fn(RegS) -> DigestOp, RegR, &'static str);
+        let vectors: [Vector; 12] = [
+            (
+                b"",
+                |s| DigestOp::Sha3(s, Reg8::Reg3),
+                RegR::R256,
+                "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+            ),
+            (
+                b"abc",
+                |s| DigestOp::Sha3(s, Reg8::Reg3),
+                RegR::R256,
+                "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+            ),
+            (
+                b"",
+                |s| DigestOp::Keccak(s, Reg8::Reg3),
+                RegR::R256,
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            ),
+            (
+                b"abc",
+                |s| DigestOp::Keccak(s, Reg8::Reg3),
+                RegR::R256,
+                "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+            ),
+            (
+                b"abc",
+                |s| DigestOp::Blake2b(s, Reg8::Reg3),
+                RegR::R512,
+                "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+                 7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+            ),
+            (
+                b"",
+                |s| DigestOp::Blake3(s, Reg8::Reg3),
+                RegR::R256,
+                "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            ),
+            (
+                b"abc",
+                |s| DigestOp::Blake3(s, Reg8::Reg3),
+                RegR::R256,
+                "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+            ),
+            (
+                b"",
+                |s| DigestOp::Hash160(s, Reg8::Reg3),
+                RegR::R160,
+                "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb",
+            ),
+            (
+                b"",
+                |s| DigestOp::Hash256(s, Reg8::Reg3),
+                RegR::R256,
+                "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456",
+            ),
+            (
+                b"abc",
+                |s| DigestOp::Hash256(s, Reg8::Reg3),
+                RegR::R256,
+                "4f8b42c22dd3729b519ba6f68d2da7cc5b2d606d05daed5ad5128cc03e6c6358",
+            ),
+            (
+                b"abc",
+                |s| DigestOp::Sha256(s, Reg16::Reg3),
+                RegR::R256,
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                b"abc",
+                |s| DigestOp::Ripemd(s, Reg16::Reg3),
+                RegR::R160,
+                "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc",
+            ),
+        ];
+
+        let mut register = CoreRegs::default();
+        let lib_site = LibSite::default();
+        for (msg, op, reg, hash) in vectors {
+            BytesOp::Put(1.into(), Box::new(ByteStr::with(msg)), false)
+                .exec(&mut register, lib_site);
+            op(1.into()).exec(&mut register, lib_site);
+            assert!(register.st0);
+            let expected = Vec::<u8>::from_hex(hash).unwrap();
+            assert_eq!(register.get(reg, Reg32::Reg3).unwrap().as_ref(), &expected[..]);
+
+            // Missing source
+            op(2.into()).exec(&mut register, lib_site);
+            assert!(!register.st0);
+            assert_eq!(register.get(reg, Reg32::Reg3), MaybeNumber::none());
+            ControlFlowOp::Succ.exec(&mut register, lib_site);
+        }
+    }
+
+    #[test]
+    fn digest_tagged_test() {
+        let mut register = CoreRegs::default();
+        let lib_site = LibSite::default();
+        BytesOp::Put(1.into(), Box::new(ByteStr::with(b"BIP0340/challenge")), false)
+            .exec(&mut register, lib_site);
+        BytesOp::Put(2.into(), Box::new(ByteStr::with(b"message")), false)
+            .exec(&mut register, lib_site);
+        DigestOp::TaggedSha256(1.into(), 2.into(), Reg16::Reg3).exec(&mut register, lib_site);
+        assert!(register.st0);
+
+        let tag = sha256::Hash::hash(b"BIP0340/challenge");
+        let mut engine = sha256::Hash::engine();
+        engine.input(&tag[..]);
+        engine.input(&tag[..]);
+        engine.input(b"message");
+        let expected = sha256::Hash::from_engine(engine).into_inner();
+        assert_eq!(register.get(RegR::R256, Reg32::Reg3).unwrap().as_ref(), &expected[..]);
+
+        // Empty tag differs from missing one
+        BytesOp::Put(1.into(), Box::default(), false).exec(&mut register, lib_site);
+        DigestOp::TaggedSha256(1.into(), 2.into(), Reg16::Reg3).exec(&mut register, lib_site);
+        assert!(register.st0);
+        DigestOp::TaggedSha256(4.into(), 2.into(), Reg16::Reg3).exec(&mut register, lib_site);
+        assert!(!register.st0);
+        assert_eq!(register.get(RegR::R256, Reg32::Reg3), MaybeNumber::none());
+    }
+
+    #[test]
+    fn hmac_test() {
+        use amplify::hex::FromHex;
+
+        // Test case 2 from RFC 4231
+        let mut register = CoreRegs::default();
+        let lib_site = LibSite::default();
+        BytesOp::Put(1.into(), Box::new(ByteStr::with(b"Jefe")), false)
+            .exec(&mut register, lib_site);
+        BytesOp::Put(2.into(), Box::new(ByteStr::with(b"what do ya want for nothing?")), false)
+            .exec(&mut register, lib_site);
+
+        DigestOp::HmacSha256(1.into(), 2.into(), Reg16::Reg1).exec(&mut register, lib_site);
+        assert!(register.st0);
+        let expected =
+            Vec::<u8>::from_hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+                .unwrap();
+        assert_eq!(register.get(RegR::R256, Reg32::Reg1).unwrap().as_ref(), &expected[..]);
+
+        DigestOp::HmacSha512(1.into(), 2.into(), Reg16::Reg1).exec(&mut register, lib_site);
+        assert!(register.st0);
+        let expected = Vec::<u8>::from_hex(
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        )
+        .unwrap();
+        assert_eq!(register.get(RegR::R512, Reg32::Reg1).unwrap().as_ref(), &expected[..]);
+
+        // Missing key
+        DigestOp::HmacSha256(3.into(), 2.into(), Reg16::Reg1).exec(&mut register, lib_site);
+        assert!(!register.st0);
+        assert_eq!(register.get(RegR::R256, Reg32::Reg1), MaybeNumber::none());
+    }
+
+    #[test]
+    fn hkdf_test() {
+        use amplify::hex::FromHex;
+
+        // Test cases 1 and 3 from RFC 5869
+        let vectors = [
+            (
+                "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+                "000102030405060708090a0b0c",
+                "f0f1f2f3f4f5f6f7f8f9",
+                "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf\
+                 34007208d5b887185865",
+            ),
+            (
+                "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+                "",
+                "",
+                "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d\
+                 9d201395faa4b61a96c8",
+            ),
+        ];
+
+        let mut register = CoreRegs::default();
+        let lib_site = LibSite::default();
+        for (ikm, salt, info, prk, okm) in vectors {
+            for (reg, hex) in [(1, ikm), (2, salt), (3, info)] {
+                let bytes = Vec::<u8>::from_hex(hex).unwrap();
+                BytesOp::Put(reg.into(), Box::new(ByteStr::with(bytes)), false)
+                    .exec(&mut register, lib_site);
+            }
+            let okm = Vec::<u8>::from_hex(okm).unwrap();
+            PutOp::PutA(RegA::A16, Reg32::Reg1, MaybeNumber::from(okm.len() as u16).into())
+                .exec(&mut register, lib_site);
+
+            // Extract step is HMAC-SHA256 of the input keying material keyed with the salt
+            DigestOp::HmacSha256(2.into(), 1.into(), Reg16::Reg1).exec(&mut register, lib_site);
+            assert!(register.st0);
+            let prk = Vec::<u8>::from_hex(prk).unwrap();
+            assert_eq!(register.get(RegR::R256, Reg32::Reg1).unwrap().as_ref(), &prk[..]);
+
+            DigestOp::HkdfExpand(Reg32::Reg1, 3.into(), Reg32::Reg1, 4.into())
+                .exec(&mut register, lib_site);
+            assert!(register.st0);
+            assert_eq!(register.get_s(4u8).unwrap().as_ref(), &okm[..]);
+        }
+
+        // Output length exceeding 255 blocks
+        PutOp::PutA(RegA::A16, Reg32::Reg1, MaybeNumber::from(255u16 * 32 + 1).into())
+            .exec(&mut register, lib_site);
+        DigestOp::HkdfExpand(Reg32::Reg1, 3.into(), Reg32::Reg1, 4.into())
+            .exec(&mut register, lib_site);
+        assert!(!register.st0);
+        assert!(register.get_s(4u8).is_none());
+        ControlFlowOp::Succ.exec(&mut register, lib_site);
+        PutOp::PutA(RegA::A16, Reg32::Reg1, MaybeNumber::from(255u16 * 32).into())
+            .exec(&mut register, lib_site);
+        DigestOp::HkdfExpand(Reg32::Reg1, 3.into(), Reg32::Reg1, 4.into())
+            .exec(&mut register, lib_site);
+        assert!(register.st0);
+        assert_eq!(register.get_s(4u8).unwrap().len(), 255 * 32);
     }
 
     #[test]
@@ -1262,7 +2260,7 @@ mod tests {
         Secp256k1Op::Gen(Reg32::Reg3, Reg8::Reg3).exec(&mut register, lib_site);
         CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg2, Reg32::Reg3)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
     }
 
     #[test]
@@ -1282,7 +2280,7 @@ mod tests {
         Secp256k1Op::Gen(Reg32::Reg3, Reg8::Reg3).exec(&mut register, lib_site);
         CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg2, Reg32::Reg3)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
     }
 
     #[test]
@@ -1297,12 +2295,12 @@ mod tests {
         Secp256k1Op::Neg(Reg32::Reg2, Reg8::Reg3).exec(&mut register, lib_site);
         CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg1, Reg32::Reg2)
             .exec(&mut register, lib_site);
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
         ControlFlowOp::Succ.exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg1, Reg32::Reg3)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         PutOp::PutR(RegR::R256, Reg32::Reg5, MaybeNumber::from(5u8).into())
             .exec(&mut register, lib_site);
         PutOp::PutR(RegR::R256, Reg32::Reg6, MaybeNumber::from(6u8).into())
@@ -1313,7 +2311,119 @@ mod tests {
         Secp256k1Op::Add(Reg32::Reg2, Reg8::Reg6).exec(&mut register, lib_site);
         CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg5, Reg32::Reg6)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
+    }
+
+    #[test]
+    #[cfg(feature = "secp256k1")]
+    fn secp256k1_ecdsa_test() {
+        use secp256k1::{Message, PublicKey, SecretKey, SECP256K1};
+
+        let sk = SecretKey::from_slice(&[0x33; 32]).unwrap();
+        let pk = PublicKey::from_secret_key(SECP256K1, &sk).serialize_uncompressed();
+        let hash = sha256::Hash::hash(b"message").into_inner();
+        let sig = sk.sign_ecdsa(Message::from_slice(&hash).unwrap());
+
+        let mut register = CoreRegs::default();
+        let lib_site = LibSite::default();
+        let put = |register: &mut CoreRegs, reg, idx, data: &[u8]| {
+            PutOp::PutR(reg, idx, MaybeNumber::from(Number::from_slice(data)).into())
+                .exec(register, lib_site);
+        };
+        put(&mut register, RegR::R256, Reg32::Reg1, &hash);
+        put(&mut register, RegR::R512, Reg32::Reg2, &pk[1..]);
+        put(&mut register, RegR::R512, Reg32::Reg3, &sig.serialize_compact());
+        BytesOp::Put(1.into(), Box::new(ByteStr::with(sig.serialize_der())), false)
+            .exec(&mut register, lib_site);
+
+        Secp256k1VerifyOp::Ecdsa(Reg32::Reg1, Reg32::Reg2, Reg32::Reg3)
+            .exec(&mut register, lib_site);
+        assert!(register.st0);
+        Secp256k1VerifyOp::EcdsaDer(Reg32::Reg1, Reg32::Reg2, 1.into())
+            .exec(&mut register, lib_site);
+        assert!(register.st0);
+
+        // Signature over a different message
+        put(&mut register, RegR::R256, Reg32::Reg1, &sha256::Hash::hash(b"other").into_inner());
+        Secp256k1VerifyOp::Ecdsa(Reg32::Reg1, Reg32::Reg2, Reg32::Reg3)
+            .exec(&mut register, lib_site);
+        assert!(!register.st0);
+        ControlFlowOp::Succ.exec(&mut register, lib_site);
+        Secp256k1VerifyOp::EcdsaDer(Reg32::Reg1, Reg32::Reg2, 1.into())
+            .exec(&mut register, lib_site);
+        assert!(!register.st0);
+
+        // Compact signature is not a valid DER
+        put(&mut register, RegR::R256, Reg32::Reg1, &hash);
+        BytesOp::Put(1.into(), Box::new(ByteStr::with(sig.serialize_compact())), false)
+            .exec(&mut register, lib_site);
+        Secp256k1VerifyOp::EcdsaDer(Reg32::Reg1, Reg32::Reg2, 1.into())
+            .exec(&mut register, lib_site);
+        assert!(!register.st0);
+
+        // Missing public key
+        ControlFlowOp::Succ.exec(&mut register, lib_site);
+        Secp256k1VerifyOp::Ecdsa(Reg32::Reg1, Reg32::Reg4, Reg32::Reg3)
+            .exec(&mut register, lib_site);
+        assert!(!register.st0);
+    }
+
+    #[test]
+    #[cfg(feature = "secp256k1")]
+    fn secp256k1_schnorr_test() {
+        use amplify::hex::FromHex;
+
+        // Test vectors from BIP-340
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+                 25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+                true,
+            ),
+            (
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de3341\
+                 8906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a",
+                true,
+            ),
+            // Public key not on the curve
+            (
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+            ),
+        ];
+
+        let mut register = CoreRegs::default();
+        let lib_site = LibSite::default();
+        for (msg, pubkey, sig, valid) in vectors {
+            let msg = Vec::<u8>::from_hex(msg).unwrap();
+            let pubkey = Vec::<u8>::from_hex(pubkey).unwrap();
+            let sig = Vec::<u8>::from_hex(sig).unwrap();
+            for (reg, idx, data) in [
+                (RegR::R256, Reg32::Reg1, msg),
+                (RegR::R256, Reg32::Reg2, pubkey),
+                (RegR::R512, Reg32::Reg3, sig),
+            ] {
+                PutOp::PutR(reg, idx, MaybeNumber::from(Number::from_slice(data)).into())
+                    .exec(&mut register, lib_site);
+            }
+            Secp256k1VerifyOp::Schnorr(Reg32::Reg1, Reg32::Reg2, Reg32::Reg3)
+                .exec(&mut register, lib_site);
+            assert_eq!(register.st0, valid);
+
+            // Wrong message
+            PutOp::PutR(RegR::R256, Reg32::Reg1, MaybeNumber::from(1u8).into())
+                .exec(&mut register, lib_site);
+            Secp256k1VerifyOp::Schnorr(Reg32::Reg1, Reg32::Reg2, Reg32::Reg3)
+                .exec(&mut register, lib_site);
+            assert!(!register.st0);
+        }
     }
 
     #[test]
@@ -1327,16 +2437,25 @@ mod tests {
             .exec(&mut register, lib_site);
         PutOp::PutR(RegR::R256, Reg32::Reg3, MaybeNumber::from(6u8).into())
             .exec(&mut register, lib_site);
-        Curve25519Op::Gen(Reg32::Reg1, Reg8::Reg1).exec(&mut register, lib_site);
-        Curve25519Op::Mul(RegBlockAR::R, Reg32::Reg2, Reg32::Reg1, Reg32::Reg2)
+        Curve25519Op::Gen(Reg32::Reg1, Reg8::Reg4).exec(&mut register, lib_site);
+        Curve25519Op::Mul(RegBlockAR::R, Reg32::Reg2, Reg32::Reg4, Reg32::Reg5)
             .exec(&mut register, lib_site);
-        Curve25519Op::Gen(Reg32::Reg3, Reg8::Reg3).exec(&mut register, lib_site);
-        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg2, Reg32::Reg3)
+        assert!(register.st0);
+        Curve25519Op::Gen(Reg32::Reg3, Reg8::Reg6).exec(&mut register, lib_site);
+        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R256, Reg32::Reg5, Reg32::Reg6)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
-        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg1, Reg32::Reg3)
+        assert!(register.st0);
+        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R256, Reg32::Reg4, Reg32::Reg6)
+            .exec(&mut register, lib_site);
+        assert!(!register.st0);
+        ControlFlowOp::Succ.exec(&mut register, lib_site);
+        PutOp::PutA(RegA::A256, Reg32::Reg1, MaybeNumber::from(3u8).into())
             .exec(&mut register, lib_site);
-        assert_eq!(false, register.st0);
+        Curve25519Op::Mul(RegBlockAR::A, Reg32::Reg1, Reg32::Reg4, Reg32::Reg7)
+            .exec(&mut register, lib_site);
+        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R256, Reg32::Reg6, Reg32::Reg7)
+            .exec(&mut register, lib_site);
+        assert!(register.st0);
     }
 
     #[test]
@@ -1353,46 +2472,37 @@ mod tests {
         Curve25519Op::Gen(Reg32::Reg1, Reg8::Reg1).exec(&mut register, lib_site);
         Curve25519Op::Gen(Reg32::Reg2, Reg8::Reg2).exec(&mut register, lib_site);
         Curve25519Op::Gen(Reg32::Reg3, Reg8::Reg3).exec(&mut register, lib_site);
-        Curve25519Op::Add(Reg32::Reg1, Reg32::Reg2, Reg32::Reg4, false)
-            .exec(&mut register, lib_site);
-        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg3, Reg32::Reg4)
+        Curve25519Op::Add(Reg32::Reg1, Reg32::Reg2, Reg32::Reg4).exec(&mut register, lib_site);
+        assert!(register.st0);
+        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R256, Reg32::Reg3, Reg32::Reg4)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
     }
 
     #[test]
     #[cfg(feature = "curve25519")]
-    fn curve25519_add_overflow_test() {
+    fn curve25519_invalid_point_test() {
         let mut register = CoreRegs::default();
         let lib_site = LibSite::default();
-        let l_plus_two_bytes: [u8; 32] = [
-            0xef, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9,
-            0xde, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x10,
-        ];
-        PutOp::PutR(
-            RegR::R256,
-            Reg32::Reg1,
-            MaybeNumber::from(Number::from_slice(l_plus_two_bytes)).into(),
-        )
-        .exec(&mut register, lib_site);
-        PutOp::PutR(RegR::R256, Reg32::Reg2, MaybeNumber::from(1u8).into())
-            .exec(&mut register, lib_site);
-        PutOp::PutR(RegR::R256, Reg32::Reg3, MaybeNumber::from(3u8).into())
+        // y = 2 does not correspond to any curve point
+        PutOp::PutR(RegR::R256, Reg32::Reg1, MaybeNumber::from(2u8).into())
             .exec(&mut register, lib_site);
-        Curve25519Op::Gen(Reg32::Reg1, Reg8::Reg1).exec(&mut register, lib_site);
-        Curve25519Op::Gen(Reg32::Reg2, Reg8::Reg2).exec(&mut register, lib_site);
-        Curve25519Op::Gen(Reg32::Reg3, Reg8::Reg3).exec(&mut register, lib_site);
-        Curve25519Op::Add(Reg32::Reg1, Reg32::Reg2, Reg32::Reg4, false)
+        PutOp::PutR(RegR::R256, Reg32::Reg2, MaybeNumber::from(1u8).into())
             .exec(&mut register, lib_site);
-        assert_eq!(false, register.st0);
+        Curve25519Op::Gen(Reg32::Reg2, Reg8::Reg3).exec(&mut register, lib_site);
+        Curve25519Op::Add(Reg32::Reg1, Reg32::Reg3, Reg32::Reg4).exec(&mut register, lib_site);
+        assert!(!register.st0);
+        assert_eq!(register.get(RegR::R256, Reg32::Reg4), MaybeNumber::none());
         ControlFlowOp::Succ.exec(&mut register, lib_site);
-        Curve25519Op::Add(Reg32::Reg1, Reg32::Reg2, Reg32::Reg4, true)
-            .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
-        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg3, Reg32::Reg4)
+        Curve25519Op::Neg(Reg32::Reg1, Reg8::Reg4).exec(&mut register, lib_site);
+        assert!(!register.st0);
+        ControlFlowOp::Succ.exec(&mut register, lib_site);
+        Curve25519Op::Mul(RegBlockAR::R, Reg32::Reg2, Reg32::Reg1, Reg32::Reg4)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(!register.st0);
+        ControlFlowOp::Succ.exec(&mut register, lib_site);
+        Curve25519Op::Gen(Reg32::Reg5, Reg8::Reg4).exec(&mut register, lib_site);
+        assert!(!register.st0);
     }
 
     #[test]
@@ -1405,14 +2515,14 @@ mod tests {
         Curve25519Op::Gen(Reg32::Reg1, Reg8::Reg1).exec(&mut register, lib_site);
         Curve25519Op::Neg(Reg32::Reg1, Reg8::Reg2).exec(&mut register, lib_site);
         Curve25519Op::Neg(Reg32::Reg2, Reg8::Reg3).exec(&mut register, lib_site);
-        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg1, Reg32::Reg2)
+        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R256, Reg32::Reg1, Reg32::Reg2)
             .exec(&mut register, lib_site);
-        assert_eq!(false, register.st0);
+        assert!(!register.st0);
         ControlFlowOp::Succ.exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
-        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg1, Reg32::Reg3)
+        assert!(register.st0);
+        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R256, Reg32::Reg1, Reg32::Reg3)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
         PutOp::PutR(RegR::R256, Reg32::Reg5, MaybeNumber::from(5u8).into())
             .exec(&mut register, lib_site);
         PutOp::PutR(RegR::R256, Reg32::Reg6, MaybeNumber::from(6u8).into())
@@ -1420,10 +2530,84 @@ mod tests {
         Curve25519Op::Gen(Reg32::Reg5, Reg8::Reg5).exec(&mut register, lib_site);
         Curve25519Op::Gen(Reg32::Reg6, Reg8::Reg6).exec(&mut register, lib_site);
         // -G + 6G
-        Curve25519Op::Add(Reg32::Reg2, Reg32::Reg6, Reg32::Reg7, true)
-            .exec(&mut register, lib_site);
-        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R512, Reg32::Reg5, Reg32::Reg7)
+        Curve25519Op::Add(Reg32::Reg2, Reg32::Reg6, Reg32::Reg7).exec(&mut register, lib_site);
+        CmpOp::EqR(NoneEqFlag::NonEqual, RegR::R256, Reg32::Reg5, Reg32::Reg7)
             .exec(&mut register, lib_site);
-        assert_eq!(true, register.st0);
+        assert!(register.st0);
+    }
+
+    #[test]
+    #[cfg(feature = "curve25519")]
+    fn curve25519_verify_test() {
+        use amplify::hex::FromHex;
+
+        // Test vectors from RFC 8032, section 7.1
+        let vectors = [
+            (
+                "",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+                 5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "72",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+                 085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+            (
+                "af82",
+                "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac\
+                 18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+            ),
+        ];
+
+        let mut register = CoreRegs::default();
+        let lib_site = LibSite::default();
+        for (msg, pubkey, sig) in vectors {
+            let msg = Vec::<u8>::from_hex(msg).unwrap();
+            let pubkey = Vec::<u8>::from_hex(pubkey).unwrap();
+            let mut sig = Vec::<u8>::from_hex(sig).unwrap();
+            BytesOp::Put(1.into(), Box::new(ByteStr::with(&msg)), false)
+                .exec(&mut register, lib_site);
+            PutOp::PutR(
+                RegR::R256,
+                Reg32::Reg1,
+                MaybeNumber::from(Number::from_slice(&pubkey)).into(),
+            )
+            .exec(&mut register, lib_site);
+            PutOp::PutR(
+                RegR::R512,
+                Reg32::Reg2,
+                MaybeNumber::from(Number::from_slice(&sig)).into(),
+            )
+            .exec(&mut register, lib_site);
+            Curve25519Op::Verify(1.into(), Reg32::Reg1, Reg32::Reg2).exec(&mut register, lib_site);
+            assert!(register.st0);
+
+            // Wrong message
+            BytesOp::Put(1.into(), Box::new(ByteStr::with(b"wrong")), false)
+                .exec(&mut register, lib_site);
+            Curve25519Op::Verify(1.into(), Reg32::Reg1, Reg32::Reg2).exec(&mut register, lib_site);
+            assert!(!register.st0);
+
+            // Non-canonical S
+            BytesOp::Put(1.into(), Box::new(ByteStr::with(&msg)), false)
+                .exec(&mut register, lib_site);
+            sig[63] |= 0x80;
+            PutOp::PutR(
+                RegR::R512,
+                Reg32::Reg2,
+                MaybeNumber::from(Number::from_slice(&sig)).into(),
+            )
+            .exec(&mut register, lib_site);
+            Curve25519Op::Verify(1.into(), Reg32::Reg1, Reg32::Reg2).exec(&mut register, lib_site);
+            assert!(!register.st0);
+        }
+
+        // Missing message
+        Curve25519Op::Verify(2.into(), Reg32::Reg1, Reg32::Reg2).exec(&mut register, lib_site);
+        assert!(!register.st0);
     }
 }
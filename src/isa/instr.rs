@@ -61,8 +61,12 @@ where
     // 0b00_110_***
     Bytes(BytesOp),
 
+    /// Modular arithmetic instructions. See [`ModularOp`] for the details.
+    // 0b10_010_1**
+    Modular(ModularOp),
+
     /// Cryptographic hashing functions. See [`DigestOp`] for the details.
-    // 0b01_000_***
+    // 0b10_000_***
     Digest(DigestOp),
 
     #[cfg(feature = "secp256k1")]
@@ -75,6 +79,20 @@ where
     // 0b01_001_1**
     Curve25519(Curve25519Op),
 
+    #[cfg(feature = "secp256k1")]
+    /// Signature verification over Secp256k1 elliptic curve. See [`Secp256k1VerifyOp`] for the
+    /// details.
+    // 0b10_010_001..=0b10_010_011
+    Secp256k1Verify(Secp256k1VerifyOp),
+
+    /// ALU runtime extension instructions interfacing the host environment. See [`AluReOp`] for
+    /// the details.
+    ///
+    /// Opcodes of the `ALURE` sub-range of ISA extensions which are not allocated to any of these
+    /// instructions are decoded as [`Instr::ReservedInstruction`].
+    // 0b10_011_***
+    AluRe(AluReOp),
+
     /// Extension operations which can be provided by a host environment provided via generic
     /// parameter
     // 0b10_***_***
@@ -470,6 +488,54 @@ pub enum ArithmeticOp {
     Abs(RegAF, Reg16),
 }
 
+/// Modular arithmetic (`MODARITH`) instructions.
+///
+/// All operands are read from registers of the same integer arithmetic register family and are
+/// treated as unsigned integers. Intermediate results are computed at the double register width,
+/// so the operations never overflow.
+///
+/// If any of the source registers is set to `None`, or the modulus is zero, the destination is
+/// set to `None` and `st0` is set to `false`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+pub enum ModularOp {
+    /// Computes `(a + b) mod m`.
+    #[display("addmod  {0}{1},{0}{2},{0}{3},{0}{4}")]
+    AddMod(
+        RegA,
+        /** Source `a` */ Reg32,
+        /** Source `b` */ Reg32,
+        /** Modulus */ Reg32,
+        /** Operation destination */ Reg32,
+    ),
+
+    /// Computes `(a * b) mod m`.
+    #[display("mulmod  {0}{1},{0}{2},{0}{3},{0}{4}")]
+    MulMod(
+        RegA,
+        /** Source `a` */ Reg32,
+        /** Source `b` */ Reg32,
+        /** Modulus */ Reg32,
+        /** Operation destination */ Reg32,
+    ),
+
+    /// Computes `(a ^ e) mod m`.
+    #[display("powmod  {0}{1},{0}{2},{0}{3},{0}{4}")]
+    PowMod(
+        RegA,
+        /** Base */ Reg32,
+        /** Exponent */ Reg32,
+        /** Modulus */ Reg32,
+        /** Operation destination */ Reg32,
+    ),
+
+    /// Computes modular multiplicative inverse `a^-1 mod m`.
+    ///
+    /// If the inverse does not exist (i.e. `a` and `m` are not coprime) the destination is set to
+    /// `None` and `st0` is set to `false`.
+    #[display("invmod  {0}{1},{0}{2},{0}{3}")]
+    InvMod(RegA, /** Source */ Reg32, /** Modulus */ Reg32, /** Operation destination */ Reg32),
+}
+
 /// Bit operations & boolean algebra instructions
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
 pub enum BitwiseOp {
@@ -724,18 +790,18 @@ pub enum BytesOp {
     ///   (1) first, second <- None; `st0` <- false
     ///   (2) first <- None, second <- `src_len > 0` ? src : None; `st0` <- false
     ///   (3) first <- None, second <- `src_len > 0` ? src : zero-len; `st0` <- false
-    ///   (4) first <- zero-len, second <- `src_len > 0` ? src : zero-len
-    /// `offset > 0 && offset < src_len`: `st0` always set to false
-    ///   (1) first, second <- None
-    ///   (5) first <- short, second <- None
-    ///   (6) first <- short, second <- zero-len
-    ///   (7) first <- zero-ext, second <- None
-    ///   (8) first <- zero-ext, second <- zero-len
+    ///   (4-8) first <- zero-len, second <- `src_len > 0` ? src : zero-len
+    /// `offset > 0 && offset < src_len`: operation succeeds, `st0` value is not changed
     /// `offset = src_len`:
     ///   (1) first, second <- None; `st0` <- false
     ///   (5,7) first <- ok, second <- None; `st0` <- false
-    ///   (6,8) first <- ok, second <- zero-len
-    /// `offset > src_len`: operation succeeds anyway, `st0` value is not changed
+    ///   (2-4,6,8) first <- ok, second <- zero-len
+    /// `offset > src_len`: `st0` always set to false
+    ///   (1-4) first, second <- None
+    ///   (5) first <- short, second <- None
+    ///   (6) first <- short, second <- zero-len
+    ///   (7) first <- zero-len, second <- None
+    ///   (8) first <- zero-len, second <- zero-len
     /// </pre>
     ///
     /// Rule on `st0` changes: if at least one of the destination registers is set to `None`, or
@@ -778,7 +844,14 @@ pub enum BytesOp {
     ///   (6-8) Use `src_len` instead of `offset` and use flag value from the first section
     /// </pre>
     ///
-    /// In all of these cases `st0` is set to `false`. Otherwise, `st0` value is not modified.
+    /// When the offset exceeds the destination length, (2), (4) and (5) fill the gap with zeros,
+    /// while (3) and (6-8) append the source to the end of the destination. On the length
+    /// overflow only (5), (7) and (8) complete the operation; all other flags set the destination
+    /// to `None`.
+    ///
+    /// In all of these cases `st0` is set to `false`. Otherwise, `st0` value is not modified. If
+    /// any of the strings or the offset register is undefined, the destination is set to `None`
+    /// and `st0` is set to `false`.
     #[display("ins.{3}   {0},{1},a16{2}")]
     Ins(
         InsertFlag,
@@ -804,9 +877,11 @@ pub enum BytesOp {
     ///
     /// `offset_start > src_len`:
     ///   (1) set destination to `None`
-    ///   (2) set destination to zero-length string
+    ///   (2,3) set destination to zero-length string
+    ///   (4) set destination to `offset_end - offset_start` zero bytes
     /// `offset_end > src_len && offset_start <= src_len`:
     ///   (1) set destination to `None`
+    ///   (2) set destination to zero-length string
     ///   (3) set destination to the fragment of the string `offset_start..src_len`
     ///   (4) set destination to the fragment of the string `offset_start..src_len` and extend
     ///       its length up to `offset_end - offset_start` with trailing zeros.
@@ -871,6 +946,116 @@ pub enum DigestOp {
         /** Index of string register */ RegS,
         /** Index of `r512` register to save result to */ Reg16,
     ),
+
+    /// Computes SHA3-256 hash value
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if the source register does not
+    /// contain a value
+    #[display("sha3    {0},r256{1}")]
+    Sha3(
+        /** Index of string register */ RegS,
+        /** Index of `r256` register to save result to */ Reg8,
+    ),
+
+    /// Computes Keccak-256 hash value (original Keccak padding, as used in Ethereum)
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if the source register does not
+    /// contain a value
+    #[display("keccak  {0},r256{1}")]
+    Keccak(
+        /** Index of string register */ RegS,
+        /** Index of `r256` register to save result to */ Reg8,
+    ),
+
+    /// Computes BLAKE2b-512 hash value
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if the source register does not
+    /// contain a value
+    #[display("blake2  {0},r512{1}")]
+    Blake2b(
+        /** Index of string register */ RegS,
+        /** Index of `r512` register to save result to */ Reg8,
+    ),
+
+    /// Computes BLAKE3 hash value
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if the source register does not
+    /// contain a value
+    #[display("blake3  {0},r256{1}")]
+    Blake3(
+        /** Index of string register */ RegS,
+        /** Index of `r256` register to save result to */ Reg8,
+    ),
+
+    /// Computes Bitcoin `hash160` value, i.e. RIPEMD160 of SHA256
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if the source register does not
+    /// contain a value
+    #[display("hash160 {0},r160{1}")]
+    Hash160(
+        /** Index of string register */ RegS,
+        /** Index of `r160` register to save result to */ Reg8,
+    ),
+
+    /// Computes Bitcoin `hash256` value, i.e. double SHA256
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if the source register does not
+    /// contain a value
+    #[display("hash256 {0},r256{1}")]
+    Hash256(
+        /** Index of string register */ RegS,
+        /** Index of `r256` register to save result to */ Reg8,
+    ),
+
+    /// Computes BIP-340 tagged SHA256 hash value, i.e. `SHA256(SHA256(tag) || SHA256(tag) ||
+    /// msg)`
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if any of the source registers
+    /// does not contain a value
+    #[display("tagged  {0},{1},r256{2}")]
+    TaggedSha256(
+        /** Index of string register containing tag */ RegS,
+        /** Index of string register containing message */ RegS,
+        /** Index of `r256` register to save result to */ Reg16,
+    ),
+
+    /// Computes HMAC-SHA256 value
+    ///
+    /// HKDF-SHA256 extract step (RFC 5869) is HMAC-SHA256 of the input keying material keyed with
+    /// the salt, so the `hkdfext` assembler mnemonic is an alias for this instruction.
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if any of the source registers
+    /// does not contain a value
+    #[display("hmac    {0},{1},r256{2}")]
+    HmacSha256(
+        /** Index of string register containing key */ RegS,
+        /** Index of string register containing message */ RegS,
+        /** Index of `r256` register to save result to */ Reg16,
+    ),
+
+    /// Computes HMAC-SHA512 value
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if any of the source registers
+    /// does not contain a value
+    #[display("hmac    {0},{1},r512{2}")]
+    HmacSha512(
+        /** Index of string register containing key */ RegS,
+        /** Index of string register containing message */ RegS,
+        /** Index of `r512` register to save result to */ Reg16,
+    ),
+
+    /// Performs HKDF-SHA256 expand step (RFC 5869), producing output keying material of the
+    /// length taken from `a16` register
+    ///
+    /// Sets `st0` to `false` and destination register to `None` if any of the source registers
+    /// does not contain a value or if the requested length exceeds 8160 bytes (255 blocks)
+    #[display("hkdfexp r256{0},{1},a16{2},{3}")]
+    HkdfExpand(
+        /** Index of `r256` register containing pseudorandom key */ Reg32,
+        /** Index of string register containing info */ RegS,
+        /** Index of `a16` register containing output length */ Reg32,
+        /** Index of string register to save output keying material to */ RegS,
+    ),
 }
 
 /// Operations on Secp256k1 elliptic curve
@@ -903,20 +1088,59 @@ pub enum Secp256k1Op {
     Neg(/** Register hilding EC point to negate */ Reg32, /** Destination register */ Reg8),
 }
 
-/// Operations on Curve25519 elliptic curve
+/// Signature verification over Secp256k1 elliptic curve
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+pub enum Secp256k1VerifyOp {
+    /// Verifies ECDSA signature in compact form (64 bytes of `r` and `s` values) over a message
+    /// hash, setting `st0` to the result of the verification.
+    ///
+    /// Signatures with high `s` value are rejected.
+    #[display("ecdsa   r256{0},r512{1},r512{2}")]
+    Ecdsa(
+        /** Register containing message hash */ Reg32,
+        /** Register containing EC point of the public key */ Reg32,
+        /** Register containing signature */ Reg32,
+    ),
+
+    /// Verifies DER-encoded ECDSA signature from a string register over a message hash, setting
+    /// `st0` to the result of the verification.
+    ///
+    /// Signatures with high `s` value or not strictly following DER encoding are rejected.
+    #[display("ecdsa   r256{0},r512{1},{2}")]
+    EcdsaDer(
+        /** Register containing message hash */ Reg32,
+        /** Register containing EC point of the public key */ Reg32,
+        /** Index of string register containing signature */ RegS,
+    ),
+
+    /// Verifies BIP-340 Schnorr signature over a message hash, setting `st0` to the result of the
+    /// verification.
+    #[display("schnorr r256{0},r256{1},r512{2}")]
+    Schnorr(
+        /** Register containing message hash */ Reg32,
+        /** Register containing x-only public key */ Reg32,
+        /** Register containing signature */ Reg32,
+    ),
+}
+
+/// Operations on Curve25519 elliptic curve in twisted Edwards form (Ed25519).
+///
+/// Curve points are kept in `r256` registers in the compressed Edwards Y form; scalars are taken
+/// modulo the order of the basepoint. Operations set `st0` to `false` and the destination register
+/// to `None` if some of the source registers do not contain a value or a valid curve point.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
 pub enum Curve25519Op {
     /// Generates new elliptic curve point value saved into destination
-    /// register in `r512` set using scalar value from the source `r256`
+    /// register in `r256` set using scalar value from the source `r256`
     /// register
-    #[display("edgen   r256{0},r512{1}")]
+    #[display("edgen   r256{0},r256{1}")]
     Gen(
         /** Register containing scalar */ Reg32,
         /** Destination register to put G * scalar */ Reg8,
     ),
 
     /// Multiplies elliptic curve point on a scalar
-    #[display("edmul   {0}256{1},r512{2},r512{3}")]
+    #[display("edmul   {0}256{1},r256{2},r256{3}")]
     Mul(
         /** Use `a` or `r` register as scalar source */ RegBlockAR,
         /** Scalar register index */ Reg32,
@@ -925,15 +1149,35 @@ pub enum Curve25519Op {
     ),
 
     /// Adds two elliptic curve points
-    #[display("edadd   r512{0},r512{1},r512{2},{3}")]
-    Add(
-        /** Source 1 */ Reg32,
-        /** Source 2 */ Reg32,
-        /** Destination register */ Reg32,
-        /** Allow overflows */ bool,
-    ),
+    #[display("edadd   r256{0},r256{1},r256{2}")]
+    Add(/** Source 1 */ Reg32, /** Source 2 */ Reg32, /** Destination register */ Reg32),
 
     /// Negates elliptic curve point
-    #[display("edneg   r512{0},r512{1}")]
+    #[display("edneg   r256{0},r256{1}")]
     Neg(/** Register hilding EC point to negate */ Reg32, /** Destination register */ Reg8),
+
+    /// Verifies Ed25519 signature (RFC 8032) over a message from a string register, setting `st0`
+    /// to the result of the verification.
+    ///
+    /// The signature is kept in `r512` register as a concatenation of the compressed `R` point and
+    /// `S` scalar; `S` must be reduced modulo the basepoint order.
+    #[display("edver   {0},r256{1},r512{2}")]
+    Verify(
+        /** Index of string register containing message */ RegS,
+        /** Register containing public key */ Reg32,
+        /** Register containing signature */ Reg32,
+    ),
+}
+
+/// ALU runtime extension (`ALURE`) instructions providing interface to the host environment
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+pub enum AluReOp {
+    /// Calls host function with the provided id, registered with the virtual machine by the
+    /// embedding application (see [`crate::Vm::register_host_fn`]). The function reads its
+    /// arguments from the registers and writes results back to the registers.
+    ///
+    /// Sets `st0` to `false` if there is no function registered under the id, or if the function
+    /// reports a failure.
+    #[display("hcall   {0}")]
+    HCall(/** Host function id */ u16),
 }
@@ -19,6 +19,8 @@ mod flags;
 mod instr;
 pub mod opcodes;
 
+#[doc(hidden)]
+pub use asm::_asm_panic;
 pub use bytecode::{Bytecode, BytecodeError};
 pub use exec::{ExecStep, InstructionSet};
 pub use flags::{
@@ -26,8 +28,8 @@ pub use flags::{
     ParseFlagError, RoundingFlag, SignFlag, SplitFlag,
 };
 pub use instr::{
-    ArithmeticOp, BitwiseOp, BytesOp, CmpOp, ControlFlowOp, Curve25519Op, DigestOp, Instr, MoveOp,
-    PutOp, ReservedOp, Secp256k1Op,
+    AluReOp, ArithmeticOp, BitwiseOp, BytesOp, CmpOp, ControlFlowOp, Curve25519Op, DigestOp, Instr,
+    ModularOp, MoveOp, PutOp, ReservedOp, Secp256k1Op, Secp256k1VerifyOp,
 };
 
 /// List of standardised ISA extensions.
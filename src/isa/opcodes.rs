@@ -105,6 +105,11 @@ pub const INSTR_RESV_TO: u8 = 0b01_111_111;
 pub const INSTR_RIPEMD: u8 = 0b10_000_000;
 pub const INSTR_SHA256: u8 = 0b10_000_001;
 pub const INSTR_SHA512: u8 = 0b10_000_010;
+pub const INSTR_KECCAK: u8 = 0b10_000_011;
+pub const INSTR_BLAKE: u8 = 0b10_000_100;
+pub const INSTR_HMAC: u8 = 0b10_000_101;
+pub const INSTR_HASH_BTC: u8 = 0b10_000_110;
+pub const INSTR_SHA256_TAGGED: u8 = 0b10_000_111;
 
 // ### Secp256k1 operations (SECP256K1)
 
@@ -119,6 +124,30 @@ pub const INSTR_ED_GEN: u8 = 0b10_001_100;
 pub const INSTR_ED_MUL: u8 = 0b10_001_101;
 pub const INSTR_ED_ADD: u8 = 0b10_001_110;
 pub const INSTR_ED_NEG: u8 = 0b10_001_111;
+pub const INSTR_ED_VERIFY: u8 = 0b10_010_000;
+
+// ### Secp256k1 signature verification (SECP256K1)
+
+pub const INSTR_SECP_ECDSA: u8 = 0b10_010_001;
+pub const INSTR_SECP_ECDSA_DER: u8 = 0b10_010_010;
+pub const INSTR_SECP_SCHNORR: u8 = 0b10_010_011;
+
+// ### Modular arithmetic (MODARITH)
+
+pub const INSTR_ADDMOD: u8 = 0b10_010_100;
+pub const INSTR_MULMOD: u8 = 0b10_010_101;
+pub const INSTR_POWMOD: u8 = 0b10_010_110;
+pub const INSTR_INVMOD: u8 = 0b10_010_111;
+
+// ### ALU runtime extensions (ALURE)
+//
+// Sub-range of ISA extension opcodes allocated to the `ALURE` extension, identified by
+// `ISA_ID_ALURE`. Other ISA extensions must not use these opcodes.
+
+pub const INSTR_ALURE_FROM: u8 = 0b10_011_000;
+pub const INSTR_ALURE_TO: u8 = 0b10_011_111;
+
+pub const INSTR_HCALL: u8 = 0b10_011_000;
 
 // Opcodes with may be used by ISA extensions
 pub const INSTR_ISAE_FROM: u8 = 0b10_000_000;
@@ -87,6 +87,7 @@
 //!   - one for signed/unsigned variant of the encoding
 //!   - one for checked or wrapped variant of exception handling
 //! * Float encoding has 4 variants of rounding, matching IEEE-754 options
+//!
 //! Thus, many arithmetic instructions have 8 variants, indicating the used encoding (unsigned,
 //! signed integer or float) and operation behavior in situation when resulting value does not fit
 //! into the register (overflow or wrap for integers and one of four rounding options for floats).
@@ -108,6 +109,7 @@
 //! - Status (st0), boolean (one bit)
 //! - Cycle counter (cy0), 16 bits
 //! - Instruction complexity accumulator (ca0), 16 bits
+//! - Instruction complexity limit (cl0), optional 64 bits
 //! - Call stack register (cs0), 3*2^16 bits (192kB block)
 //! - Call stack pointer register (cp0), 16 bits
 //!
@@ -115,7 +117,6 @@
 
 // TODO(#6) Complete string operations
 // TODO(#7) Complete assembly compiler for string operations
-// TODO(#8) Implement operations on Edwards curves
 
 #[macro_use]
 extern crate alloc;
@@ -138,5 +139,5 @@ pub mod reg;
 pub mod vm;
 
 pub use isa::Isa;
-pub use program::Program;
-pub use vm::Vm;
+pub use program::{CompiledProgram, Program};
+pub use vm::{ExecOutcome, Halt, Vm};
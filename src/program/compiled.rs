@@ -0,0 +1,178 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Programs with pre-decoded instructions, which can be executed multiple times without the
+//! decoding overhead
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use super::lib::exec_decoded;
+use super::{Lib, LibId, LibSite, Program};
+use crate::isa::InstructionSet;
+use crate::reg::CoreRegs;
+use crate::vm::{Env, Halt, NoTrace, Tracer};
+
+/// Library with pre-decoded instructions and cached library id.
+///
+/// Instructions are decoded once, when the library is compiled. Execution of the instructions
+/// located at their original offsets does not require decoding; jumps into the middle of an
+/// instruction or beyond the decoded code are still supported by decoding the bytecode on the
+/// fly, so the execution produces the same results as the execution of the original [`Lib`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CompiledLib<Isa>
+where
+    Isa: InstructionSet,
+{
+    id: LibId,
+    lib: Lib,
+    code: Vec<Isa>,
+    /// Offsets of the decoded instructions, plus the offset following the last of them
+    offsets: Vec<u16>,
+}
+
+impl<Isa> CompiledLib<Isa>
+where
+    Isa: InstructionSet,
+{
+    /// Decodes library code
+    pub fn compile(lib: Lib) -> Self {
+        let id = lib.id();
+        let (code, offsets) = lib.decode::<Isa>();
+        CompiledLib { id, lib, code, offsets }
+    }
+
+    /// Returns library id computed during compilation
+    #[inline]
+    pub fn id(&self) -> LibId { self.id }
+
+    /// Returns reference to the original library
+    #[inline]
+    pub fn lib(&self) -> &Lib { &self.lib }
+
+    /// Releases the original library
+    #[inline]
+    pub fn into_lib(self) -> Lib { self.lib }
+
+    /// Returns pre-decoded instructions
+    #[inline]
+    pub fn instructions(&self) -> &[Isa] { &self.code }
+
+    /// Returns index of the pre-decoded instruction located at a given offset, if any
+    pub fn instr_index(&self, pos: u16) -> Option<usize> {
+        self.offsets[..self.code.len()].binary_search(&pos).ok()
+    }
+
+    /// Executes library code starting at entrypoint, like [`Lib::exec`].
+    #[inline]
+    pub fn exec(&self, entrypoint: u16, registers: &mut CoreRegs) -> Result<LibSite, (u16, Halt)> {
+        self.exec_traced(entrypoint, registers, &mut NoTrace)
+    }
+
+    /// Executes library code starting at entrypoint, reporting execution events to the provided
+    /// tracer, like [`Lib::exec_traced`].
+    #[inline]
+    pub fn exec_traced(
+        &self,
+        entrypoint: u16,
+        registers: &mut CoreRegs,
+        tracer: &mut impl Tracer,
+    ) -> Result<LibSite, (u16, Halt)> {
+        self.exec_with(entrypoint, registers, &mut Env::default(), tracer)
+    }
+
+    /// Executes library code starting at entrypoint with provided execution environment.
+    pub(crate) fn exec_with(
+        &self,
+        entrypoint: u16,
+        registers: &mut CoreRegs,
+        env: &mut Env<Isa>,
+        tracer: &mut impl Tracer,
+    ) -> Result<LibSite, (u16, Halt)> {
+        let mut pos = entrypoint;
+        let mut index = self.instr_index(pos);
+        loop {
+            let next = match index {
+                Some(idx) => exec_decoded(
+                    &self.code[idx],
+                    LibSite::with(pos, self.id),
+                    self.offsets[idx + 1],
+                    registers,
+                    env,
+                    tracer,
+                )?,
+                None => self.lib.step_as::<Isa>(self.id, pos, registers, env, tracer)?,
+            };
+            if next.lib != self.id {
+                return Ok(next);
+            }
+            // Sequential execution does not require the instruction lookup
+            index = match index {
+                Some(idx) if next.pos == self.offsets[idx + 1] && idx + 1 < self.code.len() => {
+                    Some(idx + 1)
+                }
+                _ => self.instr_index(next.pos),
+            };
+            pos = next.pos;
+        }
+    }
+}
+
+/// Program with pre-decoded instructions of all its libraries.
+///
+/// Compiled program is executed by [`crate::Vm::exec_compiled`] and provides the same results as
+/// the original [`Program`], avoiding instruction decoding and library id computation on each of
+/// the program runs.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CompiledProgram<Isa>
+where
+    Isa: InstructionSet,
+{
+    libs: BTreeMap<LibId, CompiledLib<Isa>>,
+    entrypoint: LibSite,
+}
+
+impl<Isa> CompiledProgram<Isa>
+where
+    Isa: InstructionSet,
+{
+    /// Compiles all libraries of the program
+    pub fn compile<const RUNTIME_MAX_TOTAL_LIBS: u16>(
+        program: &Program<Isa, RUNTIME_MAX_TOTAL_LIBS>,
+    ) -> Self {
+        let libs =
+            program.libs().map(|(id, lib)| (*id, CompiledLib::compile(lib.clone()))).collect();
+        CompiledProgram { libs, entrypoint: program.entrypoint() }
+    }
+
+    /// Returns reference to a specific compiled library, if it is part of the program.
+    #[inline]
+    pub fn lib(&self, id: LibId) -> Option<&CompiledLib<Isa>> { self.libs.get(&id) }
+
+    /// Returns number of libraries used by the program.
+    #[inline]
+    pub fn libs_count(&self) -> u16 { self.libs.len() as u16 }
+
+    /// Returns program entry point.
+    #[inline]
+    pub fn entrypoint(&self) -> LibSite { self.entrypoint }
+}
+
+impl<Isa, const RUNTIME_MAX_TOTAL_LIBS: u16> From<&Program<Isa, RUNTIME_MAX_TOTAL_LIBS>>
+    for CompiledProgram<Isa>
+where
+    Isa: InstructionSet,
+{
+    #[inline]
+    fn from(program: &Program<Isa, RUNTIME_MAX_TOTAL_LIBS>) -> Self {
+        CompiledProgram::compile(program)
+    }
+}
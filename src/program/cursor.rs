@@ -22,7 +22,10 @@ use crate::program::constants::{CODE_SEGMENT_MAX_LEN, DATA_SEGMENT_MAX_LEN};
 use crate::reg::NumericRegister;
 
 /// Cursor for accessing bytecode bounded by [`CODE_SEGMENT_MAX_LEN`] length and data segment
-/// bounded by [`DATA_SEGMENT_MAX_LEN`]
+/// bounded by [`DATA_SEGMENT_MAX_LEN`].
+///
+/// Bytecode provided to the cursor may be shorter than the code segment; bytes beyond its end are
+/// read as zeros, like if the bytecode was extended with zeros up to `u16::MAX` bytes.
 pub struct Cursor<'a, T, D>
 where
     T: AsRef<[u8]>,
@@ -114,20 +117,31 @@ where
     #[inline]
     fn as_ref(&self) -> &[u8] { self.bytecode.as_ref() }
 
+    /// Detects end of the zero-extended bytecode
+    #[inline]
+    fn is_end(&self) -> bool { self.byte_pos == u16::MAX }
+
+    /// Reads byte from the zero-extended bytecode
+    #[inline]
+    fn byte_at(&self, pos: usize) -> u8 { self.as_ref().get(pos).copied().unwrap_or_default() }
+
+    /// Reads bytes from the zero-extended bytecode
+    fn bytes_at<const LEN: usize>(&self, pos: usize) -> [u8; LEN] {
+        let mut buf = [0u8; LEN];
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = self.byte_at(pos + i);
+        }
+        buf
+    }
+
+    /// Reads `bit_count` bits starting from the current position, which may span two bytes
     fn extract(&mut self, bit_count: u3) -> Result<u8, CodeEofError> {
-        if self.is_eof() {
+        if self.is_end() {
             return Err(CodeEofError);
         }
-        let byte = self.as_ref()[self.byte_pos as usize];
-        let mut mask = 0x00u8;
-        let mut cnt = bit_count.as_u8();
-        while cnt > 0 {
-            mask <<= 1;
-            mask |= 0x01;
-            cnt -= 1;
-        }
-        mask <<= self.bit_pos.as_u8();
-        let val = (byte & mask) >> self.bit_pos.as_u8();
+        let word = u16::from_le_bytes(self.bytes_at(self.byte_pos as usize));
+        let mask = (1u16 << bit_count.as_u8()) - 1;
+        let val = ((word >> self.bit_pos.as_u8()) & mask) as u8;
         self.inc_bits(bit_count).map(|_| val)
     }
 
@@ -160,6 +174,19 @@ where
     Self: 'a,
 {
     fn as_mut(&mut self) -> &mut [u8] { self.bytecode.as_mut() }
+
+    /// Writes `bit_count` bits of `data` at the current position, which may span two bytes
+    fn insert(&mut self, bit_count: u3, data: u8) -> Result<(), WriteError> {
+        let word = (data as u16) << self.bit_pos.as_u8();
+        let [lo, hi] = word.to_le_bytes();
+        let pos = self.byte_pos as usize;
+        let code = self.as_mut();
+        code[pos] |= lo;
+        if hi != 0 {
+            *code.get_mut(pos + 1).ok_or(CodeEofError)? |= hi;
+        }
+        self.inc_bits(bit_count).map_err(WriteError::from)
+    }
 }
 
 impl<'a, T, D> Cursor<'a, T, D>
@@ -197,7 +224,7 @@ where
 
     #[inline]
     fn seek(&mut self, byte_pos: u16) -> Result<u16, CodeEofError> {
-        if byte_pos as usize >= self.as_ref().len() {
+        if byte_pos == u16::MAX {
             return Err(CodeEofError);
         }
         let old_pos = self.byte_pos;
@@ -206,14 +233,14 @@ where
     }
 
     fn peek_u8(&self) -> Result<u8, CodeEofError> {
-        if self.is_eof() {
+        if self.is_end() {
             return Err(CodeEofError);
         }
-        Ok(self.as_ref()[self.byte_pos as usize])
+        Ok(self.byte_at(self.byte_pos as usize))
     }
 
     fn read_bool(&mut self) -> Result<bool, CodeEofError> {
-        if self.is_eof() {
+        if self.is_end() {
             return Err(CodeEofError);
         }
         let byte = self.extract(u3::with(1))?;
@@ -249,50 +276,47 @@ where
     }
 
     fn read_u8(&mut self) -> Result<u8, CodeEofError> {
-        if self.is_eof() {
+        if self.is_end() {
             return Err(CodeEofError);
         }
-        let byte = self.as_ref()[self.byte_pos as usize];
+        let byte = self.byte_at(self.byte_pos as usize);
         self.inc_bytes(1).map(|_| byte)
     }
 
     fn read_i8(&mut self) -> Result<i8, CodeEofError> {
-        if self.is_eof() {
+        if self.is_end() {
             return Err(CodeEofError);
         }
-        let byte = self.as_ref()[self.byte_pos as usize] as i8;
+        let byte = self.byte_at(self.byte_pos as usize) as i8;
         self.inc_bytes(1).map(|_| byte)
     }
 
     fn read_u16(&mut self) -> Result<u16, CodeEofError> {
-        if self.is_eof() {
+        if self.is_end() {
             return Err(CodeEofError);
         }
         let pos = self.byte_pos as usize;
-        let mut buf = [0u8; 2];
-        buf.copy_from_slice(&self.as_ref()[pos..pos + 2]);
+        let buf = self.bytes_at::<2>(pos);
         let word = u16::from_le_bytes(buf);
         self.inc_bytes(2).map(|_| word)
     }
 
     fn read_i16(&mut self) -> Result<i16, CodeEofError> {
-        if self.is_eof() {
+        if self.is_end() {
             return Err(CodeEofError);
         }
         let pos = self.byte_pos as usize;
-        let mut buf = [0u8; 2];
-        buf.copy_from_slice(&self.as_ref()[pos..pos + 2]);
+        let buf = self.bytes_at::<2>(pos);
         let word = i16::from_le_bytes(buf);
         self.inc_bytes(2).map(|_| word)
     }
 
     fn read_u24(&mut self) -> Result<u24, CodeEofError> {
-        if self.is_eof() {
+        if self.is_end() {
             return Err(CodeEofError);
         }
         let pos = self.byte_pos as usize;
-        let mut buf = [0u8; 3];
-        buf.copy_from_slice(&self.as_ref()[pos..pos + 3]);
+        let buf = self.bytes_at::<3>(pos);
         let word = u24::from_le_bytes(buf);
         self.inc_bytes(3).map(|_| word)
     }
@@ -329,59 +353,35 @@ where
     Self: 'a,
 {
     fn write_bool(&mut self, data: bool) -> Result<(), WriteError> {
-        let data = if data { 1u8 } else { 0u8 } << self.bit_pos.as_u8();
-        let pos = self.byte_pos as usize;
-        self.as_mut()[pos] |= data;
-        self.inc_bits(u3::with(1)).map_err(WriteError::from)
+        self.insert(u3::with(1), data as u8)
     }
 
     fn write_u1(&mut self, data: impl Into<u1>) -> Result<(), WriteError> {
-        let data = data.into().as_u8() << self.bit_pos.as_u8();
-        let pos = self.byte_pos as usize;
-        self.as_mut()[pos] |= data;
-        self.inc_bits(u3::with(1)).map_err(WriteError::from)
+        self.insert(u3::with(1), data.into().as_u8())
     }
 
     fn write_u2(&mut self, data: impl Into<u2>) -> Result<(), WriteError> {
-        let data = data.into().as_u8() << self.bit_pos.as_u8();
-        let pos = self.byte_pos as usize;
-        self.as_mut()[pos] |= data;
-        self.inc_bits(u3::with(2)).map_err(WriteError::from)
+        self.insert(u3::with(2), data.into().as_u8())
     }
 
     fn write_u3(&mut self, data: impl Into<u3>) -> Result<(), WriteError> {
-        let data = data.into().as_u8() << self.bit_pos.as_u8();
-        let pos = self.byte_pos as usize;
-        self.as_mut()[pos] |= data;
-        self.inc_bits(u3::with(3)).map_err(WriteError::from)
+        self.insert(u3::with(3), data.into().as_u8())
     }
 
     fn write_u4(&mut self, data: impl Into<u4>) -> Result<(), WriteError> {
-        let data = data.into().as_u8() << self.bit_pos.as_u8();
-        let pos = self.byte_pos as usize;
-        self.as_mut()[pos] |= data;
-        self.inc_bits(u3::with(4)).map_err(WriteError::from)
+        self.insert(u3::with(4), data.into().as_u8())
     }
 
     fn write_u5(&mut self, data: impl Into<u5>) -> Result<(), WriteError> {
-        let data = data.into().as_u8() << self.bit_pos.as_u8();
-        let pos = self.byte_pos as usize;
-        self.as_mut()[pos] |= data;
-        self.inc_bits(u3::with(5)).map_err(WriteError::from)
+        self.insert(u3::with(5), data.into().as_u8())
     }
 
     fn write_u6(&mut self, data: impl Into<u6>) -> Result<(), WriteError> {
-        let data = data.into().as_u8() << self.bit_pos.as_u8();
-        let pos = self.byte_pos as usize;
-        self.as_mut()[pos] |= data;
-        self.inc_bits(u3::with(6)).map_err(WriteError::from)
+        self.insert(u3::with(6), data.into().as_u8())
     }
 
     fn write_u7(&mut self, data: impl Into<u7>) -> Result<(), WriteError> {
-        let data = data.into().as_u8() << self.bit_pos.as_u8();
-        let pos = self.byte_pos as usize;
-        self.as_mut()[pos] |= data;
-        self.inc_bits(u3::with(7)).map_err(WriteError::from)
+        self.insert(u3::with(7), data.into().as_u8())
     }
 
     fn write_u8(&mut self, data: impl Into<u8>) -> Result<(), WriteError> {
@@ -27,6 +27,7 @@ use crate::isa::{BytecodeError, ExecStep, InstructionSet};
 use crate::program::segs::IsaSeg;
 use crate::program::{CodeEofError, LibSeg, LibSegOverflow, SegmentError};
 use crate::reg::CoreRegs;
+use crate::vm::{Env, Halt, NoTrace, Tracer};
 
 const LIB_ID_MIDSTATE: [u8; 32] = [
     156, 224, 228, 230, 124, 17, 108, 57, 56, 179, 202, 242, 195, 15, 80, 137, 211, 243, 147, 108,
@@ -268,25 +269,31 @@ impl Lib {
         })
     }
 
-    /// Assembles library from the provided instructions by encoding them into bytecode
+    /// Assembles library from the provided instructions by encoding them into bytecode.
+    ///
+    /// The library declares [`InstructionSet::base_isa_ids`] together with the optional ISA
+    /// extensions used by its instructions (see [`InstructionSet::instr_isa_ids`]).
     pub fn assemble<Isa>(code: &[Isa]) -> Result<Lib, AssemblerError>
     where
         Isa: InstructionSet,
     {
+        let mut isa_ids = Isa::base_isa_ids();
+        isa_ids.extend(code.iter().flat_map(Isa::instr_isa_ids));
+
         let call_sites = code.iter().filter_map(|instr| instr.call_site());
         let libs_segment = LibSeg::with(call_sites)?;
 
-        let mut code_segment = ByteStr::default();
-        let mut writer = Cursor::<_, ByteStr>::new(&mut code_segment.bytes[..], &libs_segment);
+        let mut code_segment = vec![0u8; u16::MAX as usize];
+        let mut writer = Cursor::<_, ByteStr>::new(&mut code_segment[..], &libs_segment);
         for instr in code.iter() {
             instr.write(&mut writer)?;
         }
         let pos = writer.pos();
         let data_segment = writer.into_data_segment();
-        code_segment.adjust_len(pos);
+        let code_segment = ByteStr::with(&code_segment[..pos as usize]);
 
         Ok(Lib {
-            isae: IsaSeg::from_iter(Isa::isa_ids())
+            isae: IsaSeg::from_iter(isa_ids)
                 .expect("ISA instruction set contains incorrect ISAE ids"),
             libs: libs_segment,
             code: code_segment,
@@ -334,53 +341,207 @@ impl Lib {
 
     /// Executes library code starting at entrypoint
     ///
+    /// Reaching the end of the code segment or failure to decode an instruction sets `st0` to
+    /// `false`.
+    ///
+    /// # Returns
+    ///
+    /// Location for the external code jump, or - if the execution has halted - offset of the last
+    /// executed instruction together with the reason for the halt.
+    #[inline]
+    pub fn exec<Isa>(
+        &self,
+        entrypoint: u16,
+        registers: &mut CoreRegs,
+    ) -> Result<LibSite, (u16, Halt)>
+    where
+        Isa: InstructionSet,
+    {
+        self.exec_traced::<Isa>(entrypoint, registers, &mut NoTrace)
+    }
+
+    /// Executes library code starting at entrypoint, reporting execution events to the provided
+    /// tracer. The halt itself is not reported, since it is the responsibility of the caller.
+    ///
+    /// Reaching the end of the code segment or failure to decode an instruction sets `st0` to
+    /// `false`. Since no host functions are available outside of [`crate::Vm`], `hcall`
+    /// instructions also set `st0` to `false`.
+    /// Instructions are priced with the default [`crate::vm::Complexity`] cost model.
+    ///
     /// # Returns
     ///
-    /// Location for the external code jump, if any
-    pub fn exec<Isa>(&self, entrypoint: u16, registers: &mut CoreRegs) -> Option<LibSite>
+    /// Location for the external code jump, or - if the execution has halted - offset of the last
+    /// executed instruction together with the reason for the halt.
+    pub fn exec_traced<Isa>(
+        &self,
+        entrypoint: u16,
+        registers: &mut CoreRegs,
+        tracer: &mut impl Tracer,
+    ) -> Result<LibSite, (u16, Halt)>
     where
         Isa: InstructionSet,
     {
-        let mut cursor = Cursor::with(&self.code.bytes[..], &self.data, &self.libs);
-        let lib_hash = self.id();
-        cursor.seek(entrypoint).ok()?;
+        self.exec_as::<Isa>(self.id(), entrypoint, registers, &mut Env::default(), tracer)
+    }
 
-        while !cursor.is_eof() {
-            let pos = cursor.pos();
+    /// Executes library code starting at entrypoint using already known library id, which allows
+    /// to avoid re-hashing the library each time the execution enters it, and provided execution
+    /// environment.
+    pub(crate) fn exec_as<Isa>(
+        &self,
+        lib_hash: LibId,
+        entrypoint: u16,
+        registers: &mut CoreRegs,
+        env: &mut Env<Isa>,
+        tracer: &mut impl Tracer,
+    ) -> Result<LibSite, (u16, Halt)>
+    where
+        Isa: InstructionSet,
+    {
+        let mut cursor = Cursor::with(&self.code, &self.data, &self.libs);
+        let mut pos = entrypoint;
+        loop {
+            match self.exec_instr::<Isa>(&mut cursor, lib_hash, pos, registers, env, tracer)? {
+                next if next.lib == lib_hash => pos = next.pos,
+                next => return Ok(next),
+            }
+        }
+    }
 
-            let instr = Isa::read(&mut cursor).ok()?;
-            let next = instr.exec(registers, LibSite::with(pos, lib_hash));
+    /// Executes a single instruction located at `pos`, reporting execution events to the provided
+    /// tracer. The halt itself is not reported, since it is the responsibility of the caller.
+    ///
+    /// # Returns
+    ///
+    /// Location of the next instruction to execute, either in this or in an external library. If
+    /// the execution has halted, offset of the executed instruction together with the reason for
+    /// the halt.
+    pub fn step<Isa>(
+        &self,
+        pos: u16,
+        registers: &mut CoreRegs,
+        tracer: &mut impl Tracer,
+    ) -> Result<LibSite, (u16, Halt)>
+    where
+        Isa: InstructionSet,
+    {
+        self.step_as::<Isa>(self.id(), pos, registers, &mut Env::default(), tracer)
+    }
 
-            #[cfg(all(debug_assertions, feature = "std"))]
-            eprint!("\n@{:06}> {:48}; st0={}", pos, instr, registers.st0);
+    /// Executes a single instruction located at `pos` using already known library id and
+    /// provided execution environment.
+    pub(crate) fn step_as<Isa>(
+        &self,
+        lib_hash: LibId,
+        pos: u16,
+        registers: &mut CoreRegs,
+        env: &mut Env<Isa>,
+        tracer: &mut impl Tracer,
+    ) -> Result<LibSite, (u16, Halt)>
+    where
+        Isa: InstructionSet,
+    {
+        let mut cursor = Cursor::with(&self.code, &self.data, &self.libs);
+        self.exec_instr::<Isa>(&mut cursor, lib_hash, pos, registers, env, tracer)
+    }
 
-            if !registers.acc_complexity(instr) {
-                #[cfg(all(debug_assertions, feature = "std"))]
-                eprintln!();
-                return None;
-            }
-            match next {
-                ExecStep::Stop => {
-                    #[cfg(all(debug_assertions, feature = "std"))]
-                    eprintln!();
-                    return None;
-                }
-                ExecStep::Next => continue,
-                ExecStep::Jump(pos) => {
-                    #[cfg(all(debug_assertions, feature = "std"))]
-                    eprint!(" -> {}", pos);
-                    cursor.seek(pos).ok()?;
-                }
-                ExecStep::Call(site) => {
-                    #[cfg(all(debug_assertions, feature = "std"))]
-                    eprint!(" -> {}", site);
-                    return Some(site);
-                }
+    /// Decodes all instructions in the code segment, stopping at the first instruction which can't
+    /// be decoded.
+    ///
+    /// # Returns
+    ///
+    /// Decoded instructions and their offsets; the offsets list contains an extra last item with
+    /// the offset following the last decoded instruction.
+    pub(crate) fn decode<Isa>(&self) -> (Vec<Isa>, Vec<u16>)
+    where
+        Isa: InstructionSet,
+    {
+        let mut cursor = Cursor::with(&self.code, &self.data, &self.libs);
+        let mut code = vec![];
+        let mut offsets = vec![0u16];
+        let mut pos = 0u16;
+        while pos < self.code.len() {
+            let instr = match Isa::read(&mut cursor) {
+                Ok(instr) => instr,
+                Err(_) => break,
+            };
+            let next = cursor.pos();
+            if next <= pos {
+                break;
             }
+            code.push(instr);
+            offsets.push(next);
+            pos = next;
         }
+        (code, offsets)
+    }
+
+    fn exec_instr<Isa>(
+        &self,
+        cursor: &mut Cursor<&ByteStr, &ByteStr>,
+        lib_hash: LibId,
+        pos: u16,
+        registers: &mut CoreRegs,
+        env: &mut Env<Isa>,
+        tracer: &mut impl Tracer,
+    ) -> Result<LibSite, (u16, Halt)>
+    where
+        Isa: InstructionSet,
+    {
+        cursor.seek(pos).map_err(|_| {
+            registers.st0 = false;
+            (pos, Halt::CodeEnd)
+        })?;
+        let site = LibSite::with(pos, lib_hash);
+
+        let instr = Isa::read(cursor).map_err(|err| {
+            registers.st0 = false;
+            (pos, Halt::CodeEof(err))
+        })?;
+        exec_decoded(&instr, site, cursor.pos(), registers, env, tracer)
+    }
+}
 
-        None
+/// Executes already decoded instruction located at `site`, which is followed by the next
+/// instruction at `next_pos`, reporting execution events to the provided tracer.
+pub(crate) fn exec_decoded<Isa>(
+    instr: &Isa,
+    site: LibSite,
+    next_pos: u16,
+    registers: &mut CoreRegs,
+    env: &mut Env<Isa>,
+    tracer: &mut impl Tracer,
+) -> Result<LibSite, (u16, Halt)>
+where
+    Isa: InstructionSet,
+{
+    tracer.fetch(site, instr);
+
+    let depth = registers.cp0;
+    let cost = env.cost.cost(instr, registers);
+    let next = instr.exec(registers, site);
+    if let ExecStep::Host(id) = next {
+        env.hosts.call(id, registers);
+    }
+    let within_limit = registers.acc_cost(cost);
+    tracer.exec(site, instr, registers);
+
+    if !within_limit {
+        return Err((site.pos, Halt::ComplexityLimit));
+    }
+    let to = match next {
+        ExecStep::Stop => return Err((site.pos, Halt::Stop)),
+        ExecStep::Halt(halt) => return Err((site.pos, halt)),
+        ExecStep::Next | ExecStep::Host(_) => return Ok(LibSite::with(next_pos, site.lib)),
+        ExecStep::Jump(offset) => LibSite::with(offset, site.lib),
+        ExecStep::Call(to) => to,
+    };
+    match registers.cp0.cmp(&depth) {
+        Ordering::Greater => tracer.call(site, to),
+        Ordering::Less => tracer.ret(site, to),
+        Ordering::Equal => tracer.jump(site, to),
     }
+    Ok(to)
 }
 
 /// Location within a library
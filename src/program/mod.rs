@@ -11,16 +11,24 @@
 
 //! Business logic and data structures for working with AluVM code libraries
 
+mod compiled;
 pub mod constants;
 mod cursor;
 mod lib;
 #[allow(clippy::module_inception)]
 mod program;
+mod resolver;
 mod rw;
 mod segs;
+#[cfg(feature = "std")]
+mod store;
 
+pub use compiled::{CompiledLib, CompiledProgram};
 pub use cursor::Cursor;
 pub use lib::{AssemblerError, Lib, LibId, LibIdError, LibIdTag, LibSite};
-pub use program::{LibError, Program};
+pub use program::{LibError, LinkError, Program};
+pub use resolver::{CachedResolver, LibResolver, MemResolver, ResolveError};
 pub use rw::{CodeEofError, Read, Write, WriteError};
 pub use segs::{IsaSeg, IsaSegError, LibSeg, LibSegOverflow, SegmentError};
+#[cfg(feature = "std")]
+pub use store::{LibStore, StoreError};
@@ -12,13 +12,14 @@
 use alloc::borrow::ToOwned;
 use alloc::collections::BTreeMap;
 use alloc::string::String;
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 
 use super::constants::LIBS_MAX_TOTAL;
 use super::*;
 use crate::isa::InstructionSet;
 
-/// Errors returned by [`Program::add_lib`] method
+/// Errors returned by [`Program::add_lib`] and [`Program::set_entrypoint`] methods
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
 #[cfg_attr(feature = "std", derive(Error))]
 #[display(doc_comments)]
@@ -29,6 +30,33 @@ pub enum LibError {
     /// Attempt to add library when maximum possible number of libraries is already present in
     /// the VM
     TooManyLibs,
+
+    /// entry point library {0} is not a part of the program
+    UnknownEntrypoint(LibId),
+}
+
+/// Link errors detected by [`Program::validate`] method
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[cfg_attr(feature = "std", derive(Error))]
+#[display(doc_comments)]
+pub enum LinkError {
+    /// program entry point {0} is outside of the program code
+    Entrypoint(LibSite),
+
+    /// library {0} depends on library {1} which is not a part of the program
+    MissingLib(LibId, LibId),
+
+    /// instruction at {0} can't be decoded
+    Undecodable(LibSite),
+
+    /// instruction at {0} calls library {1}, which is not a part of the program
+    CallMissingLib(LibSite, LibId),
+
+    /// instruction at {0} calls {1}, which is outside of the callee code segment
+    CallOutOfCode(LibSite, LibSite),
+
+    /// instruction at {0} jumps to offset {1}, which is not an instruction boundary
+    JumpMisaligned(LibSite, u16),
 }
 
 /// An AluVM program executable by a virtual machine.
@@ -76,11 +104,17 @@ where
         let mut runtime = Self::empty_unchecked();
         let id = lib.id();
         runtime.add_lib(lib).expect("adding single library to lib segment overflows");
-        runtime.set_entrypoint(LibSite::with(0, id));
+        runtime.set_entrypoint(LibSite::with(0, id)).expect("library was just added");
         runtime
     }
 
     /// Constructs new virtual machine runtime from a set of libraries with a given entry point.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by [`Program::add_lib`] for each of the libraries, or
+    /// [`LibError::UnknownEntrypoint`] if the entry point library is not among the provided
+    /// libraries.
     pub fn with(
         libs: impl IntoIterator<Item = Lib>,
         entrypoint: LibSite,
@@ -89,13 +123,17 @@ where
         for lib in libs {
             runtime.add_lib(lib)?;
         }
-        runtime.set_entrypoint(entrypoint);
+        runtime.set_entrypoint(entrypoint)?;
         Ok(runtime)
     }
 
     /// Returns reference to a specific library, if it is part of the current program.
     pub fn lib(&self, id: LibId) -> Option<&Lib> { self.libs.get(&id) }
 
+    /// Returns iterator over all libraries of the program together with their ids.
+    #[inline]
+    pub fn libs(&self) -> impl Iterator<Item = (&LibId, &Lib)> { self.libs.iter() }
+
     /// Adds Alu bytecode library to the virtual machine runtime.
     ///
     /// # Errors
@@ -122,13 +160,77 @@ where
         Ok(self.libs.insert(lib.id(), lib).is_none())
     }
 
+    /// Performs link-time checks of all program libraries, verifying that
+    /// - the entry point and all libraries referenced by the library segments and called by the
+    ///   code are present in the program;
+    /// - the code segments of all libraries can be decoded;
+    /// - `call` and `exec` instructions target offsets inside the code segment of the callee;
+    /// - `jmp`, `jif` and `routine` instructions target instruction boundaries.
+    ///
+    /// # Errors
+    ///
+    /// List of all detected link errors, grouped by the library in which they were found.
+    pub fn validate(&self) -> Result<(), Vec<LinkError>> {
+        let mut errors = vec![];
+        match self.libs.get(&self.entrypoint.lib) {
+            Some(lib) if self.entrypoint.pos < lib.code.len() => {}
+            _ => errors.push(LinkError::Entrypoint(self.entrypoint)),
+        }
+        for (id, lib) in &self.libs {
+            for dep in &lib.libs {
+                if !self.libs.contains_key(dep) {
+                    errors.push(LinkError::MissingLib(*id, *dep));
+                }
+            }
+
+            let (code, offsets) = lib.decode::<Isa>();
+            let boundaries = &offsets[..code.len()];
+            for (instr, pos) in code.iter().zip(boundaries) {
+                let site = LibSite::with(*pos, *id);
+                if let Some(target) = instr.jump_target() {
+                    if boundaries.binary_search(&target).is_err() {
+                        errors.push(LinkError::JumpMisaligned(site, target));
+                    }
+                }
+                if let Some(target) = instr.call_site() {
+                    match self.libs.get(&target.lib) {
+                        None => errors.push(LinkError::CallMissingLib(site, target.lib)),
+                        Some(callee) if target.pos >= callee.code.len() => {
+                            errors.push(LinkError::CallOutOfCode(site, target))
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+            let end = offsets[code.len()];
+            if end < lib.code.len() {
+                errors.push(LinkError::Undecodable(LibSite::with(end, *id)));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Returns number of libraries used by the program.
     pub fn libs_count(&self) -> u16 { self.libs.len() as u16 }
 
     /// Returns program entry point.
     pub fn entrypoint(&self) -> LibSite { self.entrypoint }
 
-    // TODO: Return error if the library is not known
     /// Sets new entry point value (used when calling [`crate::Vm::run`])
-    pub fn set_entrypoint(&mut self, entrypoint: LibSite) { self.entrypoint = entrypoint; }
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LibError::UnknownEntrypoint`] and leaves entry point unchanged if the library of
+    /// the new entry point is not a part of the program.
+    pub fn set_entrypoint(&mut self, entrypoint: LibSite) -> Result<(), LibError> {
+        if !self.libs.contains_key(&entrypoint.lib) {
+            return Err(LibError::UnknownEntrypoint(entrypoint.lib));
+        }
+        self.entrypoint = entrypoint;
+        Ok(())
+    }
 }
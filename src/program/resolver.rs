@@ -0,0 +1,158 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! On-demand resolution of libraries which are not a part of a program
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FromIterator;
+
+use super::{Lib, LibId};
+
+/// Errors happening during library resolution
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[cfg_attr(feature = "std", derive(Error))]
+#[display(doc_comments)]
+pub enum ResolveError {
+    /// library {0} is not known to the resolver
+    NotFound(LibId),
+
+    /// library {0} was requested, but the resolver has returned library {1}
+    IdMismatch(LibId, LibId),
+}
+
+/// Source of libraries which are not a part of a program, consulted by [`crate::Vm`] when the
+/// execution reaches an unknown library (see [`crate::Vm::set_resolver`]).
+pub trait LibResolver {
+    /// Returns library with a given id, if it is known to the resolver.
+    ///
+    /// Implementations are not required to verify that the returned library has the requested id:
+    /// this is done by [`CachedResolver`].
+    fn resolve(&mut self, id: LibId) -> Option<Lib>;
+}
+
+impl<R> LibResolver for Box<R>
+where
+    R: LibResolver + ?Sized,
+{
+    #[inline]
+    fn resolve(&mut self, id: LibId) -> Option<Lib> { R::resolve(self, id) }
+}
+
+/// In-memory library resolver
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct MemResolver(BTreeMap<LibId, Lib>);
+
+impl MemResolver {
+    /// Constructs empty in-memory resolver
+    #[inline]
+    pub fn new() -> Self { MemResolver::default() }
+
+    /// Adds library to the resolver.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the library was not known to the resolver before and `false` otherwise.
+    #[inline]
+    pub fn add_lib(&mut self, lib: Lib) -> bool { self.0.insert(lib.id(), lib).is_none() }
+
+    /// Returns number of libraries known to the resolver
+    #[inline]
+    pub fn libs_count(&self) -> usize { self.0.len() }
+}
+
+impl FromIterator<Lib> for MemResolver {
+    fn from_iter<T: IntoIterator<Item = Lib>>(iter: T) -> Self {
+        MemResolver(iter.into_iter().map(|lib| (lib.id(), lib)).collect())
+    }
+}
+
+impl LibResolver for MemResolver {
+    #[inline]
+    fn resolve(&mut self, id: LibId) -> Option<Lib> { self.0.get(&id).cloned() }
+}
+
+/// Caching wrapper around a library resolver, which verifies the ids of the resolved libraries
+/// and keeps them for the subsequent use.
+#[derive(Clone, Default)]
+pub struct CachedResolver<R>
+where
+    R: LibResolver,
+{
+    inner: R,
+    cache: BTreeMap<LibId, Lib>,
+}
+
+impl<R> Debug for CachedResolver<R>
+where
+    R: LibResolver,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedResolver").field("cache", &self.cache.keys()).finish()
+    }
+}
+
+impl<R> CachedResolver<R>
+where
+    R: LibResolver,
+{
+    /// Constructs caching wrapper around a resolver
+    #[inline]
+    pub fn new(inner: R) -> Self { CachedResolver { inner, cache: empty!() } }
+
+    /// Returns library with a given id, resolving it with the inner resolver on the first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResolveError::NotFound`] if the library is not known to the inner resolver and
+    /// [`ResolveError::IdMismatch`] if the inner resolver has returned a library with a different
+    /// id. Failed resolutions are not cached.
+    pub fn lib(&mut self, id: LibId) -> Result<&Lib, ResolveError> {
+        if !self.cache.contains_key(&id) {
+            let lib = self.inner.resolve(id).ok_or(ResolveError::NotFound(id))?;
+            let actual = lib.id();
+            if actual != id {
+                return Err(ResolveError::IdMismatch(id, actual));
+            }
+            self.cache.insert(id, lib);
+        }
+        Ok(&self.cache[&id])
+    }
+
+    /// Returns already resolved library with a given id, without consulting the inner resolver
+    #[inline]
+    pub fn cached(&self, id: LibId) -> Option<&Lib> { self.cache.get(&id) }
+
+    /// Checks whether the library with a given id was already resolved
+    #[inline]
+    pub fn is_cached(&self, id: LibId) -> bool { self.cache.contains_key(&id) }
+
+    /// Returns number of already resolved libraries
+    #[inline]
+    pub fn cached_count(&self) -> usize { self.cache.len() }
+
+    /// Removes all resolved libraries from the cache
+    #[inline]
+    pub fn clear(&mut self) { self.cache.clear() }
+
+    /// Releases the inner resolver
+    #[inline]
+    pub fn into_inner(self) -> R { self.inner }
+}
+
+impl<R> LibResolver for CachedResolver<R>
+where
+    R: LibResolver,
+{
+    #[inline]
+    fn resolve(&mut self, id: LibId) -> Option<Lib> { self.lib(id).ok().cloned() }
+}
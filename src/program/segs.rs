@@ -69,7 +69,7 @@ pub struct IsaSeg(BTreeSet<String>);
 impl IsaSeg {
     /// Returns iterator over unique ISA ids iterated in the deterministic (lexicographic) order
     #[inline]
-    pub fn iter(&self) -> ::alloc::collections::btree_set::Iter<String> { self.0.iter() }
+    pub fn iter(&self) -> ::alloc::collections::btree_set::Iter<'_, String> { self.0.iter() }
 }
 
 impl<'a> IntoIterator for &'a IsaSeg {
@@ -193,7 +193,7 @@ pub struct LibSeg {
 impl LibSeg {
     /// Returns iterator over unique libraries iterated in the deterministic (lexicographic) order
     #[inline]
-    pub fn iter(&self) -> ::alloc::collections::btree_set::Iter<LibId> { self.into_iter() }
+    pub fn iter(&self) -> ::alloc::collections::btree_set::Iter<'_, LibId> { self.into_iter() }
 }
 
 impl<'a> IntoIterator for &'a LibSeg {
@@ -0,0 +1,196 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Content-addressed on-disk storage of the libraries
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use amplify::IoError;
+
+use super::{Lib, LibId, LibResolver};
+use crate::data::encoding::{Decode, DecodeError, Encode};
+
+/// Errors happening during library store operations
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum StoreError {
+    /// library store I/O error ({0})
+    #[from]
+    #[from(io::Error)]
+    Io(IoError),
+
+    /// library {0} is not present in the store
+    NotFound(LibId),
+
+    /// file for library {0} contains library {1}
+    IdMismatch(LibId, LibId),
+
+    /// file for library {0} contains invalid data
+    ///
+    /// details: {1}
+    Decode(LibId, DecodeError),
+}
+
+/// Content-addressed storage of the libraries in a file system directory.
+///
+/// Each library is kept in a separate file named by its [`LibId`] in bech32 form (`alu1…`). Files
+/// with other names are ignored by the store.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LibStore {
+    dir: PathBuf,
+}
+
+impl LibStore {
+    /// Opens library store located in a given directory, creating the directory if it does not
+    /// exist.
+    pub fn open(dir: impl AsRef<Path>) -> Result<LibStore, StoreError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(LibStore { dir })
+    }
+
+    /// Returns directory of the store
+    #[inline]
+    pub fn dir(&self) -> &Path { &self.dir }
+
+    /// Returns path to the file holding library with a given id
+    #[inline]
+    pub fn lib_path(&self, id: LibId) -> PathBuf { self.dir.join(id.to_string()) }
+
+    /// Checks whether the library with a given id is present in the store
+    #[inline]
+    pub fn contains(&self, id: LibId) -> bool { self.lib_path(id).is_file() }
+
+    /// Saves library to the store, replacing existing file for the library, if any.
+    ///
+    /// The library is first written to a temporary file, which is then renamed, so the store never
+    /// contains partially written libraries.
+    ///
+    /// # Returns
+    ///
+    /// Id of the saved library
+    pub fn save(&self, lib: &Lib) -> Result<LibId, StoreError> {
+        let id = lib.id();
+        let path = self.lib_path(id);
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, lib.serialize())?;
+        fs::rename(&tmp, &path)?;
+        Ok(id)
+    }
+
+    /// Loads library from the store, verifying that its id matches the requested one.
+    pub fn load(&self, id: LibId) -> Result<Lib, StoreError> {
+        let data = fs::read(self.lib_path(id)).map_err(|err| match err.kind() {
+            ErrorKind::NotFound => StoreError::NotFound(id),
+            _ => err.into(),
+        })?;
+        let lib = Lib::deserialize(data).map_err(|err| StoreError::Decode(id, err))?;
+        let actual = lib.id();
+        if actual != id {
+            return Err(StoreError::IdMismatch(id, actual));
+        }
+        Ok(lib)
+    }
+
+    /// Removes library from the store.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the library was present in the store and `false` otherwise.
+    pub fn remove(&self, id: LibId) -> Result<bool, StoreError> {
+        match fs::remove_file(self.lib_path(id)) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Lists ids of all libraries present in the store
+    pub fn list(&self) -> Result<BTreeSet<LibId>, StoreError> {
+        let mut ids = bset![];
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            if let Some(id) = entry.file_name().to_str().and_then(|name| name.parse().ok()) {
+                ids.insert(id);
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Resolves transitive dependencies of the provided libraries, as they are listed in the
+    /// library segments.
+    ///
+    /// # Returns
+    ///
+    /// Ids of all libraries reachable from the roots, including the roots themselves.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any of the reachable libraries is not present in the store or can't be loaded.
+    pub fn dependencies(
+        &self,
+        roots: impl IntoIterator<Item = LibId>,
+    ) -> Result<BTreeSet<LibId>, StoreError> {
+        self.reachable(roots, true)
+    }
+
+    /// Removes from the store all libraries which are not reachable from the provided roots.
+    /// Roots and dependencies missing from the store are ignored.
+    ///
+    /// # Returns
+    ///
+    /// Ids of the removed libraries.
+    pub fn gc(
+        &self,
+        roots: impl IntoIterator<Item = LibId>,
+    ) -> Result<BTreeSet<LibId>, StoreError> {
+        let reachable = self.reachable(roots, false)?;
+        let mut removed = bset![];
+        for id in self.list()? {
+            if !reachable.contains(&id) && self.remove(id)? {
+                removed.insert(id);
+            }
+        }
+        Ok(removed)
+    }
+
+    fn reachable(
+        &self,
+        roots: impl IntoIterator<Item = LibId>,
+        strict: bool,
+    ) -> Result<BTreeSet<LibId>, StoreError> {
+        let mut reachable = bset![];
+        let mut queue = roots.into_iter().collect::<Vec<_>>();
+        while let Some(id) = queue.pop() {
+            if !reachable.insert(id) {
+                continue;
+            }
+            let lib = match self.load(id) {
+                Ok(lib) => lib,
+                Err(StoreError::NotFound(_)) if !strict => continue,
+                Err(err) => return Err(err),
+            };
+            queue.extend(lib.libs.iter().filter(|dep| !reachable.contains(*dep)));
+        }
+        Ok(reachable)
+    }
+}
+
+impl LibResolver for LibStore {
+    #[inline]
+    fn resolve(&mut self, id: LibId) -> Option<Lib> { self.load(id).ok() }
+}
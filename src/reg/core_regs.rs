@@ -14,20 +14,85 @@ use alloc::string::ToString;
 use alloc::vec::Vec;
 use core::fmt::{self, Debug, Formatter};
 
-use amplify::num::apfloat::ieee;
-use amplify::num::{u1024, u256, u512};
+use amplify::num::apfloat::{ieee, Float};
+use amplify::num::{u1024, u256, u5, u512};
 use half::bf16;
 
-use super::{Reg32, RegA, RegAFR, RegF, RegR, RegS};
-use crate::data::{ByteStr, MaybeNumber, Number};
+use super::{NumericRegister, Reg32, RegA, RegAFR, RegAll, RegF, RegR, RegS};
+use crate::data::{ByteStr, MaybeNumber, Number, Posit512};
 use crate::isa::InstructionSet;
 use crate::program::LibSite;
+use crate::vm::Halt;
 
 /// Maximal size of call stack.
 ///
 /// Equals to 2^16 (limited by `cy0` and `cp0` bit size)
 pub const CALL_STACK_SIZE: usize = 1 << 16;
 
+/// Numeric registers in the order used for the register state serialization
+pub(crate) const NUMERIC_REGS: [RegAFR; 24] = [
+    RegAFR::A(RegA::A8),
+    RegAFR::A(RegA::A16),
+    RegAFR::A(RegA::A32),
+    RegAFR::A(RegA::A64),
+    RegAFR::A(RegA::A128),
+    RegAFR::A(RegA::A256),
+    RegAFR::A(RegA::A512),
+    RegAFR::A(RegA::A1024),
+    RegAFR::F(RegF::F16B),
+    RegAFR::F(RegF::F16),
+    RegAFR::F(RegF::F32),
+    RegAFR::F(RegF::F64),
+    RegAFR::F(RegF::F80),
+    RegAFR::F(RegF::F128),
+    RegAFR::F(RegF::F256),
+    RegAFR::F(RegF::F512),
+    RegAFR::R(RegR::R128),
+    RegAFR::R(RegR::R160),
+    RegAFR::R(RegR::R256),
+    RegAFR::R(RegR::R512),
+    RegAFR::R(RegR::R1024),
+    RegAFR::R(RegR::R2048),
+    RegAFR::R(RegR::R4096),
+    RegAFR::R(RegR::R8192),
+];
+
+/// Register which value differs between two register states, as reported by [`CoreRegs::diff`]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+pub enum RegChange {
+    /// Register from `A`, `F` or `R` families, like `a64[3]`
+    #[display("{0}{1}")]
+    Num(RegAFR, Reg32),
+
+    /// String register, like `s16[2]`
+    #[display(inner)]
+    Str(RegS),
+
+    /// Status register `st0`
+    #[display("st0")]
+    St0,
+
+    /// Jump counter `cy0`
+    #[display("cy0")]
+    Cy0,
+
+    /// Complexity accumulator `ca0`
+    #[display("ca0")]
+    Ca0,
+
+    /// Complexity limit `cl0`
+    #[display("cl0")]
+    Cl0,
+
+    /// Content of the call stack `cs0`
+    #[display("cs0")]
+    Cs0,
+
+    /// Call stack top pointer `cp0`
+    #[display("cp0")]
+    Cp0,
+}
+
 /// Structure keeping state of all registers in a single microprosessor/VM core
 #[derive(Clone)]
 pub struct CoreRegs {
@@ -49,8 +114,7 @@ pub struct CoreRegs {
     pub(crate) f80: [Option<ieee::X87DoubleExtended>; 32],
     pub(crate) f128: [Option<ieee::Quad>; 32],
     pub(crate) f256: [Option<ieee::Oct>; 32],
-    // TODO(#5) Implement tapered floating point type
-    pub(crate) f512: [Option<u512>; 32],
+    pub(crate) f512: [Option<Posit512>; 32],
 
     // Non-arithmetic registers:
     pub(crate) r128: [Option<[u8; 16]>; 32],
@@ -71,7 +135,7 @@ pub struct CoreRegs {
 
     /// Counts number of jumps (possible cycles). The number of jumps is limited by 2^16 per
     /// script.
-    cy0: u16,
+    pub(crate) cy0: u16,
 
     /// Complexity accumulator / counter.
     ///
@@ -82,24 +146,27 @@ pub struct CoreRegs {
     ///
     /// - [`CoreRegs::cy0`] register
     /// - [`CoreRegs::cl0`] register
-    ca0: u64,
+    pub(crate) ca0: u64,
 
     /// Complexity limit
     ///
     /// If this register has a value set, once [`CoreRegs::ca0`] will reach this value the VM will
     /// stop program execution setting `st0` to `false`.
-    cl0: Option<u64>,
+    pub(crate) cl0: Option<u64>,
 
-    /// Call stack
+    /// Call stack.
+    ///
+    /// The stack grows on demand and always contains exactly `cp0` items, up to the
+    /// [`CALL_STACK_SIZE`] limit.
     ///
     /// # See also
     ///
     /// - [`CALL_STACK_SIZE`] constant
     /// - [`CoreRegs::cp0`] register
-    cs0: Vec<LibSite>,
+    pub(crate) cs0: Vec<LibSite>,
 
     /// Defines "top" of the call stack
-    cp0: u16,
+    pub(crate) cp0: u16,
 }
 
 impl Default for CoreRegs {
@@ -139,7 +206,7 @@ impl Default for CoreRegs {
             cy0: 0,
             ca0: 0,
             cl0: None,
-            cs0: vec![LibSite::default(); CALL_STACK_SIZE],
+            cs0: vec![],
             cp0: 0,
         }
     }
@@ -153,44 +220,92 @@ impl CoreRegs {
     #[inline]
     pub fn new() -> CoreRegs { CoreRegs::default() }
 
-    pub(crate) fn jmp(&mut self) -> Result<(), ()> {
-        self.cy0
-            .checked_add(1)
-            .map(|cy| self.cy0 = cy)
-            .ok_or_else(|| {
-                self.st0 = false;
-            })
-            .map(|_| ())
+    /// Initializes register state like [`CoreRegs::new`], setting complexity limit register `cl0`
+    /// to the provided value.
+    #[inline]
+    pub fn with_complexity_limit(limit: u64) -> CoreRegs {
+        CoreRegs { cl0: Some(limit), ..default!() }
     }
 
-    pub(crate) fn call(&mut self, site: LibSite) -> Result<(), ()> {
-        self.cy0
+    /// Returns registers to the initial state, like [`CoreRegs::new`], but preserving the
+    /// complexity limit `cl0`: sets `st0` to `true`, counters to zero, call stack to empty and the
+    /// rest of registers to `None` value.
+    ///
+    /// Unlike constructing new registers, does not allocate memory.
+    #[inline]
+    pub fn reset(&mut self) { self.reset_keeping(&[]) }
+
+    /// Returns registers to the initial state, like [`CoreRegs::reset`], leaving values of the
+    /// registers from the provided families intact.
+    pub fn reset_keeping(&mut self, keep: &[RegAll]) {
+        let families = NUMERIC_REGS.iter().map(RegAll::from).chain(core::iter::once(RegAll::S));
+        for family in families.filter(|family| !keep.contains(family)) {
+            self.clear_family(family);
+        }
+        self.st0 = true;
+        self.cy0 = 0;
+        self.ca0 = 0;
+        self.cs0.clear();
+        self.cp0 = 0;
+    }
+
+    /// Sets all registers of a given family to `None`
+    pub fn clear_family(&mut self, family: impl Into<RegAll>) {
+        fn clear<T>(regs: &mut [Option<T>]) { regs.iter_mut().for_each(|reg| *reg = None) }
+        match family.into() {
+            RegAll::A(RegA::A8) => clear(&mut self.a8),
+            RegAll::A(RegA::A16) => clear(&mut self.a16),
+            RegAll::A(RegA::A32) => clear(&mut self.a32),
+            RegAll::A(RegA::A64) => clear(&mut self.a64),
+            RegAll::A(RegA::A128) => clear(&mut self.a128),
+            RegAll::A(RegA::A256) => clear(&mut self.a256),
+            RegAll::A(RegA::A512) => clear(&mut self.a512),
+            RegAll::A(RegA::A1024) => clear(&mut self.a1024[..]),
+            RegAll::F(RegF::F16B) => clear(&mut self.f16b),
+            RegAll::F(RegF::F16) => clear(&mut self.f16),
+            RegAll::F(RegF::F32) => clear(&mut self.f32),
+            RegAll::F(RegF::F64) => clear(&mut self.f64),
+            RegAll::F(RegF::F80) => clear(&mut self.f80),
+            RegAll::F(RegF::F128) => clear(&mut self.f128),
+            RegAll::F(RegF::F256) => clear(&mut self.f256),
+            RegAll::F(RegF::F512) => clear(&mut self.f512),
+            RegAll::R(RegR::R128) => clear(&mut self.r128),
+            RegAll::R(RegR::R160) => clear(&mut self.r160),
+            RegAll::R(RegR::R256) => clear(&mut self.r256),
+            RegAll::R(RegR::R512) => clear(&mut self.r512),
+            RegAll::R(RegR::R1024) => clear(&mut self.r1024[..]),
+            RegAll::R(RegR::R2048) => clear(&mut self.r2048[..]),
+            RegAll::R(RegR::R4096) => clear(&mut self.r4096[..]),
+            RegAll::R(RegR::R8192) => clear(&mut self.r8192[..]),
+            RegAll::S => clear(&mut self.s16[..]),
+        }
+    }
+
+    pub(crate) fn jmp(&mut self) -> Result<(), Halt> {
+        self.cy0.checked_add(1).map(|cy| self.cy0 = cy).ok_or_else(|| {
+            self.st0 = false;
+            Halt::JumpLimit
+        })
+    }
+
+    pub(crate) fn call(&mut self, site: LibSite) -> Result<(), Halt> {
+        self.jmp()?;
+        self.cp0
             .checked_add(1)
-            .map(|cy| self.cy0 = cy)
+            .map(|cp| {
+                self.cs0.push(site);
+                self.cp0 = cp;
+            })
             .ok_or_else(|| {
                 self.st0 = false;
-            })
-            .map(|_| {
-                self.cs0[self.cp0 as usize] = site;
-            })
-            .and_then(|_| {
-                self.cp0
-                    .checked_add(1)
-                    .ok_or_else(|| {
-                        self.st0 = false;
-                    })
-                    .map(|_| ())
+                Halt::CallStackOverflow
             })
     }
 
     pub(crate) fn ret(&mut self) -> Option<LibSite> {
-        if self.cp0 == 0 {
-            None
-        } else {
-            self.cs0[self.cp0 as usize] = LibSite::default();
-            self.cp0 -= 1;
-            Some(self.cs0[self.cp0 as usize])
-        }
+        let site = self.cs0.pop()?;
+        self.cp0 -= 1;
+        Some(site)
     }
 
     /// Retrieves register value
@@ -241,6 +356,66 @@ impl CoreRegs {
         }
     }
 
+    /// Retrieves exact bit representation of the register value, including NaN and negative zero
+    /// float values, which are not reported by [`CoreRegs::get`].
+    pub(crate) fn get_raw(&self, reg: RegAFR, index: Reg32) -> Option<Number> {
+        let idx = index.to_usize();
+        let layout = reg.layout();
+        let bits = match reg {
+            RegAFR::F(RegF::F16B) => self.f16b[idx].map(|v| Number::from(v.to_bits())),
+            RegAFR::F(RegF::F16) => self.f16[idx].map(|v| Number::from(v.to_bits())),
+            RegAFR::F(RegF::F32) => self.f32[idx].map(|v| Number::from(v.to_bits())),
+            RegAFR::F(RegF::F64) => self.f64[idx].map(|v| Number::from(v.to_bits())),
+            RegAFR::F(RegF::F80) => self.f80[idx].map(|v| Number::from(v.to_bits())),
+            RegAFR::F(RegF::F128) => self.f128[idx].map(|v| Number::from(v.to_bits())),
+            RegAFR::F(RegF::F256) => self.f256[idx].map(|v| Number::from(v.to_bits())),
+            RegAFR::F(RegF::F512) => self.f512[idx].map(|v| Number::from(v.to_bits())),
+            reg => return self.get(reg, index).into(),
+        }?;
+        Number::with(&bits[..reg.bytes()], layout)
+    }
+
+    /// Compares register state with some other state, returning list of registers having
+    /// different values.
+    ///
+    /// Float registers are compared bit-wise, so NaN values are equal if they have the same bit
+    /// representation.
+    pub fn diff(&self, other: &CoreRegs) -> Vec<RegChange> {
+        let mut changes = vec![];
+        for reg in NUMERIC_REGS {
+            for index in (0..32u8).map(|i| Reg32::from(u5::with(i))) {
+                if self.get_raw(reg, index) != other.get_raw(reg, index) {
+                    changes.push(RegChange::Num(reg, index));
+                }
+            }
+        }
+        for index in 0..16u8 {
+            let reg = RegS::from(index);
+            if self.get_s(reg) != other.get_s(reg) {
+                changes.push(RegChange::Str(reg));
+            }
+        }
+        if self.st0 != other.st0 {
+            changes.push(RegChange::St0);
+        }
+        if self.cy0 != other.cy0 {
+            changes.push(RegChange::Cy0);
+        }
+        if self.ca0 != other.ca0 {
+            changes.push(RegChange::Ca0);
+        }
+        if self.cl0 != other.cl0 {
+            changes.push(RegChange::Cl0);
+        }
+        if self.cs0 != other.cs0 {
+            changes.push(RegChange::Cs0);
+        }
+        if self.cp0 != other.cp0 {
+            changes.push(RegChange::Cp0);
+        }
+        changes
+    }
+
     /// Returns value from one of `S`-registers
     #[inline]
     pub fn get_s(&self, index: impl Into<RegS>) -> Option<&ByteStr> {
@@ -318,6 +493,17 @@ impl CoreRegs {
         value.is_some()
     }
 
+    /// Assigns the provided value to one of `S`-registers.
+    ///
+    /// Returns `true` if the value was not `None`
+    #[inline]
+    pub fn set_s(&mut self, index: impl Into<RegS>, value: Option<impl Into<ByteStr>>) -> bool {
+        let value = value.map(Into::into);
+        let is_some = value.is_some();
+        self.s16[index.into().as_usize()] = value;
+        is_some
+    }
+
     /// Assigns the provided value to the register bit-wise if the register is not initialized.
     /// Silently discards most significant bits until the value fits register bit size.
     ///
@@ -370,8 +556,21 @@ impl CoreRegs {
     /// `false` if `cl0` register has value and the accumulated complexity has reached or exceeded
     /// this limit
     #[inline]
-    pub fn acc_complexity(&mut self, instr: impl InstructionSet) -> bool {
-        self.ca0 = self.ca0.saturating_add(instr.complexity());
+    pub fn acc_complexity(&mut self, instr: &impl InstructionSet) -> bool {
+        self.acc_cost(instr.complexity())
+    }
+
+    /// Accumulates instruction cost, computed by a [`crate::vm::CostModel`], into `ca0`.
+    ///
+    /// Sets `st0` to `false` if the complexity limit is reached or exceeded. Otherwise, does not
+    /// modify `st0` value.
+    ///
+    /// # Returns
+    ///
+    /// `false` if `cl0` register has value and the accumulated complexity has reached or exceeded
+    /// this limit
+    pub fn acc_cost(&mut self, cost: u64) -> bool {
+        self.ca0 = self.ca0.saturating_add(cost);
         if let Some(limit) = self.cl0 {
             if self.ca0 >= limit {
                 self.st0 = false;
@@ -387,6 +586,34 @@ impl CoreRegs {
     /// Returns vale of `st0` register
     #[inline]
     pub fn status(&self) -> bool { self.st0 }
+
+    /// Returns content of the call stack register `cs0` up to its top defined by `cp0`, i.e.
+    /// locations to which the currently executed routines will return, starting from the
+    /// outermost one.
+    #[inline]
+    pub fn call_stack(&self) -> &[LibSite] { &self.cs0 }
+
+    /// Returns value of `cp0` register, i.e. number of routines which were called and have not
+    /// returned yet
+    #[inline]
+    pub fn call_depth(&self) -> u16 { self.cp0 }
+
+    /// Returns value of `cy0` register, i.e. number of jumps performed so far
+    #[inline]
+    pub fn jumps(&self) -> u16 { self.cy0 }
+
+    /// Returns value of `ca0` register, i.e. complexity accumulated so far
+    #[inline]
+    pub fn complexity(&self) -> u64 { self.ca0 }
+
+    /// Returns value of `cl0` register, i.e. complexity limit, if any
+    #[inline]
+    pub fn complexity_limit(&self) -> Option<u64> { self.cl0 }
+
+    /// Sets value of `cl0` register. Once `ca0` reaches or exceeds the limit, the program execution
+    /// is stopped with `st0` set to `false`. Setting the limit to `None` removes it.
+    #[inline]
+    pub fn set_complexity_limit(&mut self, limit: Option<u64>) { self.cl0 = limit; }
 }
 
 impl Debug for CoreRegs {
@@ -405,8 +632,8 @@ impl Debug for CoreRegs {
         write!(f, "{}cl0{}={}{} ", reg, eq, val, cl)?;
         write!(f, "{}cp0{}={}{} ", reg, eq, val, self.cp0)?;
         write!(f, "\n\t\t{}cs0{}={}", reg, eq, val)?;
-        for p in 0..=self.cp0 {
-            write!(f, "{}\n\t\t   ", self.cs0[p as usize])?;
+        for site in &self.cs0 {
+            write!(f, "{}\n\t\t   ", site)?;
         }
 
         write!(f, "\n{}A-REG:{}\t", sect, reset)?;
@@ -584,7 +811,6 @@ impl Debug for CoreRegs {
         for i in 0..32 {
             if let Some(v) = self.f512[i] {
                 let j = i + 1;
-                let v = Number::from(v);
                 write!(
                     f,
                     "{}f512{}[{}{:02}{}]={}{}{}\n\t\t",
@@ -686,3 +912,55 @@ impl Debug for CoreRegs {
         Ok(())
     }
 }
+
+#[cfg(all(feature = "std", feature = "strict_encoding"))]
+mod _strict_encoding {
+    use std::io::{Read, Write};
+
+    use strict_encoding::{StrictDecode, StrictEncode};
+
+    use super::CoreRegs;
+    use crate::data::encoding::{Decode, Encode};
+
+    impl StrictEncode for CoreRegs {
+        fn strict_encode<E: Write>(&self, e: E) -> Result<usize, strict_encoding::Error> {
+            Encode::serialize(self).strict_encode(e)
+        }
+    }
+
+    impl StrictDecode for CoreRegs {
+        fn strict_decode<D: Read>(d: D) -> Result<Self, strict_encoding::Error> {
+            let data = Vec::<u8>::strict_decode(d)?;
+            <CoreRegs as Decode>::deserialize(data)
+                .map_err(|err| strict_encoding::Error::DataIntegrityError(err.to_string()))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod _serde {
+    use serde_crate::de::Error;
+    use serde_crate::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::CoreRegs;
+    use crate::data::encoding::{Decode, Encode};
+
+    impl Serialize for CoreRegs {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            Encode::serialize(self).serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for CoreRegs {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let data = Vec::<u8>::deserialize(deserializer)?;
+            <CoreRegs as Decode>::deserialize(data).map_err(D::Error::custom)
+        }
+    }
+}
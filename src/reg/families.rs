@@ -30,7 +30,7 @@ pub trait NumericRegister: Register {
 }
 
 /// Enumeration of integer arithmetic registers (`A`-registers)
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, Default)]
 #[repr(u8)]
 pub enum RegA {
     /// 8-bit arithmetics register
@@ -47,6 +47,7 @@ pub enum RegA {
 
     /// 64-bit arithmetics register
     #[display("a64")]
+    #[default]
     A64 = 3,
 
     /// 128-bit arithmetics register
@@ -66,10 +67,6 @@ pub enum RegA {
     A1024 = 7,
 }
 
-impl Default for RegA {
-    fn default() -> Self { RegA::A64 }
-}
-
 impl Register for RegA {
     #[inline]
     fn description() -> &'static str { "A register" }
@@ -168,11 +165,12 @@ impl TryFrom<RegAll> for RegA {
 
 /// Enumeration of integer arithmetic registers suited for string addresses (`a8` and `a16`
 /// registers)
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, Default)]
 #[repr(u8)]
 pub enum RegA2 {
     /// 8-bit arithmetics register
     #[display("a8")]
+    #[default]
     A8 = 0,
 
     /// 16-bit arithmetics register
@@ -180,10 +178,6 @@ pub enum RegA2 {
     A16 = 1,
 }
 
-impl Default for RegA2 {
-    fn default() -> Self { RegA2::A8 }
-}
-
 impl Register for RegA2 {
     #[inline]
     fn description() -> &'static str { "A8 or A16 register" }
@@ -245,7 +239,7 @@ impl TryFrom<RegAll> for RegA2 {
 }
 
 /// Enumeration of float arithmetic registers (`F`-registers)
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, Default)]
 #[repr(u8)]
 pub enum RegF {
     /// 16-bit bfloat16 format used in machine learning
@@ -262,6 +256,7 @@ pub enum RegF {
 
     /// 64-bit IEEE-754 binary64 double-precision
     #[display("f64")]
+    #[default]
     F64 = 3,
 
     /// 80-bit IEEE-754 extended precision
@@ -276,15 +271,11 @@ pub enum RegF {
     #[display("f256")]
     F256 = 6,
 
-    /// 512-bit tapered floating point
+    /// 512-bit tapered floating point in posit format (see [`crate::data::Posit512`])
     #[display("f512")]
     F512 = 7,
 }
 
-impl Default for RegF {
-    fn default() -> Self { RegF::F64 }
-}
-
 impl Register for RegF {
     #[inline]
     fn description() -> &'static str { "F register" }
@@ -376,7 +367,7 @@ impl TryFrom<RegAll> for RegF {
 
 /// Enumeration of the set of general registers (`R`-registers: non-arithmetic registers, mostly
 /// used for cryptography)
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, Default)]
 #[repr(u8)]
 pub enum RegR {
     /// 128-bit non-arithmetics register
@@ -389,6 +380,7 @@ pub enum RegR {
 
     /// 256-bit non-arithmetics register
     #[display("r256")]
+    #[default]
     R256 = 2,
 
     /// 512-bit non-arithmetics register
@@ -412,10 +404,6 @@ pub enum RegR {
     R8192 = 7,
 }
 
-impl Default for RegR {
-    fn default() -> Self { RegR::R256 }
-}
-
 impl Register for RegR {
     #[inline]
     fn description() -> &'static str { "R register" }
@@ -624,6 +612,22 @@ impl From<&RegAR> for RegAll {
     }
 }
 
+impl From<RegAFR> for RegAll {
+    #[inline]
+    fn from(reg: RegAFR) -> Self {
+        match reg {
+            RegAFR::A(a) => Self::A(a),
+            RegAFR::F(f) => Self::F(f),
+            RegAFR::R(r) => Self::R(r),
+        }
+    }
+}
+
+impl From<&RegAFR> for RegAll {
+    #[inline]
+    fn from(reg: &RegAFR) -> Self { RegAll::from(*reg) }
+}
+
 /// Superset of all registers which value can be represented by a
 /// [`crate::data::Number`]/[`crate::data::MaybeNumber`]. The superset includes `A`, `F`, and
 /// `R` families of registers.
@@ -1001,10 +1005,11 @@ impl TryFrom<RegAll> for RegAR {
 }
 
 /// Block of registers, either integer arithmetic or non-arithmetic (general) registers
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, Default)]
 pub enum RegBlockAR {
     /// Arithmetic integer registers (`A` registers)
     #[display("a")]
+    #[default]
     A,
 
     /// Non-arithmetic (generic) registers (`R` registers)
@@ -1012,10 +1017,6 @@ pub enum RegBlockAR {
     R,
 }
 
-impl Default for RegBlockAR {
-    fn default() -> Self { RegBlockAR::A }
-}
-
 impl Register for RegBlockAR {
     #[inline]
     fn description() -> &'static str { "A or R register block" }
@@ -1046,10 +1047,11 @@ impl TryFrom<RegAll> for RegBlockAR {
 }
 
 /// Block of registers, either integer, float arithmetic or non-arithmetic (general) registers
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, Default)]
 pub enum RegBlockAFR {
     /// Arithmetic integer registers (`A` registers)
     #[display("a")]
+    #[default]
     A,
 
     /// Arithmetic float registers (`F` registers)
@@ -1061,10 +1063,6 @@ pub enum RegBlockAFR {
     R,
 }
 
-impl Default for RegBlockAFR {
-    fn default() -> Self { RegBlockAFR::A }
-}
-
 impl Register for RegBlockAFR {
     #[inline]
     fn description() -> &'static str { "A, F or R register block" }
@@ -1096,10 +1094,11 @@ impl TryFrom<RegAll> for RegBlockAFR {
 }
 
 /// Blocks of registers including all non-control register types
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, Default)]
 pub enum RegBlock {
     /// Arithmetic integer registers (`A` registers)
     #[display("a")]
+    #[default]
     A,
 
     /// Arithmetic float registers (`F` registers)
@@ -1115,10 +1114,6 @@ pub enum RegBlock {
     S,
 }
 
-impl Default for RegBlock {
-    fn default() -> Self { RegBlock::A }
-}
-
 impl Register for RegBlock {
     #[inline]
     fn description() -> &'static str { "A, F, R or S register block" }
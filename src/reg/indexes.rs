@@ -97,7 +97,7 @@ pub enum Reg32 {
     Reg19 = 18,
 
     /// Register with index `[20]`
-    #[display("[10]")]
+    #[display("[20]")]
     Reg20 = 19,
 
     /// Register with index `[21]`
@@ -15,7 +15,9 @@ mod core_regs;
 mod families;
 mod indexes;
 
-pub use core_regs::{CoreRegs, CALL_STACK_SIZE};
+#[cfg(feature = "std")]
+pub(crate) use core_regs::NUMERIC_REGS;
+pub use core_regs::{CoreRegs, RegChange, CALL_STACK_SIZE};
 pub use families::{
     NumericRegister, RegA, RegA2, RegAF, RegAFR, RegAR, RegAll, RegBlock, RegBlockAFR, RegBlockAR,
     RegF, RegR,
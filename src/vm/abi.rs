@@ -0,0 +1,397 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Typed call ABI for invoking library methods with arguments and return values
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use amplify::num::apfloat::ieee;
+use amplify::num::{i1024, i256, i512, u1024, u256, u4, u5, u512};
+use half::bf16;
+
+use super::ExecOutcome;
+use crate::data::{ByteStr, MaybeNumber, Number};
+use crate::reg::{CoreRegs, Reg32, RegA, RegAFR, RegF, RegR, RegS};
+
+/// Family of registers used to pass values of a specific type
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, From)]
+pub enum SlotFamily {
+    /// Arithmetic, float or non-arithmetic register family
+    #[from]
+    #[from(RegA)]
+    #[from(RegF)]
+    #[from(RegR)]
+    #[display(inner)]
+    Num(RegAFR),
+
+    /// String registers
+    #[display("s16")]
+    Str,
+}
+
+impl SlotFamily {
+    /// Returns number of registers in the family
+    pub fn regs_count(self) -> u8 {
+        match self {
+            SlotFamily::Num(_) => 32,
+            SlotFamily::Str => 16,
+        }
+    }
+}
+
+/// Register holding a method argument or a return value
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+pub enum Slot {
+    /// Register from `A`, `F` or `R` families
+    #[display("{0}{1}")]
+    Num(RegAFR, Reg32),
+
+    /// String register
+    #[display(inner)]
+    Str(RegS),
+}
+
+impl Slot {
+    /// Sets the register to `None`
+    pub fn clear(self, regs: &mut CoreRegs) {
+        match self {
+            Slot::Num(reg, index) => {
+                regs.set(reg, index, MaybeNumber::none());
+            }
+            Slot::Str(index) => {
+                regs.set_s(index, None::<ByteStr>);
+            }
+        }
+    }
+}
+
+/// Allocator assigning registers to the method arguments or return values.
+///
+/// Method arguments and return values are passed through registers: each value occupies the next
+/// free register of the family matching its type, starting from the first register of the family.
+/// For instance, method taking `(u64, [u8; 32], u64)` arguments receives them in `a64[1]`,
+/// `r256[1]` and `a64[2]` registers. Return values are allocated in the same way, continuing after
+/// the arguments: if the same method returns `u64`, it must put it into `a64[3]` register.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct SlotAlloc(BTreeMap<SlotFamily, u8>);
+
+impl SlotAlloc {
+    /// Constructs allocator with all registers free
+    #[inline]
+    pub fn new() -> Self { SlotAlloc::default() }
+
+    /// Allocates next free register from a given family
+    pub fn alloc(&mut self, family: impl Into<SlotFamily>) -> Result<Slot, InvokeError> {
+        let family = family.into();
+        let next = self.0.entry(family).or_default();
+        if *next >= family.regs_count() {
+            return Err(InvokeError::SlotsExhausted(family));
+        }
+        let index = *next;
+        *next += 1;
+        Ok(match family {
+            SlotFamily::Num(reg) => Slot::Num(reg, Reg32::from(u5::with(index))),
+            SlotFamily::Str => Slot::Str(RegS::from(u4::with(index))),
+        })
+    }
+}
+
+/// Errors happening during method invocation
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[display(doc_comments)]
+#[cfg_attr(feature = "std", derive(Error))]
+pub enum InvokeError {
+    /// method has too many arguments or return values using {0} registers
+    SlotsExhausted(SlotFamily),
+
+    /// method execution has failed: {0}
+    Failed(ExecOutcome),
+
+    /// method has not returned a valid value in {0} register
+    NoResult(Slot),
+}
+
+/// Type which values can be passed to or returned from a method in a register
+pub trait AbiType: Sized {
+    /// Family of registers holding the values of the type
+    fn family() -> SlotFamily;
+
+    /// Puts the value into the register
+    fn store(&self, regs: &mut CoreRegs, slot: Slot);
+
+    /// Reads the value from the register, if it contains a valid value of the type
+    fn load(regs: &CoreRegs, slot: Slot) -> Option<Self>;
+}
+
+/// Set of method arguments
+pub trait AbiArgs {
+    /// Puts arguments into the registers allocated with the provided allocator
+    fn store_args(&self, regs: &mut CoreRegs, alloc: &mut SlotAlloc) -> Result<(), InvokeError>;
+}
+
+/// Set of method return values
+pub trait AbiResults: Sized {
+    /// Reads return values from the registers allocated with the provided allocator
+    fn load_results(regs: &CoreRegs, alloc: &mut SlotAlloc) -> Result<Self, InvokeError>;
+
+    /// Sets registers allocated for the return values with the provided allocator to `None`
+    fn clear_results(regs: &mut CoreRegs, alloc: &mut SlotAlloc) -> Result<(), InvokeError>;
+}
+
+fn get_num(regs: &CoreRegs, slot: Slot) -> Option<Number> {
+    match slot {
+        Slot::Num(reg, index) => regs.get(reg, index).into(),
+        Slot::Str(_) => None,
+    }
+}
+
+macro_rules! impl_abi_num {
+    ($ty:ty, $family:expr) => {
+        impl AbiType for $ty {
+            #[inline]
+            fn family() -> SlotFamily { $family.into() }
+
+            #[inline]
+            fn store(&self, regs: &mut CoreRegs, slot: Slot) {
+                if let Slot::Num(reg, index) = slot {
+                    regs.set(reg, index, *self);
+                }
+            }
+
+            #[inline]
+            fn load(regs: &CoreRegs, slot: Slot) -> Option<Self> {
+                get_num(regs, slot).map(<$ty>::from)
+            }
+        }
+    };
+}
+
+/// Signed integers are passed in `A` registers as their two's complement bit representation
+macro_rules! impl_abi_signed {
+    ($ty:ty, $uty:ty) => {
+        impl AbiType for $ty {
+            #[inline]
+            fn family() -> SlotFamily { <$uty>::family() }
+
+            #[inline]
+            fn store(&self, regs: &mut CoreRegs, slot: Slot) {
+                <$uty>::from_le_bytes(self.to_le_bytes()).store(regs, slot)
+            }
+
+            #[inline]
+            fn load(regs: &CoreRegs, slot: Slot) -> Option<Self> {
+                <$uty>::load(regs, slot).map(|val| <$ty>::from_le_bytes(val.to_le_bytes()))
+            }
+        }
+    };
+}
+
+impl_abi_num!(u8, RegA::A8);
+impl_abi_num!(u16, RegA::A16);
+impl_abi_num!(u32, RegA::A32);
+impl_abi_num!(u64, RegA::A64);
+impl_abi_num!(u128, RegA::A128);
+impl_abi_num!(u256, RegA::A256);
+impl_abi_num!(u512, RegA::A512);
+impl_abi_num!(u1024, RegA::A1024);
+
+impl_abi_signed!(i8, u8);
+impl_abi_signed!(i16, u16);
+impl_abi_signed!(i32, u32);
+impl_abi_signed!(i64, u64);
+impl_abi_signed!(i128, u128);
+impl_abi_signed!(i256, u256);
+impl_abi_signed!(i512, u512);
+impl_abi_signed!(i1024, u1024);
+
+impl_abi_num!([u8; 16], RegR::R128);
+impl_abi_num!([u8; 20], RegR::R160);
+impl_abi_num!([u8; 32], RegR::R256);
+impl_abi_num!([u8; 64], RegR::R512);
+impl_abi_num!([u8; 128], RegR::R1024);
+impl_abi_num!([u8; 256], RegR::R2048);
+impl_abi_num!([u8; 512], RegR::R4096);
+impl_abi_num!([u8; 1024], RegR::R8192);
+
+impl_abi_num!(bf16, RegF::F16B);
+impl_abi_num!(ieee::Half, RegF::F16);
+impl_abi_num!(ieee::Single, RegF::F32);
+impl_abi_num!(ieee::Double, RegF::F64);
+impl_abi_num!(ieee::X87DoubleExtended, RegF::F80);
+impl_abi_num!(ieee::Quad, RegF::F128);
+impl_abi_num!(ieee::Oct, RegF::F256);
+
+/// Booleans are passed in `a8` registers as `0` and `1` values
+impl AbiType for bool {
+    #[inline]
+    fn family() -> SlotFamily { RegA::A8.into() }
+
+    #[inline]
+    fn store(&self, regs: &mut CoreRegs, slot: Slot) { (*self as u8).store(regs, slot) }
+
+    fn load(regs: &CoreRegs, slot: Slot) -> Option<Self> {
+        match u8::load(regs, slot) {
+            Some(0) => Some(false),
+            Some(1) => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl AbiType for ByteStr {
+    #[inline]
+    fn family() -> SlotFamily { SlotFamily::Str }
+
+    #[inline]
+    fn store(&self, regs: &mut CoreRegs, slot: Slot) {
+        if let Slot::Str(index) = slot {
+            regs.set_s(index, Some(self.clone()));
+        }
+    }
+
+    #[inline]
+    fn load(regs: &CoreRegs, slot: Slot) -> Option<Self> {
+        match slot {
+            Slot::Str(index) => regs.get_s(index).cloned(),
+            Slot::Num(..) => None,
+        }
+    }
+}
+
+impl AbiType for Vec<u8> {
+    #[inline]
+    fn family() -> SlotFamily { SlotFamily::Str }
+
+    #[inline]
+    fn store(&self, regs: &mut CoreRegs, slot: Slot) { ByteStr::with(self).store(regs, slot) }
+
+    #[inline]
+    fn load(regs: &CoreRegs, slot: Slot) -> Option<Self> {
+        ByteStr::load(regs, slot).map(|s| s.as_ref().to_vec())
+    }
+}
+
+impl AbiArgs for () {
+    #[inline]
+    fn store_args(&self, _: &mut CoreRegs, _: &mut SlotAlloc) -> Result<(), InvokeError> { Ok(()) }
+}
+
+impl AbiResults for () {
+    #[inline]
+    fn load_results(_: &CoreRegs, _: &mut SlotAlloc) -> Result<Self, InvokeError> { Ok(()) }
+
+    #[inline]
+    fn clear_results(_: &mut CoreRegs, _: &mut SlotAlloc) -> Result<(), InvokeError> { Ok(()) }
+}
+
+impl<T> AbiArgs for T
+where
+    T: AbiType,
+{
+    fn store_args(&self, regs: &mut CoreRegs, alloc: &mut SlotAlloc) -> Result<(), InvokeError> {
+        let slot = alloc.alloc(T::family())?;
+        self.store(regs, slot);
+        Ok(())
+    }
+}
+
+impl<T> AbiResults for T
+where
+    T: AbiType,
+{
+    fn load_results(regs: &CoreRegs, alloc: &mut SlotAlloc) -> Result<Self, InvokeError> {
+        let slot = alloc.alloc(T::family())?;
+        T::load(regs, slot).ok_or(InvokeError::NoResult(slot))
+    }
+
+    fn clear_results(regs: &mut CoreRegs, alloc: &mut SlotAlloc) -> Result<(), InvokeError> {
+        alloc.alloc(T::family())?.clear(regs);
+        Ok(())
+    }
+}
+
+macro_rules! impl_abi_tuple {
+    ($($ty:ident $no:tt),+) => {
+        impl<$($ty),+> AbiArgs for ($($ty,)+)
+        where
+            $($ty: AbiType),+
+        {
+            fn store_args(
+                &self,
+                regs: &mut CoreRegs,
+                alloc: &mut SlotAlloc,
+            ) -> Result<(), InvokeError> {
+                $( self.$no.store_args(regs, alloc)?; )+
+                Ok(())
+            }
+        }
+
+        impl<$($ty),+> AbiResults for ($($ty,)+)
+        where
+            $($ty: AbiType),+
+        {
+            fn load_results(regs: &CoreRegs, alloc: &mut SlotAlloc) -> Result<Self, InvokeError> {
+                Ok(($( $ty::load_results(regs, alloc)?, )+))
+            }
+
+            fn clear_results(
+                regs: &mut CoreRegs,
+                alloc: &mut SlotAlloc,
+            ) -> Result<(), InvokeError> {
+                $( $ty::clear_results(regs, alloc)?; )+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_abi_tuple!(A 0);
+impl_abi_tuple!(A 0, B 1);
+impl_abi_tuple!(A 0, B 1, C 2);
+impl_abi_tuple!(A 0, B 1, C 2, D 3);
+impl_abi_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_abi_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_abi_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_abi_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn alloc_test() {
+        let mut alloc = SlotAlloc::new();
+        assert_eq!(alloc.alloc(RegA::A64).unwrap().to_string(), "a64[1]");
+        assert_eq!(alloc.alloc(RegR::R256).unwrap().to_string(), "r256[1]");
+        assert_eq!(alloc.alloc(RegA::A64).unwrap().to_string(), "a64[2]");
+        assert_eq!(alloc.alloc(SlotFamily::Str).unwrap().to_string(), "s16[0]");
+        for _ in 1..16 {
+            alloc.alloc(SlotFamily::Str).unwrap();
+        }
+        assert_eq!(alloc.alloc(SlotFamily::Str), Err(InvokeError::SlotsExhausted(SlotFamily::Str)));
+    }
+
+    #[test]
+    fn roundtrip_test() {
+        let mut regs = CoreRegs::new();
+        let args = (7u64, [1u8; 32], true, -5i16, b"abc".to_vec());
+        args.store_args(&mut regs, &mut SlotAlloc::new()).unwrap();
+        let res = <(u64, [u8; 32], bool, i16, Vec<u8>)>::load_results(&regs, &mut SlotAlloc::new());
+        assert_eq!(res, Ok(args));
+        assert_eq!(
+            <(u64, u64)>::load_results(&regs, &mut SlotAlloc::new()),
+            Err(InvokeError::NoResult(Slot::Num(RegA::A64.into(), Reg32::Reg2)))
+        );
+    }
+}
@@ -0,0 +1,110 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Backtraces of the failed program executions
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::isa::InstructionSet;
+use crate::program::{Lib, LibId, LibSite};
+
+/// Single frame of a [`Backtrace`]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Frame {
+    /// Location of the instruction
+    pub site: LibSite,
+
+    /// Disassembled instruction located at the site, or `None` if the library is not known or the
+    /// site does not point to a valid instruction
+    pub instr: Option<String>,
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.instr {
+            Some(instr) => write!(f, "{}: {}", self.site, instr),
+            None => write!(f, "{}: <unknown>", self.site),
+        }
+    }
+}
+
+/// Backtrace of a failed program execution.
+///
+/// The first frame is the entry point of the execution; it is followed by the `call` and `routine`
+/// instructions from the call stack `cs0` which were not returned from, starting from the
+/// outermost one. The last frame is the instruction at which the execution has halted.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Backtrace(Vec<Frame>);
+
+impl Display for Backtrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (no, frame) in self.0.iter().enumerate() {
+            if no > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "#{:<3} {}", no, frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl Backtrace {
+    /// Constructs backtrace for the execution which has started at `entrypoint` and halted at
+    /// `site` with the provided call stack, disassembling instructions from the libraries returned
+    /// by `lib` function.
+    pub(crate) fn capture<'lib, Isa>(
+        entrypoint: LibSite,
+        call_stack: &[LibSite],
+        site: LibSite,
+        lib: impl Fn(LibId) -> Option<&'lib Lib>,
+    ) -> Backtrace
+    where
+        Isa: InstructionSet,
+    {
+        let mut decoded = BTreeMap::<LibId, Option<(Vec<Isa>, Vec<u16>)>>::new();
+        let mut frame = |site: LibSite, returns: bool| -> Frame {
+            let code =
+                decoded.entry(site.lib).or_insert_with(|| lib(site.lib).map(Lib::decode::<Isa>));
+            let found = code.as_ref().and_then(|(code, offsets)| {
+                let idx = if returns {
+                    // Call stack contains sites of the instructions following the calls
+                    offsets.iter().skip(1).position(|pos| *pos == site.pos)?
+                } else {
+                    offsets[..code.len()].binary_search(&site.pos).ok()?
+                };
+                Some((offsets[idx], code[idx].to_string()))
+            });
+            match found {
+                Some((pos, instr)) => {
+                    Frame { site: LibSite::with(pos, site.lib), instr: Some(instr) }
+                }
+                None => Frame { site, instr: None },
+            }
+        };
+
+        let mut frames = Vec::with_capacity(call_stack.len() + 2);
+        frames.push(frame(entrypoint, false));
+        frames.extend(call_stack.iter().map(|site| frame(*site, true)));
+        frames.push(frame(site, false));
+        Backtrace(frames)
+    }
+
+    /// Returns frames of the backtrace, starting from the entry point
+    #[inline]
+    pub fn frames(&self) -> &[Frame] { &self.0 }
+
+    /// Returns frame of the instruction at which the execution has halted
+    #[inline]
+    pub fn last(&self) -> Option<&Frame> { self.0.last() }
+}
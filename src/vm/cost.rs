@@ -0,0 +1,108 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Pricing of the executed instructions
+
+use alloc::collections::BTreeMap;
+use core::fmt::Debug;
+
+use crate::isa::InstructionSet;
+use crate::reg::CoreRegs;
+
+/// Model pricing instructions executed by [`crate::Vm`].
+///
+/// The cost of each executed instruction is accumulated into the complexity accumulator `ca0`;
+/// once it reaches the complexity limit `cl0` the execution halts with
+/// [`crate::Halt::ComplexityLimit`].
+pub trait CostModel<Isa>: Debug
+where
+    Isa: InstructionSet,
+{
+    /// Returns cost of the instruction, which is computed from the state of the registers before
+    /// the instruction execution.
+    fn cost(&self, instr: &Isa, regs: &CoreRegs) -> u64;
+}
+
+/// Default cost model, pricing each instruction with its [`InstructionSet::complexity`]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Complexity;
+
+impl<Isa> CostModel<Isa> for Complexity
+where
+    Isa: InstructionSet,
+{
+    #[inline]
+    fn cost(&self, instr: &Isa, _: &CoreRegs) -> u64 { instr.complexity() }
+}
+
+/// Configurable fee schedule.
+///
+/// The cost of an instruction is computed as a sum of:
+/// - base cost for the instruction opcode, which defaults to [`InstructionSet::complexity`] for the
+///   opcodes without explicitly set cost;
+/// - cost of each byte of the widest numeric register used by the instruction (see
+///   [`InstructionSet::reg_bytes`]);
+/// - cost of each byte of the strings the instruction operates on (see
+///   [`InstructionSet::str_bytes`]).
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct FeeSchedule {
+    opcodes: BTreeMap<u8, u64>,
+    reg_byte: u64,
+    str_byte: u64,
+}
+
+impl FeeSchedule {
+    /// Constructs fee schedule which prices instructions in the same way as [`Complexity`] model
+    #[inline]
+    pub fn new() -> Self { FeeSchedule::default() }
+
+    /// Returns base cost explicitly set for the opcode
+    #[inline]
+    pub fn opcode_cost(&self, opcode: u8) -> Option<u64> { self.opcodes.get(&opcode).copied() }
+
+    /// Sets base cost for the opcode, returning previously set value
+    #[inline]
+    pub fn set_opcode_cost(&mut self, opcode: u8, cost: u64) -> Option<u64> {
+        self.opcodes.insert(opcode, cost)
+    }
+
+    /// Removes base cost set for the opcode, so the instruction complexity is used instead
+    #[inline]
+    pub fn reset_opcode_cost(&mut self, opcode: u8) -> Option<u64> { self.opcodes.remove(&opcode) }
+
+    /// Returns cost of a single byte of the numeric register
+    #[inline]
+    pub fn reg_byte_cost(&self) -> u64 { self.reg_byte }
+
+    /// Sets cost of a single byte of the numeric register
+    #[inline]
+    pub fn set_reg_byte_cost(&mut self, cost: u64) { self.reg_byte = cost }
+
+    /// Returns cost of a single byte of the string operands
+    #[inline]
+    pub fn str_byte_cost(&self) -> u64 { self.str_byte }
+
+    /// Sets cost of a single byte of the string operands
+    #[inline]
+    pub fn set_str_byte_cost(&mut self, cost: u64) { self.str_byte = cost }
+}
+
+impl<Isa> CostModel<Isa> for FeeSchedule
+where
+    Isa: InstructionSet,
+{
+    fn cost(&self, instr: &Isa, regs: &CoreRegs) -> u64 {
+        let base = self.opcode_cost(instr.instr_byte()).unwrap_or_else(|| instr.complexity());
+        let regs_cost = self.reg_byte.saturating_mul(instr.reg_bytes() as u64);
+        let str_cost = self.str_byte.saturating_mul(instr.str_bytes(regs) as u64);
+        base.saturating_add(regs_cost).saturating_add(str_cost)
+    }
+}
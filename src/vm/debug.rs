@@ -0,0 +1,264 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Step-by-step program execution for debugging purposes
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+use super::{resolve, ExecOutcome, NoTrace, Vm};
+use crate::data::MaybeNumber;
+use crate::isa::{Instr, InstructionSet, ReservedOp};
+use crate::program::LibSite;
+use crate::reg::{CoreRegs, Reg32, RegAFR, RegS};
+use crate::Program;
+
+/// Register watched by the [`Debugger`] for value changes
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, From)]
+pub enum Watch {
+    /// Register from `A`, `F` or `R` families, like `a64[3]`
+    #[display("{0}{1}")]
+    Reg(RegAFR, Reg32),
+
+    /// String register, like `s16[2]`
+    #[display(inner)]
+    #[from]
+    Str(RegS),
+}
+
+impl Watch {
+    fn value(self, regs: &CoreRegs) -> WatchValue {
+        match self {
+            Watch::Reg(reg, idx) => WatchValue::Num(regs.get(reg, idx)),
+            Watch::Str(reg) => WatchValue::Str(regs.get_s(reg).map(|s| s.as_ref().to_vec())),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[allow(clippy::large_enum_variant)]
+enum WatchValue {
+    Num(MaybeNumber),
+    Str(Option<Vec<u8>>),
+}
+
+/// Reason for the [`Debugger`] to pause program execution
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[display(doc_comments)]
+pub enum Pause {
+    /// requested step has been completed
+    Step,
+
+    /// execution has reached breakpoint at {0}
+    Breakpoint(LibSite),
+
+    /// value of the watched register {0} has changed
+    Watchpoint(Watch),
+
+    /// program has halted
+    Halted(ExecOutcome),
+}
+
+/// Debugger executing program step by step on a wrapped virtual machine.
+///
+/// Between the steps the debugger provides access to the state of the registers and the call
+/// stack. Program execution may be paused on reaching breakpoints - specific locations in the
+/// code, and on a change of the watched register values.
+#[derive(Debug)]
+pub struct Debugger<'prog, Isa = Instr<ReservedOp>>
+where
+    Isa: InstructionSet,
+{
+    vm: Vm<Isa>,
+    program: &'prog Program<Isa>,
+    entrypoint: LibSite,
+    site: LibSite,
+    outcome: Option<ExecOutcome>,
+    breakpoints: BTreeSet<LibSite>,
+    watchpoints: BTreeMap<Watch, WatchValue>,
+}
+
+impl<'prog, Isa> Debugger<'prog, Isa>
+where
+    Isa: InstructionSet,
+{
+    /// Constructs debugger which will execute the program on the provided virtual machine starting
+    /// from the program entry point.
+    #[inline]
+    pub fn new(vm: Vm<Isa>, program: &'prog Program<Isa>) -> Self {
+        Self::with_entrypoint(vm, program, program.entrypoint())
+    }
+
+    /// Constructs debugger which will execute the program on the provided virtual machine starting
+    /// from the given entry point.
+    pub fn with_entrypoint(vm: Vm<Isa>, program: &'prog Program<Isa>, method: LibSite) -> Self {
+        Debugger {
+            vm,
+            program,
+            entrypoint: method,
+            site: method,
+            outcome: None,
+            breakpoints: empty!(),
+            watchpoints: empty!(),
+        }
+    }
+
+    /// Returns location of the instruction which will be executed next, or `None` if the program
+    /// has halted.
+    #[inline]
+    pub fn site(&self) -> Option<LibSite> {
+        if self.outcome.is_some() {
+            None
+        } else {
+            Some(self.site)
+        }
+    }
+
+    /// Returns outcome of the program execution if the program has halted.
+    #[inline]
+    pub fn outcome(&self) -> Option<ExecOutcome> { self.outcome }
+
+    /// Returns current state of the virtual machine registers.
+    #[inline]
+    pub fn registers(&self) -> &CoreRegs { &self.vm.registers }
+
+    /// Returns current state of the call stack (see [`CoreRegs::call_stack`]).
+    #[inline]
+    pub fn call_stack(&self) -> &[LibSite] { self.vm.registers.call_stack() }
+
+    /// Returns reference to the wrapped virtual machine.
+    #[inline]
+    pub fn vm(&self) -> &Vm<Isa> { &self.vm }
+
+    /// Releases the wrapped virtual machine.
+    #[inline]
+    pub fn into_vm(self) -> Vm<Isa> { self.vm }
+
+    /// Adds breakpoint at the given location.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the breakpoint was not present before.
+    #[inline]
+    pub fn add_breakpoint(&mut self, site: LibSite) -> bool { self.breakpoints.insert(site) }
+
+    /// Removes breakpoint from the given location.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the breakpoint was present.
+    #[inline]
+    pub fn remove_breakpoint(&mut self, site: LibSite) -> bool { self.breakpoints.remove(&site) }
+
+    /// Starts watching the register for the value changes.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the register was not watched before.
+    pub fn add_watchpoint(&mut self, watch: impl Into<Watch>) -> bool {
+        let watch = watch.into();
+        let value = watch.value(&self.vm.registers);
+        self.watchpoints.insert(watch, value).is_none()
+    }
+
+    /// Stops watching the register for the value changes.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the register was watched.
+    #[inline]
+    pub fn remove_watchpoint(&mut self, watch: impl Into<Watch>) -> bool {
+        self.watchpoints.remove(&watch.into()).is_some()
+    }
+
+    /// Executes a single instruction.
+    pub fn step(&mut self) -> Pause { self.advance().unwrap_or(Pause::Step) }
+
+    /// Executes a single instruction; if this instruction is a call of a routine (`routine` or
+    /// `call` instruction), continues execution until the routine returns.
+    ///
+    /// Execution of the routine may be paused earlier by breakpoints and watchpoints.
+    pub fn step_over(&mut self) -> Pause {
+        let depth = self.call_stack().len();
+        if let Some(pause) = self.advance() {
+            return pause;
+        }
+        while self.call_stack().len() > depth {
+            if let Some(pause) = self.breakpoint() {
+                return pause;
+            }
+            if let Some(pause) = self.advance() {
+                return pause;
+            }
+        }
+        Pause::Step
+    }
+
+    /// Continues program execution until it halts or reaches a breakpoint, or until one of the
+    /// watched registers changes its value.
+    ///
+    /// The instruction at the current location is always executed, even if it has a breakpoint.
+    pub fn run(&mut self) -> Pause {
+        loop {
+            if let Some(pause) = self.advance() {
+                return pause;
+            }
+            if let Some(pause) = self.breakpoint() {
+                return pause;
+            }
+        }
+    }
+
+    fn breakpoint(&self) -> Option<Pause> {
+        self.breakpoints.get(&self.site).copied().map(Pause::Breakpoint)
+    }
+
+    fn advance(&mut self) -> Option<Pause> {
+        if let Some(outcome) = self.outcome {
+            return Some(Pause::Halted(outcome));
+        }
+
+        let regs = &mut self.vm.registers;
+        let env = &mut self.vm.env;
+        let lib = match self.program.lib(self.site.lib) {
+            Some(lib) => Ok(lib),
+            None => resolve::<Isa>(&mut self.vm.resolver, self.site.lib),
+        };
+        let next = match lib {
+            Ok(lib) => lib.step_as::<Isa>(self.site.lib, self.site.pos, regs, env, &mut NoTrace),
+            Err(halt) => {
+                regs.st0 = false;
+                Err((self.site.pos, halt))
+            }
+        };
+        match next {
+            Ok(site) => self.site = site,
+            Err((pos, halt)) => {
+                let site = LibSite::with(pos, self.site.lib);
+                let program = self.program;
+                self.vm.capture_backtrace(self.entrypoint, site, |id| program.lib(id));
+                let outcome = self.vm.outcome(halt, site);
+                self.outcome = Some(outcome);
+                return Some(Pause::Halted(outcome));
+            }
+        }
+
+        let mut pause = None;
+        for (watch, value) in &mut self.watchpoints {
+            let new = watch.value(&self.vm.registers);
+            if new != *value {
+                *value = new;
+                pause = pause.or(Some(Pause::Watchpoint(*watch)));
+            }
+        }
+        pause
+    }
+}
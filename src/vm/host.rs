@@ -0,0 +1,75 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Host functions callable from the programs with `hcall` instruction
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::fmt::{self, Debug, Formatter};
+
+use crate::reg::CoreRegs;
+
+/// Function provided by the embedding application, which can be called by a program with `hcall`
+/// instruction.
+///
+/// The function takes its arguments from the registers and puts its results into the registers.
+/// It returns `false` to signal the failure, which sets `st0` register to `false`.
+pub type HostFn = Box<dyn FnMut(&mut CoreRegs) -> bool>;
+
+/// Registry of the host functions, indexed by the function id used in `hcall` instruction
+#[derive(Default)]
+pub struct HostFns(BTreeMap<u16, HostFn>);
+
+impl Debug for HostFns {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+impl HostFns {
+    /// Constructs empty host function registry
+    #[inline]
+    pub fn new() -> Self { HostFns::default() }
+
+    /// Registers host function under a given id, returning previously registered function with
+    /// the same id, if any.
+    pub fn register(
+        &mut self,
+        id: u16,
+        f: impl FnMut(&mut CoreRegs) -> bool + 'static,
+    ) -> Option<HostFn> {
+        self.0.insert(id, Box::new(f))
+    }
+
+    /// Removes host function with a given id from the registry
+    #[inline]
+    pub fn unregister(&mut self, id: u16) -> Option<HostFn> { self.0.remove(&id) }
+
+    /// Checks whether the host function with a given id is registered
+    #[inline]
+    pub fn contains(&self, id: u16) -> bool { self.0.contains_key(&id) }
+
+    /// Returns iterator over ids of all registered host functions
+    #[inline]
+    pub fn ids(&self) -> impl Iterator<Item = u16> + '_ { self.0.keys().copied() }
+
+    /// Calls host function with a given id. If there is no such function, or if the function has
+    /// failed, sets `st0` to `false`.
+    pub(crate) fn call(&mut self, id: u16, regs: &mut CoreRegs) {
+        let success = match self.0.get_mut(&id) {
+            Some(f) => f(regs),
+            None => false,
+        };
+        if !success {
+            regs.st0 = false;
+        }
+    }
+}
@@ -0,0 +1,492 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Alu virtual machine
+
+mod abi;
+mod backtrace;
+mod cost;
+mod debug;
+mod host;
+mod trace;
+
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+
+pub use self::abi::{AbiArgs, AbiResults, AbiType, InvokeError, Slot, SlotAlloc, SlotFamily};
+pub use self::backtrace::{Backtrace, Frame};
+pub use self::cost::{Complexity, CostModel, FeeSchedule};
+pub use self::debug::{Debugger, Pause, Watch};
+pub use self::host::{HostFn, HostFns};
+#[cfg(feature = "std")]
+pub use self::trace::Stderr;
+pub use self::trace::{JsonTracer, NoTrace, TextTracer, Tracer};
+use crate::isa::{Instr, InstructionSet, ReservedOp};
+use crate::program::{
+    CachedResolver, CodeEofError, CompiledLib, CompiledProgram, Lib, LibId, LibResolver, LibSite,
+    ResolveError,
+};
+use crate::reg::{CoreRegs, RegAll};
+use crate::Program;
+
+/// Reason for the virtual machine to halt program execution
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[display(doc_comments)]
+pub enum Halt {
+    /// program has completed with `succ` instruction
+    Succ,
+
+    /// program has failed with `fail` instruction
+    Fail,
+
+    /// program has returned from the outermost routine with `ret` instruction
+    Ret,
+
+    /// program execution was stopped by an instruction
+    Stop,
+
+    /// execution has reached the end of the code segment
+    CodeEnd,
+
+    /// complexity accumulator `ca0` has reached complexity limit `cl0`
+    ComplexityLimit,
+
+    /// jump counter `cy0` has overflown
+    JumpLimit,
+
+    /// call stack `cs0` has overflown
+    CallStackOverflow,
+
+    /// unable to decode instruction: {0}
+    CodeEof(CodeEofError),
+
+    /// library {0} is not a part of the program
+    MissingLib(LibId),
+
+    /// library {0} provided by the resolver is invalid or not supported by the instruction set
+    InvalidLib(LibId),
+}
+
+/// Outcome of a program execution by the virtual machine
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[display("{halt} at {site}")]
+pub struct ExecOutcome {
+    /// Value of the `st0` register at the end of the program execution
+    pub success: bool,
+
+    /// Reason for the execution halt
+    pub halt: Halt,
+
+    /// Location of the instruction at which the execution has halted
+    pub site: LibSite,
+
+    /// Complexity accumulated during the execution (value of the `ca0` register)
+    pub complexity: u64,
+
+    /// Number of performed jumps (value of the `cy0` register)
+    pub jumps: u16,
+}
+
+/// Alu virtual machine providing single-core execution environment
+///
+/// # Run isolation
+///
+/// The virtual machine keeps its registers between the program runs: a run starts with the
+/// register state left by the previous one, including `st0`, the counters `cy0` and `ca0` and the
+/// call stack. This allows passing data between the runs, but makes them dependent on each other;
+/// in order to reuse the same instance for unrelated runs (for instance, from a pool of virtual
+/// machines) call [`Vm::reset`] or [`Vm::reset_keeping`] before each of them, which is cheaper
+/// than constructing a new instance.
+///
+/// The following is not affected by the reset and persists across all runs:
+/// - complexity limit `cl0`;
+/// - registered host functions, including any state captured by them;
+/// - cost model;
+/// - library resolver and the libraries it has already resolved.
+#[derive(Getters, Debug, Default)]
+pub struct Vm<Isa = Instr<ReservedOp>>
+where
+    Isa: InstructionSet,
+{
+    /// A set of registers
+    registers: Box<CoreRegs>,
+
+    /// Host functions and cost model used during the program execution
+    #[getter(skip)]
+    env: Env<Isa>,
+
+    /// Resolver for the libraries which are not a part of the executed program
+    #[getter(skip)]
+    resolver: Option<CachedResolver<Box<dyn LibResolver>>>,
+
+    /// Backtrace of the last execution, if it has failed
+    #[getter(skip)]
+    backtrace: Option<Backtrace>,
+
+    phantom: PhantomData<Isa>,
+}
+
+/// Runtime for program execution.
+impl<Isa> Vm<Isa>
+where
+    Isa: InstructionSet,
+{
+    /// Constructs new virtual machine instance.
+    pub fn new() -> Self {
+        Self {
+            registers: Box::default(),
+            env: Env::default(),
+            resolver: None,
+            backtrace: None,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Constructs new virtual machine instance with the complexity limit register `cl0` set to
+    /// the provided value.
+    ///
+    /// Programs which accumulate complexity reaching or exceeding the limit are halted with
+    /// [`Halt::ComplexityLimit`] and `st0` set to `false`.
+    pub fn with_limits(complexity_limit: u64) -> Self {
+        Self {
+            registers: Box::new(CoreRegs::with_complexity_limit(complexity_limit)),
+            env: Env::default(),
+            resolver: None,
+            backtrace: None,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Sets complexity limit register `cl0`, or removes the limit if `None` is provided.
+    #[inline]
+    pub fn set_complexity_limit(&mut self, limit: Option<u64>) {
+        self.registers.set_complexity_limit(limit)
+    }
+
+    /// Registers host function under a given id, which can be called by the programs with
+    /// `hcall` instruction, replacing previously registered function with the same id.
+    ///
+    /// The function reads its arguments from the registers and writes results back to them; it
+    /// returns `false` to signal a failure, which sets `st0` to `false`. Calls to the ids without
+    /// registered functions also set `st0` to `false`.
+    #[inline]
+    pub fn register_host_fn(
+        &mut self,
+        id: u16,
+        f: impl FnMut(&mut CoreRegs) -> bool + 'static,
+    ) -> Option<HostFn> {
+        self.env.hosts.register(id, f)
+    }
+
+    /// Removes host function with a given id
+    #[inline]
+    pub fn unregister_host_fn(&mut self, id: u16) -> Option<HostFn> {
+        self.env.hosts.unregister(id)
+    }
+
+    /// Returns registry of the host functions available to the programs
+    #[inline]
+    pub fn host_fns(&self) -> &HostFns { &self.env.hosts }
+
+    /// Sets cost model pricing the executed instructions, replacing the default [`Complexity`]
+    /// model. The cost of each executed instruction is accumulated into `ca0` and checked against
+    /// the complexity limit `cl0` (see [`Vm::with_limits`]).
+    #[inline]
+    pub fn set_cost_model(&mut self, model: impl CostModel<Isa> + 'static) {
+        self.env.cost = Box::new(model);
+    }
+
+    /// Returns cost model pricing the executed instructions
+    #[inline]
+    pub fn cost_model(&self) -> &dyn CostModel<Isa> { self.env.cost.as_ref() }
+
+    /// Sets resolver which is consulted when the execution reaches a library which is not a part
+    /// of the program. Resolved libraries are verified to have the requested id and are cached by
+    /// the virtual machine, so each of them is resolved at most once.
+    ///
+    /// If the resolver does not know the library, the execution halts with [`Halt::MissingLib`];
+    /// if the resolved library has a different id or uses ISA extensions not supported by the
+    /// instruction set, the execution halts with [`Halt::InvalidLib`].
+    pub fn set_resolver(&mut self, resolver: impl LibResolver + 'static) {
+        self.resolver = Some(CachedResolver::new(Box::new(resolver)));
+    }
+
+    /// Removes library resolver together with all libraries resolved by it
+    #[inline]
+    pub fn remove_resolver(&mut self) { self.resolver = None; }
+
+    /// Returns the virtual machine to the initial state for the next run: sets `st0` to `true`,
+    /// counters to zero, call stack to empty, all other registers to `None` and removes the
+    /// backtrace of the previous execution. Complexity limit and the execution environment are
+    /// preserved (see [`Vm`] documentation on run isolation).
+    #[inline]
+    pub fn reset(&mut self) { self.reset_keeping(&[]) }
+
+    /// Returns the virtual machine to the initial state for the next run, like [`Vm::reset`],
+    /// leaving values of the registers from the provided families intact. This allows to prepare
+    /// program inputs before the reset, or to keep them for multiple runs.
+    pub fn reset_keeping(&mut self, families: &[RegAll]) {
+        self.registers.reset_keeping(families);
+        self.backtrace = None;
+    }
+
+    /// Returns backtrace of the last execution if it has completed with `st0` set to `false`, or
+    /// `None` if it has succeeded.
+    ///
+    /// The backtrace is captured automatically by all program execution methods.
+    #[inline]
+    pub fn backtrace(&self) -> Option<&Backtrace> { self.backtrace.as_ref() }
+
+    /// Returns copy of the current state of all registers, which may be serialized and later
+    /// restored with [`Vm::restore`].
+    ///
+    /// Snapshot does not include the location of the next instruction to execute; in order to
+    /// resume interrupted execution it has to be saved separately (see [`Debugger::site`]).
+    #[inline]
+    pub fn snapshot(&self) -> CoreRegs { CoreRegs::clone(&self.registers) }
+
+    /// Replaces the state of all registers with the one taken by [`Vm::snapshot`].
+    #[inline]
+    pub fn restore(&mut self, snapshot: CoreRegs) { *self.registers = snapshot; }
+
+    /// Executes the program starting from the provided entry point (set with
+    /// [`Program::set_entrypoint`] and [`Program::with`], or initialized to 0 offset of the
+    /// first used library if [`Program::new`] was used).
+    ///
+    /// # Returns
+    ///
+    /// Value of the `st0` register at the end of the program execution.
+    #[inline]
+    pub fn run(&mut self, program: &Program<Isa>) -> bool { self.exec(program).success }
+
+    /// Executes the program starting from the provided entry point.
+    ///
+    /// # Returns
+    ///
+    /// Value of the `st0` register at the end of the program execution.
+    #[inline]
+    pub fn call(&mut self, program: &Program<Isa>, method: LibSite) -> bool {
+        self.exec_call(program, method).success
+    }
+
+    /// Executes the program starting from the program entry point, like [`Vm::run`].
+    ///
+    /// # Returns
+    ///
+    /// Detailed [`ExecOutcome`] describing why and where the execution has halted.
+    #[inline]
+    pub fn exec(&mut self, program: &Program<Isa>) -> ExecOutcome {
+        self.exec_call(program, program.entrypoint())
+    }
+
+    /// Executes the program starting from the provided entry point, like [`Vm::call`].
+    ///
+    /// If the execution reaches a library which is not a part of the program, the library is
+    /// resolved with the resolver set by [`Vm::set_resolver`]. If there is no resolver or the
+    /// library can't be resolved, execution halts with [`Halt::MissingLib`] (or
+    /// [`Halt::InvalidLib`]) and sets `st0` to `false`.
+    ///
+    /// # Returns
+    ///
+    /// Detailed [`ExecOutcome`] describing why and where the execution has halted.
+    #[inline]
+    pub fn exec_call(&mut self, program: &Program<Isa>, method: LibSite) -> ExecOutcome {
+        self.exec_traced(program, method, &mut NoTrace)
+    }
+
+    /// Invokes library method located at `method`, passing it the provided arguments and reading
+    /// back typed return values.
+    ///
+    /// Arguments are put into the registers before the execution and return values are read from
+    /// the registers following them after it, in the order defined by [`SlotAlloc`]. Return value
+    /// registers are set to `None` before the execution; the rest of the registers is left
+    /// unchanged, so the state remaining from the previous runs is visible to the method.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvokeError::Failed`] if the method execution has completed with `st0` set to
+    /// `false`, and [`InvokeError::NoResult`] if the method has not put a valid value into one of
+    /// the return value registers.
+    pub fn invoke<Args, Res>(
+        &mut self,
+        program: &Program<Isa>,
+        method: LibSite,
+        args: Args,
+    ) -> Result<Res, InvokeError>
+    where
+        Args: AbiArgs,
+        Res: AbiResults,
+    {
+        let mut alloc = SlotAlloc::new();
+        args.store_args(&mut self.registers, &mut alloc)?;
+        let mut results = alloc.clone();
+        Res::clear_results(&mut self.registers, &mut alloc)?;
+        let outcome = self.exec_call(program, method);
+        if !outcome.success {
+            return Err(InvokeError::Failed(outcome));
+        }
+        Res::load_results(&self.registers, &mut results)
+    }
+
+    /// Executes the program starting from the provided entry point, like [`Vm::exec_call`],
+    /// reporting execution events to the provided [`Tracer`].
+    ///
+    /// # Returns
+    ///
+    /// Detailed [`ExecOutcome`] describing why and where the execution has halted.
+    pub fn exec_traced(
+        &mut self,
+        program: &Program<Isa>,
+        method: LibSite,
+        tracer: &mut impl Tracer,
+    ) -> ExecOutcome {
+        let mut site = method;
+        let (pos, halt) = loop {
+            let lib = match program.lib(site.lib) {
+                Some(lib) => lib,
+                None => match resolve::<Isa>(&mut self.resolver, site.lib) {
+                    Ok(lib) => lib,
+                    Err(halt) => {
+                        self.registers.st0 = false;
+                        break (site.pos, halt);
+                    }
+                },
+            };
+            match lib.exec_as::<Isa>(site.lib, site.pos, &mut self.registers, &mut self.env, tracer)
+            {
+                Ok(next) => site = next,
+                Err(halted) => break halted,
+            }
+        };
+        let site = LibSite::with(pos, site.lib);
+        tracer.halt(site, halt, &self.registers);
+        self.capture_backtrace(method, site, |id| program.lib(id));
+        self.outcome(halt, site)
+    }
+
+    /// Executes the program with pre-decoded instructions starting from its entry point, like
+    /// [`Vm::run`].
+    ///
+    /// # Returns
+    ///
+    /// Value of the `st0` register at the end of the program execution.
+    #[inline]
+    pub fn run_compiled(&mut self, program: &CompiledProgram<Isa>) -> bool {
+        self.exec_compiled(program, program.entrypoint(), &mut NoTrace).success
+    }
+
+    /// Executes the program with pre-decoded instructions starting from the provided entry point,
+    /// reporting execution events to the provided [`Tracer`], like [`Vm::exec_traced`].
+    ///
+    /// # Returns
+    ///
+    /// Detailed [`ExecOutcome`] describing why and where the execution has halted.
+    pub fn exec_compiled(
+        &mut self,
+        program: &CompiledProgram<Isa>,
+        method: LibSite,
+        tracer: &mut impl Tracer,
+    ) -> ExecOutcome {
+        let mut site = method;
+        let (pos, halt) = loop {
+            let regs = &mut self.registers;
+            let next = match program.lib(site.lib) {
+                Some(lib) => lib.exec_with(site.pos, regs, &mut self.env, tracer),
+                None => match resolve::<Isa>(&mut self.resolver, site.lib) {
+                    Ok(lib) => lib.exec_as::<Isa>(site.lib, site.pos, regs, &mut self.env, tracer),
+                    Err(halt) => {
+                        regs.st0 = false;
+                        break (site.pos, halt);
+                    }
+                },
+            };
+            match next {
+                Ok(next) => site = next,
+                Err(halted) => break halted,
+            }
+        };
+        let site = LibSite::with(pos, site.lib);
+        tracer.halt(site, halt, &self.registers);
+        self.capture_backtrace(method, site, |id| program.lib(id).map(CompiledLib::lib));
+        self.outcome(halt, site)
+    }
+
+    /// Captures backtrace of the execution started at `method` which has halted at `site`, if
+    /// the execution has failed. Instructions are disassembled from the program libraries provided
+    /// by `lib` function or from the libraries resolved during the execution.
+    pub(crate) fn capture_backtrace<'lib>(
+        &mut self,
+        method: LibSite,
+        site: LibSite,
+        lib: impl Fn(LibId) -> Option<&'lib Lib>,
+    ) {
+        if self.registers.st0 {
+            self.backtrace = None;
+            return;
+        }
+        let resolver = &self.resolver;
+        let backtrace =
+            Backtrace::capture::<Isa>(method, self.registers.call_stack(), site, |id| {
+                lib(id).or_else(|| resolver.as_ref().and_then(|resolver| resolver.cached(id)))
+            });
+        self.backtrace = Some(backtrace);
+    }
+
+    fn outcome(&self, halt: Halt, site: LibSite) -> ExecOutcome {
+        ExecOutcome {
+            success: self.registers.st0,
+            halt,
+            site,
+            complexity: self.registers.ca0,
+            jumps: self.registers.cy0,
+        }
+    }
+}
+
+/// Execution environment provided by the virtual machine to the executed instructions
+#[derive(Debug)]
+pub(crate) struct Env<Isa>
+where
+    Isa: InstructionSet,
+{
+    /// Host functions which can be called by the programs with `hcall` instruction
+    pub hosts: HostFns,
+
+    /// Cost model pricing the executed instructions
+    pub cost: Box<dyn CostModel<Isa>>,
+}
+
+impl<Isa> Default for Env<Isa>
+where
+    Isa: InstructionSet,
+{
+    fn default() -> Self { Env { hosts: HostFns::new(), cost: Box::new(Complexity) } }
+}
+
+/// Resolves library which is not a part of the program with the provided resolver, checking that
+/// the library is supported by the instruction set.
+pub(crate) fn resolve<Isa>(
+    resolver: &mut Option<CachedResolver<Box<dyn LibResolver>>>,
+    id: LibId,
+) -> Result<&Lib, Halt>
+where
+    Isa: InstructionSet,
+{
+    let resolver = resolver.as_mut().ok_or(Halt::MissingLib(id))?;
+    let lib = resolver.lib(id).map_err(|err| match err {
+        ResolveError::NotFound(_) => Halt::MissingLib(id),
+        ResolveError::IdMismatch(..) => Halt::InvalidLib(id),
+    })?;
+    if !lib.isae.iter().all(|isa| Isa::is_supported(isa)) {
+        return Err(Halt::InvalidLib(id));
+    }
+    Ok(lib)
+}
@@ -0,0 +1,290 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Observing program execution by the virtual machine
+
+use alloc::string::ToString;
+use core::fmt::{self, Display, Write};
+
+use super::Halt;
+use crate::isa::InstructionSet;
+use crate::program::LibSite;
+use crate::reg::CoreRegs;
+
+/// Observer of the program execution.
+///
+/// Tracer is provided to [`crate::Vm::exec_traced`] and receives callbacks for each of the
+/// execution events. All callbacks have default no-op implementations, so a tracer needs to
+/// implement only the ones it is interested in.
+pub trait Tracer {
+    /// Called when the instruction at `site` is fetched and decoded, before it gets executed.
+    #[inline]
+    fn fetch<Isa>(&mut self, _site: LibSite, _instr: &Isa)
+    where
+        Isa: InstructionSet,
+    {
+    }
+
+    /// Called after the instruction at `site` is executed and its complexity is accumulated into
+    /// `ca0`, providing the resulting state of the registers.
+    #[inline]
+    fn exec<Isa>(&mut self, _site: LibSite, _instr: &Isa, _regs: &CoreRegs)
+    where
+        Isa: InstructionSet,
+    {
+    }
+
+    /// Called when the instruction at `from` performs a jump (including `exec` instruction jumping
+    /// into other library) to the location `to`.
+    #[inline]
+    fn jump(&mut self, _from: LibSite, _to: LibSite) {}
+
+    /// Called when the instruction at `from` performs a call (with `routine` or `call`
+    /// instructions) to the location `to`.
+    #[inline]
+    fn call(&mut self, _from: LibSite, _to: LibSite) {}
+
+    /// Called when the `ret` instruction at `from` returns to the location `to`.
+    #[inline]
+    fn ret(&mut self, _from: LibSite, _to: LibSite) {}
+
+    /// Called once the program execution halts at `site`.
+    #[inline]
+    fn halt(&mut self, _site: LibSite, _halt: Halt, _regs: &CoreRegs) {}
+}
+
+/// Tracer which ignores all execution events.
+///
+/// Used by the virtual machine when no tracer is provided; since all of its callbacks are no-op,
+/// it adds no runtime overhead.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct NoTrace;
+
+impl Tracer for NoTrace {}
+
+/// Writer sending its output to the standard error stream.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Stderr;
+
+#[cfg(feature = "std")]
+impl Write for Stderr {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        use std::io::Write as _;
+        std::io::stderr().write_all(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+/// Tracer writing human-readable execution log, one line per event.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct TextTracer<W>
+where
+    W: Write,
+{
+    writer: W,
+}
+
+impl<W> TextTracer<W>
+where
+    W: Write,
+{
+    /// Constructs tracer writing its output into the provided writer.
+    #[inline]
+    pub fn new(writer: W) -> Self { TextTracer { writer } }
+
+    /// Releases the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W { self.writer }
+}
+
+#[cfg(feature = "std")]
+impl TextTracer<Stderr> {
+    /// Constructs tracer writing its output into the standard error stream.
+    #[inline]
+    pub fn stderr() -> Self { TextTracer::new(Stderr) }
+}
+
+// Tracers must not affect program execution, so the write errors are ignored
+impl<W> Tracer for TextTracer<W>
+where
+    W: Write,
+{
+    fn exec<Isa>(&mut self, site: LibSite, instr: &Isa, regs: &CoreRegs)
+    where
+        Isa: InstructionSet,
+    {
+        let _ = writeln!(
+            self.writer,
+            "@{:06}> {:48}; st0={} ca0={} cy0={}",
+            site.pos,
+            instr.to_string(),
+            regs.status(),
+            regs.complexity(),
+            regs.jumps()
+        );
+    }
+
+    fn jump(&mut self, _from: LibSite, to: LibSite) {
+        let _ = writeln!(self.writer, "         -> jump {}", to);
+    }
+
+    fn call(&mut self, _from: LibSite, to: LibSite) {
+        let _ = writeln!(self.writer, "         -> call {}", to);
+    }
+
+    fn ret(&mut self, _from: LibSite, to: LibSite) {
+        let _ = writeln!(self.writer, "         <- ret {}", to);
+    }
+
+    fn halt(&mut self, site: LibSite, halt: Halt, regs: &CoreRegs) {
+        let _ = writeln!(self.writer, "halt at {}: {}; st0={}", site, halt, regs.status());
+    }
+}
+
+/// Tracer writing execution log in JSON lines format, one JSON object per event.
+///
+/// Each object has `event` field, which is one of `exec`, `jump`, `call`, `ret` and `halt`, and
+/// `lib` and `pos` fields with the location of the instruction producing the event.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct JsonTracer<W>
+where
+    W: Write,
+{
+    writer: W,
+}
+
+impl<W> JsonTracer<W>
+where
+    W: Write,
+{
+    /// Constructs tracer writing its output into the provided writer.
+    #[inline]
+    pub fn new(writer: W) -> Self { JsonTracer { writer } }
+
+    /// Releases the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W { self.writer }
+
+    fn transfer(&mut self, event: &str, from: LibSite, to: LibSite) {
+        let _ = writeln!(
+            self.writer,
+            r#"{{"event":"{}","lib":"{}","pos":{},"to_lib":"{}","to_pos":{}}}"#,
+            event, from.lib, from.pos, to.lib, to.pos
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+impl JsonTracer<Stderr> {
+    /// Constructs tracer writing its output into the standard error stream.
+    #[inline]
+    pub fn stderr() -> Self { JsonTracer::new(Stderr) }
+}
+
+impl<W> Tracer for JsonTracer<W>
+where
+    W: Write,
+{
+    fn exec<Isa>(&mut self, site: LibSite, instr: &Isa, regs: &CoreRegs)
+    where
+        Isa: InstructionSet,
+    {
+        let _ = writeln!(
+            self.writer,
+            r#"{{"event":"exec","lib":"{}","pos":{},"instr":"{}","st0":{},"ca0":{},"cy0":{}}}"#,
+            site.lib,
+            site.pos,
+            JsonEscaped(instr),
+            regs.status(),
+            regs.complexity(),
+            regs.jumps()
+        );
+    }
+
+    fn jump(&mut self, from: LibSite, to: LibSite) { self.transfer("jump", from, to) }
+
+    fn call(&mut self, from: LibSite, to: LibSite) { self.transfer("call", from, to) }
+
+    fn ret(&mut self, from: LibSite, to: LibSite) { self.transfer("ret", from, to) }
+
+    fn halt(&mut self, site: LibSite, halt: Halt, regs: &CoreRegs) {
+        let _ = writeln!(
+            self.writer,
+            r#"{{"event":"halt","lib":"{}","pos":{},"halt":"{}","st0":{},"ca0":{},"cy0":{}}}"#,
+            site.lib,
+            site.pos,
+            JsonEscaped(&halt),
+            regs.status(),
+            regs.complexity(),
+            regs.jumps()
+        );
+    }
+}
+
+/// Displays inner value escaping it for the use inside JSON string literal
+struct JsonEscaped<'a, T>(&'a T)
+where
+    T: Display;
+
+impl<'a, T> Display for JsonEscaped<'a, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Escaper<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+        impl Write for Escaper<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for ch in s.chars() {
+                    match ch {
+                        '"' => self.0.write_str("\\\"")?,
+                        '\\' => self.0.write_str("\\\\")?,
+                        '\n' => self.0.write_str("\\n")?,
+                        '\r' => self.0.write_str("\\r")?,
+                        '\t' => self.0.write_str("\\t")?,
+                        ch if (ch as u32) < 0x20 => write!(self.0, "\\u{:04x}", ch as u32)?,
+                        ch => self.0.write_char(ch)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        write!(Escaper(f), "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::*;
+    use crate::isa::{ControlFlowOp, Instr, ReservedOp};
+
+    #[test]
+    fn json_escape() {
+        assert_eq!(JsonEscaped(&"a\"b\\c\nd\u{1}").to_string(), r#"a\"b\\c\nd\u0001"#);
+    }
+
+    #[test]
+    fn json_lines() {
+        let mut tracer = JsonTracer::new(String::new());
+        let regs = CoreRegs::default();
+        let instr = Instr::<ReservedOp>::ControlFlow(ControlFlowOp::Succ);
+        tracer.exec(LibSite::default(), &instr, &regs);
+        tracer.halt(LibSite::default(), Halt::Succ, &regs);
+        let log = tracer.into_inner();
+        let mut lines = log.lines();
+        assert!(lines.next().unwrap().starts_with(r#"{"event":"exec","lib":"alu1"#));
+        assert!(lines.next().unwrap().ends_with(r#""st0":true,"ca0":0,"cy0":0}"#));
+        assert_eq!(lines.next(), None);
+    }
+}
@@ -17,8 +17,9 @@ extern crate aluvm;
 #[macro_use]
 extern crate paste;
 
-use aluvm::isa::Instr;
+use aluvm::isa::{Instr, InstructionSet};
 use aluvm::program::{Lib, Program};
+use aluvm::vm::TextTracer;
 use aluvm::Vm;
 
 #[test]
@@ -108,6 +109,21 @@ fn a_lt_u_test() {
     run(code, true)
 }
 
+#[test]
+fn cnv_asm_test() {
+    use aluvm::isa::MoveOp;
+    use aluvm::reg::{Reg32, RegA, RegF};
+
+    let code = aluasm! {
+        cnv     a8[1],a16[2];
+        cnv     f32[3],f64[4];
+    };
+    assert_eq!(code, vec![
+        Instr::Move(MoveOp::CnvA(RegA::A8, Reg32::Reg1, RegA::A16, Reg32::Reg2)),
+        Instr::Move(MoveOp::CnvF(RegF::F32, Reg32::Reg3, RegF::F64, Reg32::Reg4)),
+    ]);
+}
+
 #[test]
 fn a_lt_s_test() {
     let code = aluasm! {
@@ -133,12 +149,339 @@ fn a_lt_s_test() {
     run(code, false);
 }
 
+#[test]
+fn f512_arithm_test() {
+    let code = aluasm! {
+        put     3,a64[1];
+        put     2,a64[2];
+        cnv     a64[1],f512[1];
+        cnv     a64[2],f512[2];
+        dup     f512[2],f512[3];
+        div.n   f512[1],f512[2]; // 3 / 2
+        mul.n   f512[3],f512[2]; // 2 * 1.5
+        eq.e    f512[1],f512[2];
+        ret;
+    };
+    run(code, true)
+}
+
+#[test]
+fn f512_rounding_test() {
+    let code = aluasm! {
+        put     1,a64[1];
+        put     3,a64[2];
+        cnv     a64[1],f512[1];
+        cnv     a64[2],f512[2];
+        dup     f512[2],f512[3];
+        div.z   f512[1],f512[3]; // 1 / 3 rounded toward zero
+        dup     f512[2],f512[4];
+        div.c   f512[1],f512[4]; // 1 / 3 rounded toward +∞
+        lt.e    f512[3],f512[4];
+        ret;
+    };
+    run(code, true)
+}
+
+#[test]
+fn f512_div_zero_test() {
+    let code = aluasm! {
+        put     1,a64[1];
+        put     0,a64[2];
+        cnv     a64[1],f512[1];
+        cnv     a64[2],f512[2];
+        div.n   f512[1],f512[2];
+        ret;
+    };
+    run(code, false)
+}
+
+#[test]
+fn f512_cnv_test() {
+    let code = aluasm! {
+        put     5,a64[1];
+        put     2,a64[2];
+        cnv     a64[1],f512[1];
+        cnv     a64[2],f512[2];
+        div.n   f512[1],f512[2]; // 5 / 2
+        cnv     f512[2],f64[1];
+        cnv     f64[1],f512[3];
+        eq.e    f512[2],f512[3];
+        ret;
+    };
+    run(code, true)
+}
+
+#[test]
+fn f256_arithm_test() {
+    let code = aluasm! {
+        put     3,a64[1];
+        put     2,a64[2];
+        cnv     a64[1],f256[1];
+        cnv     a64[2],f256[2];
+        dup     f256[2],f256[3];
+        div.n   f256[1],f256[2]; // 3 / 2
+        mul.n   f256[3],f256[2]; // 2 * 1.5
+        sub.n   f256[2],f256[1]; // 3 - 3
+        lt.e    f256[1],f256[3];
+        ret;
+    };
+    run(code, true)
+}
+
+#[test]
+fn f256_cnv_test() {
+    let code = aluasm! {
+        put     5,a64[1];
+        put     2,a64[2];
+        cnv     a64[1],f256[1];
+        cnv     a64[2],f256[2];
+        div.n   f256[1],f256[2]; // 5 / 2
+        cnv     f256[2],f64[1];
+        cnv     f64[1],f256[3];
+        eq.e    f256[2],f256[3];
+        add.n   f256[3],f256[2]; // 2.5 + 2.5
+        cnv     f256[2],a64[3];
+        eq.n    a64[1],a64[3];
+        ret;
+    };
+    run(code, true)
+}
+
+#[test]
+fn f256_cnv_inexact_test() {
+    let code = aluasm! {
+        put     7,a64[1];
+        put     2,a64[2];
+        cnv     a64[1],f256[1];
+        cnv     a64[2],f256[2];
+        div.n   f256[1],f256[2]; // 7 / 2
+        cnv     f256[2],a64[3];
+        ret;
+    };
+    run(code, false)
+}
+
+#[test]
+fn cnv_signed_test() {
+    let code = aluasm! {
+        put     255,a8[1];
+        cnv     a8[1],a16[1];
+        put     65535,a16[2];
+        eq.n    a16[1],a16[2];
+        cnv     a8[1],f32[1];
+        cnv     a16[2],f32[2];
+        eq.e    f32[1],f32[2];
+        cnv     f32[1],a64[1];
+        put     0xFFFFFFFFFFFFFFFF,a64[2];
+        eq.n    a64[1],a64[2];
+        ret;
+    };
+    run(code, true)
+}
+
+#[test]
+fn cnv_overflow_test() {
+    let code = aluasm! {
+        put     300,a16[1];
+        cnv     a16[1],a8[1];
+        ret;
+    };
+    run(code, false)
+}
+
+#[test]
+#[cfg(feature = "curve25519")]
+fn ed25519_test() {
+    let code = aluasm! {
+        put     2,r256[1];
+        put     3,r256[2];
+        put     6,r256[3];
+        put     3,a256[1];
+        edgen   r256[1],r256[4];
+        edmul   r256[2],r256[4],r256[5];
+        edmul   a256[1],r256[4],r256[6];
+        edgen   r256[3],r256[7];
+        eq.n    r256[5],r256[7];
+        eq.n    r256[6],r256[7];
+        edneg   r256[4],r256[8];
+        edadd   r256[7],r256[8],r256[9];
+        edadd   r256[4],r256[4],r256[10];
+        eq.n    r256[9],r256[10];
+        ret;
+    };
+    run(code, true)
+}
+
+#[test]
+fn modular_test() {
+    let code = aluasm! {
+        put     7,a64[1];
+        put     5,a64[2];
+        put     9,a64[3];
+        addmod  a64[1],a64[2],a64[3],a64[4];
+        put     3,a64[5];
+        eq.n    a64[4],a64[5];
+        mulmod  a64[1],a64[2],a64[3],a64[4];
+        put     8,a64[5];
+        eq.n    a64[4],a64[5];
+        powmod  a64[1],a64[2],a64[3],a64[4];
+        put     4,a64[5];
+        eq.n    a64[4],a64[5];
+        invmod  a64[1],a64[3],a64[4];
+        eq.n    a64[4],a64[5];
+        ret;
+    };
+    run(code, true)
+}
+
+#[test]
+fn modular_no_inverse_test() {
+    let code = aluasm! {
+        put     3,a64[1];
+        put     9,a64[2];
+        invmod  a64[1],a64[2],a64[3];
+        ret;
+    };
+    run(code, false)
+}
+
+#[test]
+fn modular_bytecode_test() {
+    let code = aluasm! {
+        addmod  a8[1],a8[2],a8[3],a8[4];
+        mulmod  a256[31],a256[30],a256[29],a256[28];
+        powmod  a1024[17],a1024[5],a1024[1],a1024[31];
+        invmod  a512[7],a512[15],a512[31];
+        ret;
+    };
+    let lib = Lib::assemble(&code).unwrap();
+    assert_eq!(lib.disassemble::<Instr>().unwrap(), code);
+}
+
+#[test]
+fn modular_isa_test() {
+    let code = aluasm! {
+        invmod  a8[1],a8[2],a8[3];
+    };
+    let lib = Lib::assemble(&code).unwrap();
+    assert_eq!(lib.code_segment()[0], 0b10_010_111);
+    assert!(lib.isae.iter().any(|isa| isa == "MODARITH"));
+    assert!(<Instr>::is_supported("MODARITH"));
+}
+
+#[test]
+fn plain_lib_id_test() {
+    let code = aluasm! {
+        add     5,a64[1];
+        dup     a64[1],a64[2];
+        ret;
+    };
+    let lib = Lib::assemble(&code).unwrap();
+    assert!(!lib.isae.iter().any(|isa| isa == "MODARITH"));
+    // libraries not using optional ISA extensions keep their ids
+    #[cfg(not(any(feature = "secp256k1", feature = "curve25519")))]
+    assert_eq!(
+        lib.id().to_string(),
+        "alu1jvzhc03t3mxkpgsj68et8k4y37jj9rjajg8x8zpny25js0wamsdqwxx2w9"
+    );
+    #[cfg(all(feature = "secp256k1", feature = "curve25519"))]
+    assert_eq!(
+        lib.id().to_string(),
+        "alu1uk8vwsn4zyfvfp69324090m8kcn2ehl27386gnpues9per5r44csrkjcfa"
+    );
+}
+
+#[test]
+fn digest_bytecode_test() {
+    let code = aluasm! {
+        sha3    s16[1],r256[7];
+        keccak  s16[15],r256[2];
+        blake2  s16[3],r512[5];
+        blake3  s16[4],r256[7];
+        hash160 s16[5],r160[1];
+        hash256 s16[6],r256[7];
+        tagged  s16[7],s16[8],r256[14];
+        hmac    s16[1],s16[2],r256[15];
+        hmac    s16[3],s16[4],r512[8];
+        hkdfext s16[5],s16[6],r256[9];
+        hkdfexp r256[31],s16[7],a16[17],s16[15];
+        ret;
+    };
+    let lib = Lib::assemble(&code).unwrap();
+    assert_eq!(lib.disassemble::<Instr>().unwrap(), code);
+}
+
+#[test]
+fn hkdfext_alias_test() {
+    let hkdfext = aluasm! {
+        hkdfext s16[5],s16[6],r256[9];
+    };
+    let hmac = aluasm! {
+        hmac    s16[5],s16[6],r256[9];
+    };
+    assert_eq!(hkdfext, hmac);
+}
+
+#[test]
+#[cfg(feature = "secp256k1")]
+fn secp256k1_bytecode_test() {
+    let code = aluasm! {
+        ecdsa   r256[1],r512[30],r512[31];
+        ecdsa   r256[31],r512[2],s16[15];
+        schnorr r256[17],r256[31],r512[9];
+        ret;
+    };
+    let lib = Lib::assemble(&code).unwrap();
+    assert_eq!(lib.disassemble::<Instr>().unwrap(), code);
+}
+
+#[test]
+#[cfg(all(feature = "secp256k1", feature = "curve25519"))]
+fn ec_opcodes_test() {
+    let code = aluasm! {
+        secpgen r256[1],r512[2];
+        secpneg r512[1],r512[2];
+        edgen   r256[1],r256[2];
+        edneg   r256[1],r256[2];
+        edver   s16[1],r256[2],r512[3];
+        ecdsa   r256[1],r512[2],r512[3];
+        ecdsa   r256[1],r512[2],s16[3];
+        schnorr r256[1],r256[2],r512[3];
+    };
+    let opcodes = code
+        .into_iter()
+        .map(|instr| Lib::assemble(&[instr]).unwrap().code_segment()[0])
+        .collect::<Vec<_>>();
+    assert_eq!(opcodes, vec![
+        0b10_001_000,
+        0b10_001_011,
+        0b10_001_100,
+        0b10_001_111,
+        0b10_010_000,
+        0b10_010_001,
+        0b10_010_010,
+        0b10_010_011
+    ]);
+}
+
+#[test]
+#[cfg(all(feature = "secp256k1", feature = "curve25519"))]
+fn ec_instr_range_test() {
+    use aluvm::isa::{Bytecode, Curve25519Op, Secp256k1Op, Secp256k1VerifyOp};
+
+    assert_eq!(Secp256k1Op::instr_range(), 0b10_001_000..=0b10_001_011);
+    assert_eq!(Curve25519Op::instr_range(), 0b10_001_100..=0b10_010_000);
+    assert_eq!(Secp256k1VerifyOp::instr_range(), 0b10_010_001..=0b10_010_011);
+}
+
 fn run(code: Vec<Instr>, expect_success: bool) {
     let mut runtime = Vm::<Instr>::new();
 
     let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
-    let res = runtime.run(&program);
+    let mut tracer = TextTracer::new(String::new());
+    let res = runtime.exec_traced(&program, program.entrypoint(), &mut tracer).success;
 
+    println!("\nExecution trace:\n{}", tracer.into_inner());
     println!("\nVM microprocessor core state:\n{:#?}", runtime.registers());
     assert!(res == expect_success)
 }
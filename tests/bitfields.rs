@@ -0,0 +1,111 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use aluvm::isa::{BitwiseOp, BytesOp, ExtendFlag, Instr};
+use aluvm::program::{Cursor, Lib, LibSeg, Read, Write};
+use aluvm::reg::{Reg32, RegA, RegA2, RegAR, RegR, RegS};
+use amplify::num::{u3, u5, u7};
+
+#[test]
+fn cursor_straddling_fields_test() {
+    let libs = LibSeg::default();
+    let mut code = [0u8; 6];
+
+    // u5 at bit offset 13, u7 at 18 and u3 at 30 cross byte boundaries
+    let mut writer = Cursor::<_, Vec<u8>>::new(&mut code, &libs);
+    writer.write_u3(u3::with(0b101)).unwrap();
+    writer.write_u5(u5::with(0b11011)).unwrap();
+    writer.write_u5(u5::with(0b10111)).unwrap();
+    writer.write_u5(u5::with(0b01101)).unwrap();
+    writer.write_u7(u7::with(0b1110101)).unwrap();
+    writer.write_u5(u5::with(0b10011)).unwrap();
+    writer.write_u3(u3::with(0b111)).unwrap();
+    writer.write_bool(true).unwrap();
+    drop(writer);
+    assert_eq!(code, [0b1101_1101, 0b1011_0111, 0b1101_0101, 0b1110_0111, 0b0000_0011, 0]);
+
+    let mut reader = Cursor::<_, Vec<u8>>::new(&code, &libs);
+    assert_eq!(reader.read_u3().unwrap(), u3::with(0b101));
+    assert_eq!(reader.read_u5().unwrap(), u5::with(0b11011));
+    assert_eq!(reader.read_u5().unwrap(), u5::with(0b10111));
+    assert_eq!(reader.read_u5().unwrap(), u5::with(0b01101));
+    assert_eq!(reader.read_u7().unwrap(), u7::with(0b1110101));
+    assert_eq!(reader.read_u5().unwrap(), u5::with(0b10011));
+    assert_eq!(reader.read_u3().unwrap(), u3::with(0b111));
+    assert!(reader.read_bool().unwrap());
+}
+
+#[test]
+fn cursor_straddling_eof_test() {
+    let libs = LibSeg::default();
+    let mut code = [0u8; 1];
+    let mut writer = Cursor::<_, Vec<u8>>::new(&mut code, &libs);
+    writer.write_u5(u5::with(0)).unwrap();
+    // high bits of the field do not fit into the bytecode
+    assert!(writer.write_u5(u5::with(0b11111)).is_err());
+}
+
+#[test]
+fn instr_straddling_fields_test() {
+    let code = vec![
+        Instr::Bitwise(BitwiseOp::Scl(RegA2::A8, Reg32::Reg22, RegAR::A(RegA::A128), Reg32::Reg30)),
+        Instr::Bitwise(BitwiseOp::Scr(
+            RegA2::A16,
+            Reg32::Reg15,
+            RegAR::R(RegR::R256),
+            Reg32::Reg29,
+        )),
+        Instr::Bytes(BytesOp::Fill(
+            RegS::from(9),
+            Reg32::Reg27,
+            Reg32::Reg19,
+            Reg32::Reg31,
+            ExtendFlag::Fail,
+        )),
+    ];
+    let lib = Lib::assemble(&code).unwrap();
+    assert_eq!(lib.disassemble::<Instr>().unwrap(), code);
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn secp_straddling_fields_test() {
+    use aluvm::isa::Secp256k1Op;
+    use aluvm::reg::RegBlockAR;
+
+    let code = vec![Instr::Secp256k1(Secp256k1Op::Mul(
+        RegBlockAR::R,
+        Reg32::Reg31,
+        Reg32::Reg23,
+        Reg32::Reg19,
+    ))];
+    let lib = Lib::assemble(&code).unwrap();
+    assert_eq!(lib.disassemble::<Instr>().unwrap(), code);
+}
+
+#[cfg(feature = "curve25519")]
+#[test]
+fn curve25519_straddling_fields_test() {
+    use aluvm::isa::Curve25519Op;
+    use aluvm::reg::RegBlockAR;
+
+    let code = vec![
+        Instr::Curve25519(Curve25519Op::Mul(
+            RegBlockAR::R,
+            Reg32::Reg31,
+            Reg32::Reg23,
+            Reg32::Reg19,
+        )),
+        Instr::Curve25519(Curve25519Op::Add(Reg32::Reg7, Reg32::Reg31, Reg32::Reg28)),
+    ];
+    let lib = Lib::assemble(&code).unwrap();
+    assert_eq!(lib.disassemble::<Instr>().unwrap(), code);
+}
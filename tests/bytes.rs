@@ -0,0 +1,290 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use aluvm::data::ByteStr;
+use aluvm::isa::{BytesOp, DeleteFlag, InsertFlag, Instr, InstructionSet, SplitFlag};
+use aluvm::program::{Lib, LibSite};
+use aluvm::reg::{CoreRegs, Reg32, RegA, RegA2, RegS};
+
+const SRC: u8 = 0;
+const DST1: u8 = 1;
+const DST2: u8 = 2;
+
+fn s(bytes: &[u8]) -> Option<ByteStr> { Some(ByteStr::with(bytes)) }
+
+fn exec(op: BytesOp, strings: &[(u8, Option<ByteStr>)], offsets: &[u16]) -> CoreRegs {
+    let mut regs = CoreRegs::new();
+    for (idx, val) in strings {
+        regs.set_s(*idx, val.clone());
+    }
+    for (idx, offset) in [Reg32::Reg1, Reg32::Reg2].iter().zip(offsets) {
+        regs.set(RegA::A16, idx, *offset);
+    }
+    op.exec(&mut regs, LibSite::default());
+    regs
+}
+
+fn get(regs: &CoreRegs, idx: u8) -> Option<ByteStr> { regs.get_s(idx).cloned() }
+
+fn splt(flag: SplitFlag, src: &[u8], offset: u16) -> (Option<ByteStr>, Option<ByteStr>, bool) {
+    let op = BytesOp::Splt(flag, Reg32::Reg1, RegS::from(SRC), RegS::from(DST1), RegS::from(DST2));
+    let regs = exec(op, &[(SRC, s(src)), (DST1, s(b"x")), (DST2, s(b"y"))], &[offset]);
+    (get(&regs, DST1), get(&regs, DST2), regs.status())
+}
+
+#[test]
+fn splt_test() {
+    use SplitFlag::*;
+
+    let all = [
+        NoneNone,
+        NoneNoneOnEmpty,
+        NoneZeroOnEmpty,
+        ZeroZeroOnEmpty,
+        CutNone,
+        CutZero,
+        ZeroNone,
+        ZeroZero,
+    ];
+    for flag in all {
+        assert_eq!(splt(flag, b"abcdef", 1), (s(b"a"), s(b"bcdef"), true));
+        assert_eq!(splt(flag, b"abcdef", 5), (s(b"abcde"), s(b"f"), true));
+    }
+
+    // Zero offset
+    assert_eq!(splt(NoneNone, b"abc", 0), (None, None, false));
+    assert_eq!(splt(NoneNoneOnEmpty, b"abc", 0), (None, s(b"abc"), false));
+    assert_eq!(splt(NoneNoneOnEmpty, b"", 0), (None, None, false));
+    assert_eq!(splt(NoneZeroOnEmpty, b"abc", 0), (None, s(b"abc"), false));
+    assert_eq!(splt(NoneZeroOnEmpty, b"", 0), (None, s(b""), false));
+    for flag in [ZeroZeroOnEmpty, CutNone, CutZero, ZeroNone, ZeroZero] {
+        assert_eq!(splt(flag, b"abc", 0), (s(b""), s(b"abc"), true));
+        assert_eq!(splt(flag, b"", 0), (s(b""), s(b""), true));
+    }
+
+    // Offset equal to the string length
+    assert_eq!(splt(NoneNone, b"abc", 3), (None, None, false));
+    assert_eq!(splt(CutNone, b"abc", 3), (s(b"abc"), None, false));
+    assert_eq!(splt(ZeroNone, b"abc", 3), (s(b"abc"), None, false));
+    for flag in [NoneNoneOnEmpty, NoneZeroOnEmpty, ZeroZeroOnEmpty, CutZero, ZeroZero] {
+        assert_eq!(splt(flag, b"abc", 3), (s(b"abc"), s(b""), true));
+    }
+
+    // Offset exceeding the string length
+    for flag in [NoneNone, NoneNoneOnEmpty, NoneZeroOnEmpty, ZeroZeroOnEmpty] {
+        assert_eq!(splt(flag, b"abc", 4), (None, None, false));
+    }
+    assert_eq!(splt(CutNone, b"abc", 4), (s(b"abc"), None, false));
+    assert_eq!(splt(CutZero, b"abc", u16::MAX), (s(b"abc"), s(b""), false));
+    assert_eq!(splt(ZeroNone, b"abc", 4), (s(b""), None, false));
+    assert_eq!(splt(ZeroZero, b"abc", 4), (s(b""), s(b""), false));
+
+    // Undefined operands
+    let op = BytesOp::Splt(ZeroZero, Reg32::Reg1, RegS::from(SRC), RegS::from(DST1), RegS::from(2));
+    let regs = exec(op.clone(), &[(SRC, s(b"abc")), (DST1, s(b"x"))], &[]);
+    assert_eq!((get(&regs, DST1), get(&regs, DST2), regs.status()), (None, None, false));
+    let regs = exec(op, &[(DST1, s(b"x"))], &[1]);
+    assert_eq!((get(&regs, DST1), get(&regs, DST2), regs.status()), (None, None, false));
+}
+
+fn ins(flag: InsertFlag, src: &[u8], dst: &[u8], offset: u16) -> (Option<ByteStr>, bool) {
+    let op = BytesOp::Ins(flag, Reg32::Reg1, RegS::from(SRC), RegS::from(DST1));
+    let regs = exec(op, &[(SRC, s(src)), (DST1, s(dst))], &[offset]);
+    (get(&regs, DST1), regs.status())
+}
+
+#[test]
+fn ins_test() {
+    use InsertFlag::*;
+
+    let all = [FailOnLen, FailOnOffset, FailOnOffsetLen, Extend, Append, ExtendCut, Cut, Shorten];
+    for flag in all {
+        assert_eq!(ins(flag, b"xy", b"abc", 0), (s(b"xyabc"), true));
+        assert_eq!(ins(flag, b"xy", b"abc", 1), (s(b"axybc"), true));
+        assert_eq!(ins(flag, b"xy", b"abc", 3), (s(b"abcxy"), true));
+        assert_eq!(ins(flag, b"", b"abc", 2), (s(b"abc"), true));
+        assert_eq!(ins(flag, b"xy", b"", 0), (s(b"xy"), true));
+    }
+
+    // Offset exceeding the destination length
+    assert_eq!(ins(FailOnOffset, b"xy", b"abc", 5), (None, false));
+    for flag in [FailOnOffsetLen, Extend, ExtendCut] {
+        assert_eq!(ins(flag, b"xy", b"abc", 5), (s(b"abc\0\0xy"), false));
+    }
+    for flag in [FailOnLen, Append, Cut, Shorten] {
+        assert_eq!(ins(flag, b"xy", b"abc", 5), (s(b"abcxy"), false));
+    }
+
+    // Maximal length without overflow
+    let max = u16::MAX as usize;
+    let dst = vec![1u8; max - 2];
+    let mut expected = dst.clone();
+    expected.extend(b"xy");
+    for flag in all {
+        assert_eq!(ins(flag, b"xy", &dst, max as u16 - 2), (s(&expected), true));
+    }
+    let mut expected = dst.clone();
+    expected.extend([0, 0]);
+    assert_eq!(ins(Extend, b"", &dst, max as u16), (s(&expected), false));
+
+    // Overflow past 65535 bytes
+    let src = vec![2u8; 1000];
+    let dst = vec![1u8; 65000];
+    let mut joined = vec![1u8; 10];
+    joined.extend(&src);
+    joined.extend(&dst[10..]);
+    for flag in [FailOnLen, FailOnOffset, FailOnOffsetLen, Extend, Append] {
+        assert_eq!(ins(flag, &src, &dst, 10), (None, false));
+    }
+    for flag in [Cut, ExtendCut] {
+        assert_eq!(ins(flag, &src, &dst, 10), (s(&joined[..max]), false));
+    }
+    let mut shortened = vec![1u8; 10];
+    shortened.extend(&src[..max - 65000]);
+    shortened.extend(&dst[10..]);
+    assert_eq!(ins(Shorten, &src, &dst, 10), (s(&shortened), false));
+
+    // Overflow with the offset exceeding the destination length
+    let dst = vec![1u8; 64000];
+    let mut extended = dst.clone();
+    extended.resize(65000, 0);
+    extended.extend(&src);
+    assert_eq!(ins(ExtendCut, &src, &dst, 65000), (s(&extended[..max]), false));
+    assert_eq!(ins(Extend, &src, &dst, 65000), (None, false));
+    assert_eq!(ins(FailOnOffsetLen, &src, &dst, 65000), (None, false));
+    let mut appended = dst.clone();
+    appended.extend(&src);
+    assert_eq!(ins(Append, &src, &dst, 65000), (s(&appended), false));
+    let src = vec![2u8; 2000];
+    let mut appended = dst.clone();
+    appended.extend(&src);
+    assert_eq!(ins(FailOnLen, &src, &dst, 65000), (None, false));
+    assert_eq!(ins(Cut, &src, &dst, 65000), (s(&appended[..max]), false));
+    assert_eq!(ins(Shorten, &src, &dst, 65000), (s(&appended[..max]), false));
+
+    // Undefined operands
+    let op = BytesOp::Ins(Cut, Reg32::Reg1, RegS::from(SRC), RegS::from(DST1));
+    let regs = exec(op.clone(), &[(SRC, s(b"xy"))], &[0]);
+    assert_eq!((get(&regs, DST1), regs.status()), (None, false));
+    let regs = exec(op, &[(SRC, s(b"xy")), (DST1, s(b"abc"))], &[]);
+    assert_eq!((get(&regs, DST1), regs.status()), (None, false));
+}
+
+fn del(
+    flag: DeleteFlag,
+    src: &[u8],
+    offsets: [u16; 2],
+    flags: [bool; 2],
+) -> (Option<ByteStr>, bool) {
+    let op = BytesOp::Del(
+        flag,
+        RegA2::A16,
+        Reg32::Reg1,
+        RegA2::A16,
+        Reg32::Reg2,
+        flags[0],
+        flags[1],
+        RegS::from(SRC),
+        RegS::from(DST1),
+    );
+    let regs = exec(op, &[(SRC, s(src)), (DST1, s(b"x"))], &offsets);
+    (get(&regs, DST1), regs.status())
+}
+
+#[test]
+fn del_test() {
+    use DeleteFlag::*;
+
+    for flag in [None, Zero, Cut, Extend] {
+        assert_eq!(del(flag, b"abcdef", [1, 3], [true; 2]), (s(b"adef"), true));
+        assert_eq!(del(flag, b"abcdef", [3, 1], [true; 2]), (s(b"adef"), true));
+        assert_eq!(del(flag, b"abcdef", [0, 6], [true; 2]), (s(b""), true));
+        assert_eq!(del(flag, b"abcdef", [6, 6], [true; 2]), (s(b"abcdef"), true));
+        assert_eq!(del(flag, b"abcdef", [2, 2], [true; 2]), (s(b"abcdef"), true));
+    }
+
+    // Start offset exceeding the string length
+    assert_eq!(del(None, b"abc", [4, 6], [true, true]), (Option::None, false));
+    assert_eq!(del(None, b"abc", [4, 6], [false, true]), (Option::None, true));
+    assert_eq!(del(Zero, b"abc", [4, 6], [true, false]), (s(b""), false));
+    assert_eq!(del(Cut, b"abc", [6, 4], [false, true]), (s(b""), true));
+    assert_eq!(del(Extend, b"abc", [4, 6], [true, false]), (s(b"\0\0"), false));
+
+    // End offset exceeding the string length
+    assert_eq!(del(None, b"abcdef", [2, 8], [false, true]), (Option::None, false));
+    assert_eq!(del(Zero, b"abcdef", [2, 8], [true, false]), (s(b""), true));
+    assert_eq!(del(Cut, b"abcdef", [2, 8], [false, true]), (s(b"cdef"), false));
+    assert_eq!(del(Extend, b"abcdef", [8, 2], [false, true]), (s(b"cdef\0\0"), false));
+    assert_eq!(
+        del(Extend, b"abcdef", [6, u16::MAX], [false, false]).0.unwrap().len(),
+        u16::MAX - 6
+    );
+
+    // Maximal string length
+    let src = vec![1u8; u16::MAX as usize];
+    assert_eq!(del(Cut, &src, [0, u16::MAX - 1], [true; 2]), (s(&[1]), true));
+    assert_eq!(del(Cut, &src, [u16::MAX, u16::MAX], [true; 2]), (s(&src), true));
+
+    // Offsets from `a8` registers and undefined operands
+    let op = BytesOp::Del(
+        Cut,
+        RegA2::A8,
+        Reg32::Reg1,
+        RegA2::A16,
+        Reg32::Reg2,
+        false,
+        false,
+        RegS::from(SRC),
+        RegS::from(DST1),
+    );
+    let mut regs = exec(op.clone(), &[(SRC, s(b"abcdef"))], &[0, 4]);
+    regs.set(RegA::A8, Reg32::Reg1, 1u8);
+    op.exec(&mut regs, LibSite::default());
+    assert_eq!((get(&regs, DST1), regs.status()), (s(b"aef"), false));
+    let regs = exec(op.clone(), &[(SRC, s(b"abcdef"))], &[1, 4]);
+    assert_eq!((get(&regs, DST1), regs.status()), (Option::None, false));
+    let mut regs = exec(op.clone(), &[(DST1, s(b"x"))], &[1, 4]);
+    regs.set(RegA::A8, Reg32::Reg1, 1u8);
+    op.exec(&mut regs, LibSite::default());
+    assert_eq!((get(&regs, DST1), regs.status()), (Option::None, false));
+}
+
+#[test]
+fn bytecode_test() {
+    let code = vec![
+        Instr::Bytes(BytesOp::Splt(
+            SplitFlag::CutZero,
+            Reg32::Reg1,
+            RegS::from(SRC),
+            RegS::from(DST1),
+            RegS::from(DST2),
+        )),
+        Instr::Bytes(BytesOp::Ins(
+            InsertFlag::Extend,
+            Reg32::Reg2,
+            RegS::from(SRC),
+            RegS::from(DST1),
+        )),
+        Instr::Bytes(BytesOp::Del(
+            DeleteFlag::Cut,
+            RegA2::A8,
+            Reg32::Reg1,
+            RegA2::A16,
+            Reg32::Reg2,
+            true,
+            false,
+            RegS::from(SRC),
+            RegS::from(DST1),
+        )),
+    ];
+    let lib = Lib::assemble(&code).unwrap();
+    assert_eq!(lib.disassemble::<Instr>().unwrap(), code);
+}
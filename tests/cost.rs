@@ -0,0 +1,97 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+extern crate alloc;
+
+#[macro_use]
+extern crate aluvm;
+
+#[macro_use]
+extern crate paste;
+
+use aluvm::data::ByteStr;
+use aluvm::isa::{Bytecode, Instr, InstructionSet};
+use aluvm::program::{CompiledProgram, Lib, Program};
+use aluvm::vm::{CostModel, FeeSchedule};
+use aluvm::{Halt, Vm};
+
+fn program() -> (Program<Instr>, Vec<Instr>) {
+    let code = aluasm! {
+        put     7,a64[1];
+        add     5,a64[1];
+        sha2    s16[0],r256[1];
+        ret;
+    };
+    (Program::<Instr>::new(Lib::assemble(&code).unwrap()), code)
+}
+
+fn vm(model: impl CostModel<Instr> + 'static) -> Vm<Instr> {
+    let mut runtime = Vm::<Instr>::new();
+    let mut regs = runtime.snapshot();
+    regs.set_s(0u8, Some(ByteStr::with(b"hello")));
+    runtime.restore(regs);
+    runtime.set_cost_model(model);
+    runtime
+}
+
+#[test]
+fn default_cost_test() {
+    let (program, code) = program();
+    let complexity = code.iter().map(Instr::complexity).sum::<u64>();
+    assert_eq!(complexity, 2 + 1 + 100 + 2);
+
+    let mut runtime = Vm::<Instr>::new();
+    let outcome = runtime.exec(&program);
+    assert_eq!(outcome.halt, Halt::Ret);
+    assert_eq!(outcome.complexity, complexity);
+}
+
+#[test]
+fn fee_schedule_test() {
+    let (program, code) = program();
+    assert_eq!(code[0].reg_bytes(), 8);
+    assert_eq!(code[3].reg_bytes(), 0);
+
+    let mut fees = FeeSchedule::new();
+    fees.set_reg_byte_cost(3);
+    fees.set_str_byte_cost(10);
+    assert_eq!(fees.set_opcode_cost(code[2].instr_byte(), 20), None);
+    assert_eq!(fees.opcode_cost(code[2].instr_byte()), Some(20));
+    let expected = (2 + 8 * 3) + (1 + 8 * 3) + (20 + 5 * 10) + 2;
+
+    let mut runtime = vm(fees.clone());
+    let outcome = runtime.exec(&program);
+    assert_eq!(outcome.halt, Halt::Ret);
+    assert_eq!(outcome.complexity, expected);
+
+    let mut runtime = vm(fees.clone());
+    assert!(runtime.run_compiled(&CompiledProgram::from(&program)));
+    assert_eq!(runtime.registers().complexity(), expected);
+
+    fees.reset_opcode_cost(code[2].instr_byte());
+    let mut runtime = vm(fees);
+    assert_eq!(runtime.exec(&program).complexity, expected - 20 + 100);
+}
+
+#[test]
+fn cost_limit_test() {
+    let (program, _) = program();
+    let mut fees = FeeSchedule::new();
+    fees.set_str_byte_cost(1000);
+
+    let mut runtime = vm(fees);
+    runtime.set_complexity_limit(Some(1000));
+    let outcome = runtime.exec(&program);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::ComplexityLimit);
+    assert_eq!(outcome.site.pos, 3 + 4);
+    assert_eq!(outcome.complexity, 2 + 1 + 100 + 5000);
+}
@@ -0,0 +1,98 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+extern crate alloc;
+
+#[macro_use]
+extern crate aluvm;
+
+#[macro_use]
+extern crate paste;
+
+use aluvm::isa::Instr;
+use aluvm::program::{Lib, LibSite, Program};
+use aluvm::reg::{Reg32, RegA, RegS};
+use aluvm::vm::{Debugger, Pause, Watch};
+use aluvm::{Halt, Vm};
+
+fn program() -> Program<Instr> {
+    // `routine` occupies three bytes, so `succ` is at offset 3 and the routine starts at 4
+    let code = aluasm! {
+        routine 4;
+        succ;
+        put     1,a64[3];
+        put     2,a64[4];
+        ret;
+    };
+    Program::<Instr>::new(Lib::assemble(&code).unwrap())
+}
+
+#[test]
+fn step_test() {
+    let program = program();
+    let id = program.entrypoint().lib;
+    let mut debugger = Debugger::new(Vm::new(), &program);
+    assert_eq!(debugger.site(), Some(LibSite::with(0, id)));
+
+    assert_eq!(debugger.step(), Pause::Step);
+    assert_eq!(debugger.site(), Some(LibSite::with(4, id)));
+    assert_eq!(debugger.call_stack(), &[LibSite::with(3, id)]);
+
+    assert_eq!(debugger.step(), Pause::Step);
+    assert_eq!(debugger.registers().get(RegA::A64, Reg32::Reg3), 1u64.into());
+
+    let pause = debugger.run();
+    let outcome = debugger.outcome().unwrap();
+    assert_eq!(pause, Pause::Halted(outcome));
+    assert_eq!(outcome.halt, Halt::Succ);
+    assert!(outcome.success);
+    assert_eq!(debugger.site(), None);
+    assert!(debugger.call_stack().is_empty());
+    assert_eq!(debugger.step(), Pause::Halted(outcome));
+}
+
+#[test]
+fn step_over_test() {
+    let program = program();
+    let id = program.entrypoint().lib;
+    let mut debugger = Debugger::new(Vm::new(), &program);
+    assert_eq!(debugger.step_over(), Pause::Step);
+    assert_eq!(debugger.site(), Some(LibSite::with(3, id)));
+    assert!(debugger.call_stack().is_empty());
+    assert_eq!(debugger.registers().get(RegA::A64, Reg32::Reg4), 2u64.into());
+}
+
+#[test]
+fn breakpoint_test() {
+    let program = program();
+    let id = program.entrypoint().lib;
+    let mut debugger = Debugger::new(Vm::new(), &program);
+    let site = LibSite::with(3, id);
+    assert!(debugger.add_breakpoint(site));
+    assert!(!debugger.add_breakpoint(site));
+    assert_eq!(debugger.run(), Pause::Breakpoint(site));
+    assert_eq!(debugger.site(), Some(site));
+    assert!(debugger.remove_breakpoint(site));
+    assert!(matches!(debugger.run(), Pause::Halted(_)));
+}
+
+#[test]
+fn watchpoint_test() {
+    let program = program();
+    let mut debugger = Debugger::new(Vm::new(), &program);
+    let watch = Watch::Reg(RegA::A64.into(), Reg32::Reg4);
+    assert!(debugger.add_watchpoint(watch));
+    assert!(debugger.add_watchpoint(RegS::from(2)));
+    assert_eq!(debugger.run(), Pause::Watchpoint(watch));
+    assert_eq!(debugger.registers().get(RegA::A64, Reg32::Reg4), 2u64.into());
+    assert!(debugger.remove_watchpoint(watch));
+    assert!(matches!(debugger.run(), Pause::Halted(_)));
+}
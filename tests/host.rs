@@ -0,0 +1,135 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+extern crate alloc;
+
+#[macro_use]
+extern crate aluvm;
+
+#[macro_use]
+extern crate paste;
+
+use aluvm::data::Number;
+use aluvm::isa::{AluReOp, Instr, InstructionSet};
+use aluvm::program::{CompiledProgram, Lib, LibSeg, Program};
+use aluvm::reg::{CoreRegs, Reg32, RegA};
+use aluvm::{Halt, Vm};
+
+fn add(regs: &mut CoreRegs) -> bool {
+    let a: Option<Number> = regs.get(RegA::A64, Reg32::Reg1).into();
+    let b: Option<Number> = regs.get(RegA::A64, Reg32::Reg2).into();
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            regs.set(RegA::A64, Reg32::Reg3, u64::from(a) + u64::from(b));
+            true
+        }
+        _ => false,
+    }
+}
+
+fn program() -> Program<Instr> {
+    let code = aluasm! {
+        put     2,a64[1];
+        put     3,a64[2];
+        hcall   1;
+        ret;
+    };
+    Program::<Instr>::new(Lib::assemble(&code).unwrap())
+}
+
+#[test]
+fn hcall_test() {
+    let program = program();
+    let mut runtime = Vm::<Instr>::new();
+    assert!(runtime.register_host_fn(1, add).is_none());
+    assert!(runtime.host_fns().contains(1));
+
+    let outcome = runtime.exec(&program);
+    assert!(outcome.success);
+    assert_eq!(outcome.halt, Halt::Ret);
+    assert_eq!(runtime.registers().get(RegA::A64, Reg32::Reg3), 5u64.into());
+
+    let mut runtime = Vm::<Instr>::new();
+    runtime.register_host_fn(1, add);
+    assert!(runtime.run_compiled(&CompiledProgram::from(&program)));
+    assert_eq!(runtime.registers().get(RegA::A64, Reg32::Reg3), 5u64.into());
+}
+
+#[test]
+fn hcall_failure_test() {
+    let program = program();
+
+    let mut runtime = Vm::<Instr>::new();
+    let outcome = runtime.exec(&program);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::Ret);
+
+    let mut runtime = Vm::<Instr>::new();
+    runtime.register_host_fn(1, |_| false);
+    assert!(!runtime.run(&program));
+
+    runtime.register_host_fn(1, add);
+    assert!(runtime.unregister_host_fn(1).is_some());
+    assert!(!runtime.host_fns().contains(1));
+}
+
+#[test]
+fn hcall_state_test() {
+    let code = aluasm! {
+        hcall   7;
+        hcall   7;
+        hcall   7;
+        ret;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let mut runtime = Vm::<Instr>::new();
+    let mut counter = 0u8;
+    runtime.register_host_fn(7, move |regs| {
+        counter += 1;
+        regs.set(RegA::A8, Reg32::Reg1, counter);
+        true
+    });
+    assert!(runtime.run(&program));
+    assert_eq!(runtime.registers().get(RegA::A8, Reg32::Reg1), 3u8.into());
+}
+
+#[test]
+fn hcall_bytecode_test() {
+    let code = aluasm! {
+        hcall   0xABCD;
+        ret;
+    };
+    assert_eq!(code[0], Instr::AluRe(AluReOp::HCall(0xABCD)));
+    assert_eq!(code[0].to_string(), "hcall   43981");
+    let lib = Lib::assemble(&code).unwrap();
+    assert_eq!(lib.code_segment(), &[0b10_011_000, 0xCD, 0xAB, 0b00_000_111]);
+    assert_eq!(lib.disassemble::<Instr>().unwrap(), code);
+    assert!(lib.isae.iter().any(|isa| isa == "ALURE"));
+}
+
+#[test]
+fn alure_isae_test() {
+    let code = aluasm! {
+        ret;
+    };
+    let lib = Lib::assemble(&code).unwrap();
+    assert!(!lib.isae.iter().any(|isa| isa == "ALURE"));
+    assert!(<Instr>::is_supported("ALURE"));
+}
+
+#[test]
+fn alure_reserved_test() {
+    let lib = Lib::with("ALU ALURE", vec![0b10_011_111], vec![], LibSeg::default()).unwrap();
+    let code = lib.disassemble::<Instr>().unwrap();
+    assert_eq!(code.len(), 1);
+    assert!(matches!(code[0], Instr::ReservedInstruction(_)));
+    assert_eq!(code[0].to_string(), "rsrv:9F");
+}
@@ -0,0 +1,104 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+extern crate alloc;
+
+#[macro_use]
+extern crate aluvm;
+
+#[macro_use]
+extern crate paste;
+
+use aluvm::isa::{ControlFlowOp, Instr};
+use aluvm::program::{Lib, LibError, LibId, LibSite, LinkError, Program};
+
+fn callee() -> Lib {
+    let code = aluasm! {
+        put     1,a8[1];
+        ret;
+    };
+    Lib::assemble(&code).unwrap()
+}
+
+fn assemble(code: &[ControlFlowOp]) -> Lib {
+    let code = code.iter().copied().map(Instr::ControlFlow).collect::<Vec<Instr>>();
+    Lib::assemble(&code).unwrap()
+}
+
+#[test]
+fn validate_test() {
+    let callee = callee();
+    let callee_id = callee.id();
+    let code = vec![
+        ControlFlowOp::Call(LibSite::with(0, callee_id)),
+        ControlFlowOp::Jif(11),
+        ControlFlowOp::Call(LibSite::with(3, callee_id)),
+        ControlFlowOp::Succ,
+    ];
+    let main = assemble(&code);
+    let main_id = main.id();
+    let program = Program::<Instr>::with([main, callee], LibSite::with(0, main_id)).unwrap();
+    assert_eq!(program.validate(), Ok(()));
+}
+
+#[test]
+fn validate_errors_test() {
+    let callee = callee();
+    let callee_id = callee.id();
+    let code = vec![
+        ControlFlowOp::Call(LibSite::with(0, callee_id)),
+        ControlFlowOp::Jif(2),
+        ControlFlowOp::Call(LibSite::with(200, callee_id)),
+        ControlFlowOp::Routine(50),
+        ControlFlowOp::Succ,
+    ];
+    let main = assemble(&code);
+    let main_id = main.id();
+    let program = Program::<Instr>::new(main);
+    let errors = program.validate().unwrap_err();
+    assert_eq!(errors, vec![
+        LinkError::MissingLib(main_id, callee_id),
+        LinkError::CallMissingLib(LibSite::with(0, main_id), callee_id),
+        LinkError::JumpMisaligned(LibSite::with(4, main_id), 2),
+        LinkError::CallMissingLib(LibSite::with(7, main_id), callee_id),
+        LinkError::JumpMisaligned(LibSite::with(11, main_id), 50),
+    ]);
+
+    let main = assemble(&code);
+    let program = Program::<Instr>::with([main, callee], LibSite::with(100, main_id)).unwrap();
+    let errors = program.validate().unwrap_err();
+    assert_eq!(errors[0], LinkError::Entrypoint(LibSite::with(100, main_id)));
+    assert!(errors.contains(&LinkError::CallOutOfCode(
+        LibSite::with(7, main_id),
+        LibSite::with(200, callee_id)
+    )));
+    assert_eq!(
+        errors[0].to_string(),
+        format!("program entry point 100 @ {} is outside of the program code", main_id)
+    );
+}
+
+#[test]
+fn entrypoint_test() {
+    let callee = callee();
+    let id = callee.id();
+    let mut program = Program::<Instr>::new(callee.clone());
+    let unknown = LibSite::with(0, LibId::default());
+    assert_eq!(program.set_entrypoint(unknown), Err(LibError::UnknownEntrypoint(LibId::default())));
+    assert_eq!(program.entrypoint(), LibSite::with(0, id));
+    assert_eq!(program.set_entrypoint(LibSite::with(3, id)), Ok(()));
+    assert_eq!(program.entrypoint(), LibSite::with(3, id));
+
+    assert_eq!(
+        Program::<Instr>::with([callee], unknown).unwrap_err(),
+        LibError::UnknownEntrypoint(LibId::default())
+    );
+}
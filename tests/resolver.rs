@@ -0,0 +1,110 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+extern crate alloc;
+
+#[macro_use]
+extern crate aluvm;
+
+#[macro_use]
+extern crate paste;
+
+use alloc::rc::Rc;
+use core::cell::Cell;
+
+use aluvm::isa::{ControlFlowOp, Instr};
+use aluvm::program::{
+    CachedResolver, Lib, LibId, LibResolver, LibSite, MemResolver, Program, ResolveError,
+};
+use aluvm::reg::{Reg32, RegA};
+use aluvm::{Halt, Vm};
+
+fn callee() -> Lib {
+    let code = aluasm! {
+        put     7,a8[1];
+        ret;
+    };
+    Lib::assemble(&code).unwrap()
+}
+
+fn program(callee: LibId) -> Program<Instr> {
+    let code = [ControlFlowOp::Call(LibSite::with(0, callee)), ControlFlowOp::Succ];
+    let code = code.iter().copied().map(Instr::ControlFlow).collect::<Vec<Instr>>();
+    Program::new(Lib::assemble(&code).unwrap())
+}
+
+/// Resolver counting the number of requests and returning a fixed library
+struct Counting(Rc<Cell<usize>>, Lib);
+
+impl LibResolver for Counting {
+    fn resolve(&mut self, _: LibId) -> Option<Lib> {
+        self.0.set(self.0.get() + 1);
+        Some(self.1.clone())
+    }
+}
+
+#[test]
+fn resolve_test() {
+    let callee = callee();
+    let program = program(callee.id());
+
+    let mut runtime = Vm::<Instr>::new();
+    let outcome = runtime.exec(&program);
+    assert_eq!(outcome.halt, Halt::MissingLib(callee.id()));
+    assert!(!outcome.success);
+
+    let mut runtime = Vm::<Instr>::new();
+    runtime.set_resolver(vec![callee].into_iter().collect::<MemResolver>());
+    let outcome = runtime.exec(&program);
+    assert!(outcome.success);
+    assert_eq!(outcome.halt, Halt::Succ);
+    assert_eq!(runtime.registers().get(RegA::A8, Reg32::Reg1), 7u8.into());
+
+    runtime.set_resolver(MemResolver::new());
+    let outcome = runtime.exec(&program);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::MissingLib(outcome.site.lib));
+}
+
+#[test]
+fn resolve_cache_test() {
+    let callee = callee();
+    let program = program(callee.id());
+    let counter = Rc::new(Cell::new(0));
+
+    let mut runtime = Vm::<Instr>::new();
+    runtime.set_resolver(Counting(counter.clone(), callee));
+    assert!(runtime.run(&program));
+    assert!(runtime.run(&program));
+    assert_eq!(counter.get(), 1);
+}
+
+#[test]
+fn resolve_mismatch_test() {
+    let callee = callee();
+    let program = program(callee.id());
+    let other = Lib::assemble(&aluasm! { succ; }).unwrap();
+    let counter = Rc::new(Cell::new(0));
+
+    let mut runtime = Vm::<Instr>::new();
+    runtime.set_resolver(Counting(counter.clone(), other.clone()));
+    let outcome = runtime.exec(&program);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::InvalidLib(callee.id()));
+    assert_eq!(outcome.site, LibSite::with(0, callee.id()));
+
+    let mut resolver = CachedResolver::new(Counting(counter.clone(), other.clone()));
+    assert_eq!(resolver.lib(callee.id()), Err(ResolveError::IdMismatch(callee.id(), other.id())));
+    assert!(!resolver.is_cached(callee.id()));
+    assert_eq!(resolver.lib(other.id()), Ok(&other));
+    assert!(resolver.is_cached(other.id()));
+    assert_eq!(resolver.cached_count(), 1);
+}
@@ -0,0 +1,156 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+extern crate alloc;
+
+#[macro_use]
+extern crate aluvm;
+
+#[macro_use]
+extern crate paste;
+
+use aluvm::isa::Instr;
+use aluvm::program::{Lib, Program};
+use aluvm::reg::{Reg32, RegA, RegChange, RegS};
+use aluvm::Vm;
+
+fn program() -> Program<Instr> {
+    let code = aluasm! {
+        routine 4;
+        succ;
+        put     1,a64[3];
+        put     2,a64[4];
+        ret;
+    };
+    Program::<Instr>::new(Lib::assemble(&code).unwrap())
+}
+
+#[test]
+fn diff_test() {
+    let program = program();
+    let mut runtime = Vm::<Instr>::new();
+    let before = runtime.snapshot();
+    assert!(runtime.run(&program));
+    assert_eq!(before.diff(&before), vec![]);
+    assert_eq!(before.diff(runtime.registers()), vec![
+        RegChange::Num(RegA::A64.into(), Reg32::Reg3),
+        RegChange::Num(RegA::A64.into(), Reg32::Reg4),
+        RegChange::Cy0,
+        RegChange::Ca0,
+    ]);
+
+    runtime.restore(before.clone());
+    assert_eq!(runtime.registers().diff(&before), vec![]);
+}
+
+#[test]
+fn diff_display_test() {
+    assert_eq!(RegChange::Num(RegA::A64.into(), Reg32::Reg20).to_string(), "a64[20]");
+    assert_eq!(RegChange::Str(RegS::from(2)).to_string(), "s16[2]");
+    assert_eq!(RegChange::Cs0.to_string(), "cs0");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn encoding_test() {
+    use aluvm::data::encoding::{Decode, Encode};
+    use aluvm::data::{FloatLayout, Layout, Number};
+    use aluvm::reg::{CoreRegs, RegF};
+
+    let mut regs = CoreRegs::with_complexity_limit(1000);
+    regs.set(RegA::A8, Reg32::Reg1, 7u8);
+    regs.set(RegA::A1024, Reg32::Reg32, 5u8);
+    // Negative NaN and negative zero must be preserved bit-wise
+    let nan = Number::with([0x01, 0x00, 0xC0, 0xFF], Layout::float(FloatLayout::IeeeSingle));
+    let neg_zero = Number::with([0x00, 0x80], Layout::float(FloatLayout::IeeeHalf));
+    regs.set(RegF::F32, Reg32::Reg2, nan.unwrap());
+    regs.set(RegF::F16, Reg32::Reg3, neg_zero.unwrap());
+
+    let data = regs.serialize();
+    let decoded = CoreRegs::deserialize(&data).unwrap();
+    assert_eq!(decoded.diff(&regs), vec![]);
+    assert_eq!(decoded.complexity_limit(), Some(1000));
+    assert_eq!(decoded.serialize(), data);
+
+    assert_eq!(CoreRegs::default().diff(&regs), vec![
+        RegChange::Num(RegA::A8.into(), Reg32::Reg1),
+        RegChange::Num(RegA::A1024.into(), Reg32::Reg32),
+        RegChange::Num(RegF::F16.into(), Reg32::Reg3),
+        RegChange::Num(RegF::F32.into(), Reg32::Reg2),
+        RegChange::Cl0,
+    ]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn resume_test() {
+    use aluvm::data::encoding::{Decode, Encode};
+    use aluvm::reg::CoreRegs;
+    use aluvm::vm::Debugger;
+
+    let program = program();
+    let mut runtime = Vm::<Instr>::new();
+    let expected = runtime.exec(&program);
+
+    let mut debugger = Debugger::new(Vm::new(), &program);
+    debugger.step();
+    debugger.step();
+    let site = debugger.site().unwrap();
+    let data = debugger.vm().snapshot().serialize();
+    assert_eq!(debugger.call_stack().len(), 1);
+
+    let mut vm = Vm::<Instr>::new();
+    vm.restore(CoreRegs::deserialize(data).unwrap());
+    let mut debugger = Debugger::with_entrypoint(vm, &program, site);
+    assert_eq!(debugger.call_stack().len(), 1);
+    debugger.run();
+    assert_eq!(debugger.outcome(), Some(expected));
+    assert_eq!(debugger.registers().diff(runtime.registers()), vec![]);
+}
+
+#[cfg(any(feature = "serde", feature = "strict_encoding"))]
+fn snapshot() -> aluvm::reg::CoreRegs {
+    use aluvm::data::{FloatLayout, Layout, Number};
+    use aluvm::reg::RegF;
+
+    let program = program();
+    let mut runtime = Vm::<Instr>::with_limits(1000);
+    assert!(runtime.run(&program));
+    let mut regs = runtime.snapshot();
+    let nan = Number::with([0x01, 0x00, 0xC0, 0xFF], Layout::float(FloatLayout::IeeeSingle));
+    regs.set(RegF::F32, Reg32::Reg2, nan.unwrap());
+    regs
+}
+
+#[cfg(feature = "strict_encoding")]
+#[test]
+fn strict_encoding_test() {
+    use aluvm::reg::CoreRegs;
+    use strict_encoding::{StrictDecode, StrictEncode};
+
+    let regs = snapshot();
+    let data = regs.strict_serialize().unwrap();
+    let decoded = CoreRegs::strict_deserialize(&data).unwrap();
+    assert_eq!(decoded.diff(&regs), vec![]);
+    assert_eq!(decoded.strict_serialize().unwrap(), data);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_test() {
+    use aluvm::reg::CoreRegs;
+
+    let regs = snapshot();
+    let json = serde_json::to_string(&regs).unwrap();
+    let decoded: CoreRegs = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.diff(&regs), vec![]);
+    assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+}
@@ -0,0 +1,104 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+#![cfg(feature = "std")]
+
+#[macro_use]
+extern crate amplify;
+
+use std::fs;
+
+use aluvm::data::encoding::Encode;
+use aluvm::isa::{ControlFlowOp, Instr};
+use aluvm::program::{Lib, LibId, LibSeg, LibSite, LibStore, Program, StoreError};
+use aluvm::Vm;
+
+fn store(name: &str) -> LibStore {
+    let dir = std::env::temp_dir().join(format!("aluvm-test-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    LibStore::open(dir).unwrap()
+}
+
+fn lib(code: u8, deps: impl IntoIterator<Item = LibId>) -> Lib {
+    Lib::with("ALU", vec![code], vec![], LibSeg::from_iter(deps).unwrap()).unwrap()
+}
+
+#[test]
+fn save_load_test() {
+    let store = store("save-load");
+    let lib = lib(0x02, []);
+    let id = store.save(&lib).unwrap();
+    assert!(store.contains(id));
+    assert_eq!(store.load(id).unwrap(), lib);
+    assert_eq!(store.list().unwrap(), bset![id]);
+    assert_eq!(store.lib_path(id).file_name().unwrap().to_str().unwrap(), id.to_string());
+
+    fs::write(store.dir().join("README"), b"not a library").unwrap();
+    assert_eq!(store.list().unwrap(), bset![id]);
+
+    assert_eq!(store.remove(id), Ok(true));
+    assert_eq!(store.remove(id), Ok(false));
+    assert_eq!(store.load(id), Err(StoreError::NotFound(id)));
+    fs::remove_dir_all(store.dir()).unwrap();
+}
+
+#[test]
+fn verify_hash_test() {
+    let store = store("verify-hash");
+    let lib1 = lib(0x02, []);
+    let lib2 = lib(0x01, []);
+    store.save(&lib1).unwrap();
+    fs::write(store.lib_path(lib2.id()), lib1.serialize()).unwrap();
+    assert_eq!(store.load(lib2.id()), Err(StoreError::IdMismatch(lib2.id(), lib1.id())));
+
+    fs::write(store.lib_path(lib2.id()), b"\xFF").unwrap();
+    assert!(matches!(store.load(lib2.id()), Err(StoreError::Decode(..))));
+    fs::remove_dir_all(store.dir()).unwrap();
+}
+
+#[test]
+fn dependencies_gc_test() {
+    let store = store("deps");
+    let c = lib(0x01, []);
+    let b = lib(0x01, [c.id()]);
+    let a = lib(0x01, [b.id(), c.id()]);
+    let d = lib(0x02, []);
+    for lib in [&a, &b, &c, &d] {
+        store.save(lib).unwrap();
+    }
+    assert_eq!(store.list().unwrap(), bset![a.id(), b.id(), c.id(), d.id()]);
+    assert_eq!(store.dependencies([a.id()]).unwrap(), bset![a.id(), b.id(), c.id()]);
+    assert_eq!(store.dependencies([b.id(), d.id()]).unwrap(), bset![b.id(), c.id(), d.id()]);
+
+    assert_eq!(store.gc([b.id()]).unwrap(), bset![a.id(), d.id()]);
+    assert_eq!(store.list().unwrap(), bset![b.id(), c.id()]);
+
+    store.remove(c.id()).unwrap();
+    assert_eq!(store.dependencies([b.id()]), Err(StoreError::NotFound(c.id())));
+    assert_eq!(store.gc([b.id()]).unwrap(), bset![]);
+    fs::remove_dir_all(store.dir()).unwrap();
+}
+
+#[test]
+fn store_resolver_test() {
+    let store = store("resolver");
+    let callee = Lib::assemble::<Instr>(&[Instr::ControlFlow(ControlFlowOp::Ret)]).unwrap();
+    store.save(&callee).unwrap();
+
+    let code = [ControlFlowOp::Call(LibSite::with(0, callee.id())), ControlFlowOp::Succ];
+    let code = code.iter().copied().map(Instr::ControlFlow).collect::<Vec<Instr>>();
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+
+    let mut runtime = Vm::<Instr>::new();
+    runtime.set_resolver(store.clone());
+    assert!(runtime.run(&program));
+    fs::remove_dir_all(store.dir()).unwrap();
+}
@@ -0,0 +1,413 @@
+// Reference rust implementation of AluVM (arithmetic logic unit virtual machine).
+// To find more on AluVM please check <https://github.com/internet2-org/aluvm-spec>
+//
+// Designed & written in 2021-2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+// This work is donated to LNP/BP Standards Association by Pandora Core AG
+//
+// This software is licensed under the terms of MIT License.
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+extern crate alloc;
+
+#[macro_use]
+extern crate aluvm;
+
+#[macro_use]
+extern crate paste;
+
+use aluvm::data::MaybeNumber;
+use aluvm::isa::Instr;
+use aluvm::program::{CompiledLib, CompiledProgram, Lib, LibId, LibSite, Program};
+use aluvm::reg::{CoreRegs, Reg32, RegA};
+use aluvm::vm::{Frame, InvokeError, Slot, Tracer};
+use aluvm::{ExecOutcome, Halt, Vm};
+
+fn exec(code: Vec<Instr>) -> (ExecOutcome, LibId) {
+    let lib = Lib::assemble(&code).unwrap();
+    let id = lib.id();
+    let program = Program::<Instr>::new(lib);
+    let mut runtime = Vm::<Instr>::new();
+    (runtime.exec(&program), id)
+}
+
+#[test]
+fn halt_succ_test() {
+    let code = aluasm! {
+        put     1,a8[1];
+        succ;
+    };
+    let (outcome, id) = exec(code);
+    assert!(outcome.success);
+    assert_eq!(outcome.halt, Halt::Succ);
+    assert_eq!(outcome.site.lib, id);
+}
+
+#[test]
+fn halt_fail_test() {
+    let code = aluasm! {
+        fail;
+        succ;
+    };
+    let (outcome, id) = exec(code);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::Fail);
+    assert_eq!(outcome.site, LibSite::with(0, id));
+}
+
+#[test]
+fn halt_ret_test() {
+    let code = aluasm! {
+        ret;
+    };
+    let (outcome, _) = exec(code);
+    assert!(outcome.success);
+    assert_eq!(outcome.halt, Halt::Ret);
+    assert_eq!(outcome.jumps, 0);
+}
+
+#[test]
+fn halt_zero_padding_test() {
+    // Code segment is extended with zeros, which are decoded as `fail` instruction
+    let code = aluasm! {
+        put     1,a8[1];
+    };
+    let (outcome, _) = exec(code);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::Fail);
+    assert!(outcome.site.pos > 0);
+    assert!(outcome.complexity > 0);
+}
+
+#[test]
+fn halt_code_end_test() {
+    let code = aluasm! {
+        jmp     0xFFFF;
+    };
+    let (outcome, id) = exec(code);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::CodeEnd);
+    assert_eq!(outcome.site, LibSite::with(0xFFFF, id));
+}
+
+#[test]
+fn halt_jump_limit_test() {
+    let code = aluasm! {
+        jmp     0;
+    };
+    let (outcome, id) = exec(code);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::JumpLimit);
+    assert_eq!(outcome.site, LibSite::with(0, id));
+    assert_eq!(outcome.jumps, u16::MAX);
+}
+
+#[test]
+fn routine_ret_test() {
+    let code = aluasm! {
+        routine 4;
+        succ;
+        ret;
+    };
+    let (outcome, id) = exec(code);
+    assert!(outcome.success);
+    assert_eq!(outcome.halt, Halt::Succ);
+    assert_eq!(outcome.site, LibSite::with(3, id));
+    assert_eq!(outcome.jumps, 1);
+}
+
+#[test]
+fn halt_missing_lib_test() {
+    let code = aluasm! {
+        succ;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let mut runtime = Vm::<Instr>::new();
+    let site = LibSite::with(0, LibId::default());
+    let outcome = runtime.exec_call(&program, site);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::MissingLib(LibId::default()));
+    assert_eq!(outcome.site, site);
+    assert!(!runtime.call(&program, site));
+}
+
+#[test]
+fn complexity_limit_test() {
+    let code = aluasm! {
+        put     1,a8[1];
+        put     2,a8[2];
+        put     3,a8[3];
+        succ;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+
+    let mut runtime = Vm::<Instr>::with_limits(4);
+    assert_eq!(runtime.registers().complexity_limit(), Some(4));
+    let outcome = runtime.exec(&program);
+    assert!(!outcome.success);
+    assert_eq!(outcome.halt, Halt::ComplexityLimit);
+    assert_eq!(outcome.complexity, runtime.registers().complexity());
+    assert!(outcome.complexity >= 4);
+
+    let mut runtime = Vm::<Instr>::new();
+    runtime.set_complexity_limit(Some(1000));
+    let outcome = runtime.exec(&program);
+    assert!(outcome.success);
+    assert_eq!(outcome.halt, Halt::Succ);
+    assert_eq!(runtime.registers().jumps(), 0);
+    assert!(runtime.registers().complexity() < 1000);
+}
+
+#[derive(Default)]
+struct EventLog(Vec<String>);
+
+impl Tracer for EventLog {
+    fn jump(&mut self, from: LibSite, to: LibSite) {
+        self.0.push(format!("jump {}->{}", from.pos, to.pos))
+    }
+    fn call(&mut self, from: LibSite, to: LibSite) {
+        self.0.push(format!("call {}->{}", from.pos, to.pos))
+    }
+    fn ret(&mut self, from: LibSite, to: LibSite) {
+        self.0.push(format!("ret {}->{}", from.pos, to.pos))
+    }
+    fn halt(&mut self, site: LibSite, halt: Halt, _: &aluvm::reg::CoreRegs) {
+        self.0.push(format!("halt {} {:?}", site.pos, halt))
+    }
+}
+
+#[test]
+fn tracer_events_test() {
+    let code = aluasm! {
+        routine 6;
+        jmp     7;
+        ret;
+        succ;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let mut runtime = Vm::<Instr>::new();
+    let mut log = EventLog::default();
+    let outcome = runtime.exec_traced(&program, program.entrypoint(), &mut log);
+    assert!(outcome.success);
+    assert_eq!(log.0, vec!["call 0->6", "ret 6->3", "jump 3->7", "halt 7 Succ"]);
+}
+
+fn assert_compiled_eq(code: Vec<Instr>) {
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let compiled = CompiledProgram::compile(&program);
+
+    let mut runtime = Vm::<Instr>::new();
+    let mut log = EventLog::default();
+    let outcome = runtime.exec_traced(&program, program.entrypoint(), &mut log);
+
+    let mut compiled_runtime = Vm::<Instr>::new();
+    let mut compiled_log = EventLog::default();
+    let compiled_outcome =
+        compiled_runtime.exec_compiled(&compiled, compiled.entrypoint(), &mut compiled_log);
+
+    assert_eq!(outcome, compiled_outcome);
+    assert_eq!(log.0, compiled_log.0);
+    assert_eq!(format!("{:?}", runtime.registers()), format!("{:?}", compiled_runtime.registers()));
+}
+
+#[test]
+fn compiled_program_test() {
+    assert_compiled_eq(aluasm! {
+        routine 6;
+        jmp     7;
+        ret;
+        succ;
+    });
+    assert_compiled_eq(aluasm! {
+        put     1,a8[1];
+        put     2,a8[2];
+        lt.u    a8[1],a8[2];
+        ret;
+    });
+    assert_compiled_eq(aluasm! {
+        put     1,a8[1];
+    });
+    assert_compiled_eq(aluasm! {
+        jmp     0;
+    });
+    assert_compiled_eq(aluasm! {
+        jmp     0xFFFF;
+    });
+}
+
+#[test]
+fn compiled_mid_instr_jump_test() {
+    // Jump targets the middle of the `put` instruction, which is not pre-decoded
+    assert_compiled_eq(aluasm! {
+        jmp     4;
+        put     1,a8[1];
+        succ;
+    });
+}
+
+#[test]
+fn compiled_lib_test() {
+    let code = aluasm! {
+        put     1,a8[1];
+        succ;
+    };
+    let lib = Lib::assemble(&code).unwrap();
+    let id = lib.id();
+    let compiled = CompiledLib::<Instr>::compile(lib);
+    assert_eq!(compiled.id(), id);
+    assert_eq!(compiled.instructions(), &code[..]);
+    assert_eq!(compiled.instr_index(0), Some(0));
+    assert_eq!(compiled.instr_index(1), None);
+
+    let program = Program::<Instr>::new(compiled.into_lib());
+    let mut runtime = Vm::<Instr>::new();
+    assert!(runtime.run_compiled(&CompiledProgram::from(&program)));
+}
+
+#[test]
+fn call_stack_growth_test() {
+    let code = aluasm! {
+        routine 0;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let mut runtime = Vm::<Instr>::new();
+    assert!(runtime.registers().call_stack().is_empty());
+    let outcome = runtime.exec(&program);
+    assert_eq!(outcome.halt, Halt::JumpLimit);
+    assert_eq!(runtime.registers().call_stack().len(), u16::MAX as usize);
+    assert!(runtime.registers().call_stack().iter().all(|site| site.pos == 3));
+}
+
+#[test]
+fn invoke_test() {
+    let code = aluasm! {
+        add     5,a64[1];
+        mov     a64[1],a64[2];
+        dup     r256[1],r256[2];
+        ret;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let method = program.entrypoint();
+    let pubkey = [0xA5u8; 32];
+
+    let mut runtime = Vm::<Instr>::new();
+    let res: (u64, [u8; 32]) = runtime.invoke(&program, method, (100u64, pubkey)).unwrap();
+    assert_eq!(res, (105, pubkey));
+
+    let err = runtime.invoke::<_, (u64, u16)>(&program, method, 1u64).unwrap_err();
+    assert_eq!(err, InvokeError::NoResult(Slot::Num(RegA::A16.into(), Reg32::Reg1)));
+    assert_eq!(err.to_string(), "method has not returned a valid value in a16[1] register");
+
+    let err = runtime.invoke::<_, u64>(&program, method, u64::MAX).unwrap_err();
+    assert!(matches!(err, InvokeError::Failed(outcome) if outcome.halt == Halt::Ret));
+}
+
+#[test]
+fn invoke_no_result_test() {
+    let code = aluasm! {
+        add     5,a64[1];
+        mov     a64[1],a64[2];
+        ret;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let mut runtime = Vm::<Instr>::new();
+    assert_eq!(runtime.invoke::<_, u64>(&program, program.entrypoint(), 1u64), Ok(6));
+
+    // neither the argument nor the result of the previous run is returned
+    let code = aluasm! {
+        add     5,a64[1];
+        ret;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let err = runtime.invoke::<_, u64>(&program, program.entrypoint(), 1u64).unwrap_err();
+    assert_eq!(err, InvokeError::NoResult(Slot::Num(RegA::A64.into(), Reg32::Reg2)));
+}
+
+#[test]
+fn backtrace_test() {
+    let code = aluasm! {
+        routine 4;
+        ret;
+        routine 8;
+        ret;
+        fail;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let id = program.entrypoint().lib;
+    let frame = |pos: u16, instr: &str| Frame {
+        site: LibSite::with(pos, id),
+        instr: Some(instr.to_owned()),
+    };
+    let frames = [
+        frame(0, "routine 0x0004"),
+        frame(0, "routine 0x0004"),
+        frame(4, "routine 0x0008"),
+        frame(8, "fail"),
+    ];
+
+    let mut runtime = Vm::<Instr>::new();
+    let outcome = runtime.exec(&program);
+    assert_eq!(outcome.halt, Halt::Fail);
+    assert_eq!(runtime.registers().call_depth(), 2);
+    assert_eq!(runtime.registers().call_stack(), &[LibSite::with(3, id), LibSite::with(7, id)]);
+    let backtrace = runtime.backtrace().unwrap();
+    assert_eq!(backtrace.frames(), &frames[..]);
+    assert_eq!(backtrace.last(), frames.last());
+    assert_eq!(
+        backtrace.to_string(),
+        format!(
+            "#0   0 @ {id}: routine 0x0004\n#1   0 @ {id}: routine 0x0004\n#2   4 @ {id}: routine \
+             0x0008\n#3   8 @ {id}: fail",
+            id = id
+        )
+    );
+
+    let mut runtime = Vm::<Instr>::new();
+    assert!(!runtime.run_compiled(&CompiledProgram::from(&program)));
+    assert_eq!(runtime.backtrace().unwrap().frames(), &frames[..]);
+
+    let mut runtime = Vm::<Instr>::new();
+    let outcome = runtime.exec_call(&program, LibSite::with(7, id));
+    assert!(outcome.success);
+    assert_eq!(runtime.backtrace(), None);
+
+    let outcome = runtime.exec_call(&program, LibSite::with(1, id));
+    assert!(!outcome.success);
+    let backtrace = runtime.backtrace().unwrap();
+    assert_eq!(backtrace.frames()[0], Frame { site: LibSite::with(1, id), instr: None });
+}
+
+#[test]
+fn reset_test() {
+    let code = aluasm! {
+        put     7,a64[1];
+        put     3,a8[1];
+        routine 10;
+        ret;
+        fail;
+    };
+    let program = Program::<Instr>::new(Lib::assemble(&code).unwrap());
+    let mut runtime = Vm::<Instr>::with_limits(1000);
+    assert!(!runtime.run(&program));
+    assert!(runtime.backtrace().is_some());
+    assert_eq!(runtime.registers().call_depth(), 1);
+
+    // Without reset the next run inherits the state of the previous one
+    let outcome = runtime.exec(&program);
+    assert!(!outcome.success);
+    assert_eq!(outcome.jumps, 2);
+
+    runtime.reset_keeping(&[RegA::A64.into()]);
+    assert_eq!(runtime.registers().get(RegA::A64, Reg32::Reg1), 7u64.into());
+    assert_eq!(runtime.registers().get(RegA::A8, Reg32::Reg1), MaybeNumber::none());
+    assert!(runtime.registers().status());
+    assert_eq!(runtime.registers().jumps(), 0);
+    assert_eq!(runtime.registers().complexity(), 0);
+    assert_eq!(runtime.registers().call_depth(), 0);
+    assert!(runtime.backtrace().is_none());
+
+    runtime.reset();
+    assert!(runtime.registers().diff(&CoreRegs::with_complexity_limit(1000)).is_empty());
+    assert_eq!(runtime.exec(&program), Vm::<Instr>::with_limits(1000).exec(&program));
+}